@@ -0,0 +1,468 @@
+//! Calendar-aware date arithmetic.
+//!
+//! Years, months, weeks and days move the calendar date and keep the time of
+//! day; hours, minutes and seconds are always exact elapsed time, added
+//! afterwards. Two choices are left to the caller: what happens when a month
+//! step lands past the end of a shorter month ([`MonthEnd`]), and whether a
+//! calendar step across a DST change keeps the wall-clock time or the exact
+//! number of elapsed hours ([`Clock`]).
+
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, Offset, TimeZone,
+};
+use serde::Serialize;
+
+use crate::convert::parse_local;
+use crate::duration::CalendarOffset;
+use crate::error::ApiError;
+use crate::instant::{parse_instant, parse_instant_in};
+use crate::zone::{local_datetime, Zone};
+
+/// What to do when a month step lands on a day the target month lacks
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MonthEnd {
+    /// Use the last day of the month: January 31 + 1 month = February 29
+    #[default]
+    Clamp,
+    /// Carry the extra days into the next month: January 31 + 1 month = March 2
+    Overflow,
+    /// Reject the request with `day_out_of_range`
+    Error,
+}
+
+impl MonthEnd {
+    /// Accepted values of the `month_end` parameter
+    pub const NAMES: &'static [&'static str] = &["clamp", "overflow", "error"];
+
+    /// Parse a `month_end` parameter value
+    pub fn parse(value: &str) -> Result<Self, ApiError> {
+        match value.to_ascii_lowercase().as_str() {
+            "clamp" => Ok(MonthEnd::Clamp),
+            "overflow" => Ok(MonthEnd::Overflow),
+            "error" => Ok(MonthEnd::Error),
+            _ => Err(ApiError::InvalidChoice {
+                parameter: "month_end",
+                value: value.to_string(),
+                valid: Self::NAMES,
+            }),
+        }
+    }
+}
+
+/// How calendar steps (years to days) behave across a DST change
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Clock {
+    /// Keep the wall-clock time: one day after 12:00 is 12:00, even when the
+    /// day is 23 or 25 hours long
+    #[default]
+    Wall,
+    /// Keep elapsed time: one day is always 24 hours, so the wall-clock time
+    /// moves by the DST shift
+    Absolute,
+}
+
+impl Clock {
+    /// Accepted values of the `clock` parameter
+    pub const NAMES: &'static [&'static str] = &["wall", "absolute"];
+
+    /// Parse a `clock` parameter value
+    pub fn parse(value: &str) -> Result<Self, ApiError> {
+        match value.to_ascii_lowercase().as_str() {
+            "wall" => Ok(Clock::Wall),
+            "absolute" => Ok(Clock::Absolute),
+            _ => Err(ApiError::InvalidChoice {
+                parameter: "clock",
+                value: value.to_string(),
+                valid: Self::NAMES,
+            }),
+        }
+    }
+}
+
+/// How a wall-clock time was mapped to an instant in its zone
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+    /// The time occurs exactly once
+    Unique,
+    /// The time occurs twice (clocks fell back); the earlier one was taken
+    AmbiguousEarlier,
+    /// The time was skipped (clocks sprang forward); it was moved forward by
+    /// the length of the gap
+    GapShifted,
+}
+
+/// Outcome of [`add`]
+#[derive(Debug)]
+pub struct Sum {
+    pub result: DateTime<Zone>,
+    /// Whether [`MonthEnd`] had to adjust the day of month
+    pub month_end_adjusted: bool,
+    /// How the wall-clock result was resolved; `None` under [`Clock::Absolute`]
+    pub resolution: Option<Resolution>,
+}
+
+/// Map a wall-clock time to an instant, taking the earlier reading of an
+/// ambiguous time and shifting a skipped time forward past the gap
+///
+/// # Errors
+///
+/// Returns [`ApiError::OutOfRange`] when `zone`'s offset takes `local`, or
+/// the time it is shifted to, past either end of chrono's range.
+pub fn resolve(zone: Zone, local: NaiveDateTime) -> Result<(DateTime<Zone>, Resolution), ApiError> {
+    match zone.from_local_datetime(&local) {
+        LocalResult::Single(dt) => Ok((dt, Resolution::Unique)),
+        LocalResult::Ambiguous(earlier, _) => Ok((earlier, Resolution::AmbiguousEarlier)),
+        LocalResult::None => {
+            // Read the time with the offset in force before the gap; a day
+            // back is before any real transition, including whole-day ones
+            let day_before = local
+                .checked_sub_signed(Duration::days(1))
+                .ok_or(ApiError::OutOfRange)?;
+            let before = zone.offset_from_utc_datetime(&day_before).fix();
+            let utc = local
+                .checked_sub_offset(before)
+                .ok_or(ApiError::OutOfRange)?;
+            let shifted = zone.from_utc_datetime(&utc);
+            local_datetime(&shifted).ok_or(ApiError::OutOfRange)?;
+            Ok((shifted, Resolution::GapShifted))
+        }
+    }
+}
+
+/// Parse the starting point of arithmetic in `zone`
+///
+/// Accepts anything [`parse_instant`] does, or a local date or date-time
+/// without offset (`2024-01-31`, `2024-01-31T09:00`) read as wall-clock time
+/// in `zone` via [`resolve`]. An instant whose wall-clock time in `zone` is
+/// out of range is refused, as [`parse_instant_in`] does.
+///
+/// # Examples
+///
+/// ```
+/// use thedate::arithmetic::parse_start;
+/// use thedate::zone::Zone;
+///
+/// let tokyo = Zone::parse("Asia/Tokyo").unwrap();
+/// let start = parse_start("2024-01-31", tokyo).unwrap();
+/// assert_eq!(start.to_rfc3339(), "2024-01-31T00:00:00+09:00");
+/// ```
+pub fn parse_start(input: &str, zone: Zone) -> Result<DateTime<Zone>, ApiError> {
+    if parse_instant(input).is_ok() {
+        return parse_instant_in(input, zone);
+    }
+    let local = NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d")
+        .map(|date| date.and_time(Default::default()))
+        .or_else(|_| parse_local(input))
+        .map_err(|_| ApiError::InvalidInstant {
+            input: input.to_string(),
+            reason: "expected Unix time, RFC 3339, or a local YYYY-MM-DD[THH:MM[:SS]]".to_string(),
+        })?;
+    Ok(resolve(zone, local)?.0)
+}
+
+fn days_in_month(year: i32, month: u32) -> Option<u32> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)?
+    };
+    Some((next - first).num_days() as u32)
+}
+
+/// Move `date` by `months`, handling a missing day as `month_end` says
+///
+/// Returns the new date and whether the day of month was adjusted.
+fn shift_months(
+    date: NaiveDate,
+    months: i64,
+    month_end: MonthEnd,
+) -> Result<(NaiveDate, bool), ApiError> {
+    let index = i64::from(date.year()) * 12 + i64::from(date.month0());
+    let index = index.checked_add(months).ok_or(ApiError::OutOfRange)?;
+    let year = i32::try_from(index.div_euclid(12)).map_err(|_| ApiError::OutOfRange)?;
+    let month = index.rem_euclid(12) as u32 + 1;
+    let last = days_in_month(year, month).ok_or(ApiError::OutOfRange)?;
+    let day = date.day();
+    if day <= last {
+        let shifted = NaiveDate::from_ymd_opt(year, month, day).ok_or(ApiError::OutOfRange)?;
+        return Ok((shifted, false));
+    }
+    let end = NaiveDate::from_ymd_opt(year, month, last).ok_or(ApiError::OutOfRange)?;
+    match month_end {
+        MonthEnd::Clamp => Ok((end, true)),
+        MonthEnd::Overflow => end
+            .checked_add_signed(Duration::days(i64::from(day - last)))
+            .map(|date| (date, true))
+            .ok_or(ApiError::OutOfRange),
+        MonthEnd::Error => Err(ApiError::DayOutOfRange {
+            year,
+            month,
+            day,
+            days_in_month: last,
+        }),
+    }
+}
+
+/// Exact elapsed-time part of `offset` (hours and smaller)
+fn exact_part(offset: &CalendarOffset) -> Option<Duration> {
+    Duration::try_hours(offset.hours)?
+        .checked_add(&Duration::try_minutes(offset.minutes)?)?
+        .checked_add(&Duration::try_seconds(offset.seconds)?)?
+        .checked_add(&Duration::nanoseconds(offset.nanoseconds))
+}
+
+/// Add `offset` to `start`
+///
+/// Years and months are applied first, then weeks and days, both on the
+/// calendar date in `start`'s zone; hours, minutes and seconds are added
+/// last as exact time.
+///
+/// # Errors
+///
+/// [`ApiError::DayOutOfRange`] under [`MonthEnd::Error`] when the month step
+/// lands on a missing day, or [`ApiError::OutOfRange`] if the result cannot
+/// be represented.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use thedate::arithmetic::{add, Clock, MonthEnd};
+/// use thedate::duration::CalendarOffset;
+/// use thedate::zone::Zone;
+///
+/// let start = Zone::default().at(Utc.with_ymd_and_hms(2024, 1, 31, 0, 0, 0).unwrap());
+/// let offset = CalendarOffset { months: 1, ..Default::default() };
+/// let sum = add(&start, &offset, MonthEnd::Clamp, Clock::Wall).unwrap();
+/// assert_eq!(sum.result.to_rfc3339(), "2024-02-29T00:00:00+00:00");
+/// assert!(sum.month_end_adjusted);
+/// ```
+pub fn add(
+    start: &DateTime<Zone>,
+    offset: &CalendarOffset,
+    month_end: MonthEnd,
+    clock: Clock,
+) -> Result<Sum, ApiError> {
+    let local = local_datetime(start).ok_or(ApiError::OutOfRange)?;
+    let months = offset
+        .years
+        .checked_mul(12)
+        .and_then(|months| months.checked_add(offset.months))
+        .ok_or(ApiError::OutOfRange)?;
+    let (date, month_end_adjusted) = shift_months(local.date(), months, month_end)?;
+    let days = offset
+        .weeks
+        .checked_mul(7)
+        .and_then(|days| days.checked_add(offset.days))
+        .and_then(Duration::try_days)
+        .ok_or(ApiError::OutOfRange)?;
+    let target = date
+        .checked_add_signed(days)
+        .ok_or(ApiError::OutOfRange)?
+        .and_time(local.time());
+
+    let (moved, resolution) = match clock {
+        Clock::Wall => {
+            let (dt, resolution) = resolve(start.timezone(), target)?;
+            (dt, Some(resolution))
+        }
+        Clock::Absolute => {
+            let dt = start
+                .checked_add_signed(target - local)
+                .ok_or(ApiError::OutOfRange)?;
+            (dt, None)
+        }
+    };
+    let result = exact_part(offset)
+        .and_then(|exact| moved.checked_add_signed(exact))
+        .ok_or(ApiError::OutOfRange)?;
+    Ok(Sum {
+        result,
+        month_end_adjusted,
+        resolution,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{SecondsFormat, Utc};
+
+    fn start(input: &str, tz: &str) -> DateTime<Zone> {
+        parse_start(input, Zone::parse(tz).unwrap()).unwrap()
+    }
+
+    fn offset(s: &str) -> CalendarOffset {
+        s.parse().unwrap()
+    }
+
+    fn add_str(input: &str, tz: &str, duration: &str, month_end: MonthEnd, clock: Clock) -> String {
+        add(&start(input, tz), &offset(duration), month_end, clock)
+            .unwrap()
+            .result
+            .to_rfc3339_opts(SecondsFormat::AutoSi, false)
+    }
+
+    #[test]
+    fn test_month_end_policies() {
+        let s = start("2024-01-31", "UTC");
+        let clamp = add(&s, &offset("P1M"), MonthEnd::Clamp, Clock::Wall).unwrap();
+        assert_eq!(clamp.result.date_naive().to_string(), "2024-02-29");
+        assert!(clamp.month_end_adjusted);
+
+        let overflow = add(&s, &offset("P1M"), MonthEnd::Overflow, Clock::Wall).unwrap();
+        assert_eq!(overflow.result.date_naive().to_string(), "2024-03-02");
+
+        let err = add(&s, &offset("P1M"), MonthEnd::Error, Clock::Wall).unwrap_err();
+        assert!(matches!(
+            err,
+            ApiError::DayOutOfRange {
+                month: 2,
+                day: 31,
+                days_in_month: 29,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_month_then_days() {
+        // Months first, then days: 2024-01-31 + 1 month = 02-29, - 3 days = 02-26
+        let s = start("2024-01-31", "UTC");
+        let offset = CalendarOffset {
+            months: 1,
+            days: -3,
+            ..Default::default()
+        };
+        let sum = add(&s, &offset, MonthEnd::Clamp, Clock::Wall).unwrap();
+        assert_eq!(sum.result.date_naive().to_string(), "2024-02-26");
+    }
+
+    #[test]
+    fn test_negative_months_cross_year() {
+        assert_eq!(
+            add_str("2024-03-31", "UTC", "-P13M", MonthEnd::Clamp, Clock::Wall),
+            "2023-02-28T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_leap_day_plus_year() {
+        assert_eq!(
+            add_str("2024-02-29", "UTC", "P1Y", MonthEnd::Clamp, Clock::Wall),
+            "2025-02-28T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_day_across_dst_wall_vs_absolute() {
+        // Berlin springs forward on 2024-03-31
+        let wall = add_str(
+            "2024-03-30T12:00",
+            "Europe/Berlin",
+            "P1D",
+            MonthEnd::Clamp,
+            Clock::Wall,
+        );
+        assert_eq!(wall, "2024-03-31T12:00:00+02:00");
+        let absolute = add_str(
+            "2024-03-30T12:00",
+            "Europe/Berlin",
+            "P1D",
+            MonthEnd::Clamp,
+            Clock::Absolute,
+        );
+        assert_eq!(absolute, "2024-03-31T13:00:00+02:00");
+    }
+
+    #[test]
+    fn test_hours_are_exact_time() {
+        assert_eq!(
+            add_str(
+                "2024-03-31T01:30",
+                "Europe/Berlin",
+                "PT1H",
+                MonthEnd::Clamp,
+                Clock::Wall
+            ),
+            "2024-03-31T03:30:00+02:00"
+        );
+    }
+
+    #[test]
+    fn test_result_in_gap_is_shifted_forward() {
+        let s = start("2024-03-30T02:30", "Europe/Berlin");
+        let sum = add(&s, &offset("P1D"), MonthEnd::Clamp, Clock::Wall).unwrap();
+        assert_eq!(sum.resolution, Some(Resolution::GapShifted));
+        assert_eq!(
+            sum.result.to_rfc3339_opts(SecondsFormat::AutoSi, false),
+            "2024-03-31T03:30:00+02:00"
+        );
+    }
+
+    #[test]
+    fn test_result_in_overlap_takes_earlier() {
+        let s = start("2024-10-26T02:30", "Europe/Berlin");
+        let sum = add(&s, &offset("P1D"), MonthEnd::Clamp, Clock::Wall).unwrap();
+        assert_eq!(sum.resolution, Some(Resolution::AmbiguousEarlier));
+        assert_eq!(
+            sum.result.with_timezone(&Utc).to_rfc3339(),
+            "2024-10-27T00:30:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let s = start("2024-01-01", "UTC");
+        let offset = CalendarOffset {
+            years: 1_000_000,
+            ..Default::default()
+        };
+        assert!(matches!(
+            add(&s, &offset, MonthEnd::Clamp, Clock::Wall),
+            Err(ApiError::OutOfRange)
+        ));
+    }
+
+    #[test]
+    fn test_resolve_past_the_ends_of_the_range() {
+        let first = NaiveDate::MIN.and_hms_opt(1, 0, 0).unwrap();
+        let last = NaiveDate::MAX.and_hms_opt(23, 0, 0).unwrap();
+        let kiritimati = Zone::parse("+14:00").unwrap();
+        let baker = Zone::parse("-12:00").unwrap();
+        assert!(matches!(
+            resolve(kiritimati, first),
+            Err(ApiError::OutOfRange)
+        ));
+        assert!(matches!(resolve(baker, last), Err(ApiError::OutOfRange)));
+        let (dt, resolution) = resolve(baker, first).unwrap();
+        assert_eq!(resolution, Resolution::Unique);
+        assert_eq!(local_datetime(&dt), Some(first));
+    }
+
+    #[test]
+    fn test_parse_start_forms() {
+        assert_eq!(
+            start("1710504045", "UTC").to_rfc3339(),
+            "2024-03-15T12:00:45+00:00"
+        );
+        assert!(parse_start("31/01/2024", Zone::default()).is_err());
+    }
+
+    #[test]
+    fn test_parse_choices() {
+        assert_eq!(MonthEnd::parse("Overflow").unwrap(), MonthEnd::Overflow);
+        assert_eq!(Clock::parse("absolute").unwrap(), Clock::Absolute);
+        assert!(matches!(
+            Clock::parse("solar"),
+            Err(ApiError::InvalidChoice {
+                parameter: "clock",
+                ..
+            })
+        ));
+    }
+}
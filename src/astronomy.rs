@@ -0,0 +1,525 @@
+//! Positions of the Sun and Moon for calendar calculations.
+//!
+//! Times are *moments*: fractional R.D. day numbers in Universal Time, as in
+//! Reingold & Dershowitz, *Calendrical Calculations*, so that a moment's
+//! floor is its R.D. date (see [`crate::calendar`]). The solar longitude
+//! uses their 49-term series, accurate to about a minute of time for the
+//! solar terms; new moons use Meeus, *Astronomical Algorithms*, chapter 49,
+//! accurate to seconds. Both work in Terrestrial Time, converted with the
+//! ΔT polynomials of Espenak & Meeus.
+//!
+//! Julian dates and sidereal times take UTC as Universal Time, which is
+//! within 0.9 s of UT1. Sidereal time follows the IAU 1982 expression for
+//! mean sidereal time, with the equation of the equinoxes from Meeus,
+//! chapter 22, good to a few hundredths of a second of time.
+
+use chrono::{DateTime, Utc};
+
+/// R.D. moment of 1970-01-01T00:00:00Z
+const UNIX_EPOCH: f64 = 719_163.0;
+/// R.D. moment of noon on 2000-01-01 (J2000.0)
+const J2000: f64 = 730_120.5;
+/// Julian date of R.D. moment 0
+const JD_EPOCH: f64 = 1_721_424.5;
+/// Julian date of J2000.0
+pub const J2000_JULIAN_DATE: f64 = 2_451_545.0;
+/// Julian date of the Modified Julian Date epoch, 1858-11-17T00:00:00Z
+pub const MJD_EPOCH: f64 = 2_400_000.5;
+/// Julian date of the Truncated Julian Day epoch, 1968-05-24T00:00:00Z
+pub const TJD_EPOCH: f64 = 2_440_000.5;
+/// Mean days from equinox to equinox
+pub const MEAN_TROPICAL_YEAR: f64 = 365.242_189;
+/// Mean days from new moon to new moon
+pub const MEAN_SYNODIC_MONTH: f64 = 29.530_588_861;
+
+/// The moment of `instant`
+pub fn moment(instant: DateTime<Utc>) -> f64 {
+    let seconds = instant.timestamp() as f64 + f64::from(instant.timestamp_subsec_nanos()) / 1e9;
+    UNIX_EPOCH + seconds / 86_400.0
+}
+
+/// The instant of `moment`, to the nearest second; `None` outside chrono's
+/// range
+pub fn instant(moment: f64) -> Option<DateTime<Utc>> {
+    let seconds = ((moment - UNIX_EPOCH) * 86_400.0).round();
+    if !seconds.is_finite() || seconds.abs() > i64::MAX as f64 {
+        return None;
+    }
+    DateTime::from_timestamp(seconds as i64, 0)
+}
+
+/// Julian date of `instant`: days since noon UT on 1 January 4713 BCE
+/// (Julian)
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use thedate::astronomy::julian_date;
+///
+/// let j2000 = Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap();
+/// assert_eq!(julian_date(j2000), 2_451_545.0);
+/// ```
+pub fn julian_date(instant: DateTime<Utc>) -> f64 {
+    // From Unix time directly, which keeps microseconds in an f64
+    let days = instant.timestamp().div_euclid(86_400);
+    let seconds = instant.timestamp().rem_euclid(86_400) as f64
+        + f64::from(instant.timestamp_subsec_nanos()) / 1e9;
+    (UNIX_EPOCH + JD_EPOCH) + days as f64 + seconds / 86_400.0
+}
+
+/// Greenwich mean sidereal time at `instant`, in hours
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use thedate::astronomy::greenwich_mean_sidereal_time;
+///
+/// // Meeus, Astronomical Algorithms, example 12.a: 13h10m46.3668s
+/// let instant = Utc.with_ymd_and_hms(1987, 4, 10, 0, 0, 0).unwrap();
+/// let gmst = greenwich_mean_sidereal_time(instant);
+/// assert!((gmst - 13.179_546_33).abs() < 1e-6);
+/// ```
+pub fn greenwich_mean_sidereal_time(instant: DateTime<Utc>) -> f64 {
+    let days = julian_date(instant) - J2000_JULIAN_DATE;
+    let t = days / 36_525.0;
+    let degrees = 280.460_618_37 + 360.985_647_366_29 * days + 0.000_387_933 * t * t
+        - t.powi(3) / 38_710_000.0;
+    degrees.rem_euclid(360.0) / 15.0
+}
+
+/// Greenwich apparent sidereal time at `instant`, in hours: mean sidereal
+/// time corrected for nutation
+pub fn greenwich_apparent_sidereal_time(instant: DateTime<Utc>) -> f64 {
+    let (longitude, obliquity) = nutation(moment(instant));
+    let equation_of_equinoxes = longitude * cos_degrees(obliquity) / 15.0;
+    (greenwich_mean_sidereal_time(instant) + equation_of_equinoxes).rem_euclid(24.0)
+}
+
+/// Local sidereal time, in hours, from a Greenwich sidereal time and a
+/// `longitude` in degrees east
+///
+/// # Examples
+///
+/// ```
+/// use thedate::astronomy::local_sidereal_time;
+///
+/// assert_eq!(local_sidereal_time(1.0, -30.0), 23.0);
+/// ```
+pub fn local_sidereal_time(greenwich: f64, longitude: f64) -> f64 {
+    (greenwich + longitude / 15.0).rem_euclid(24.0)
+}
+
+/// Nutation in longitude and the true obliquity of the ecliptic at
+/// `moment`, both in degrees, after Meeus, chapter 22
+fn nutation(moment: f64) -> (f64, f64) {
+    let c = julian_centuries(moment);
+    let node = 125.044_52 - 1_934.136_261 * c;
+    let sun = 280.466_5 + 36_000.769_8 * c;
+    let moon = 218.316_5 + 481_267.881_3 * c;
+    let longitude =
+        -17.20 * sin_degrees(node) - 1.32 * sin_degrees(2.0 * sun) - 0.23 * sin_degrees(2.0 * moon)
+            + 0.21 * sin_degrees(2.0 * node);
+    let obliquity =
+        9.20 * cos_degrees(node) + 0.57 * cos_degrees(2.0 * sun) + 0.10 * cos_degrees(2.0 * moon)
+            - 0.09 * cos_degrees(2.0 * node);
+    let mean_obliquity = 84_381.448 - 46.815_0 * c - 0.000_59 * c * c + 0.001_813 * c.powi(3);
+    (longitude / 3_600.0, (mean_obliquity + obliquity) / 3_600.0)
+}
+
+/// Approximate calendar year of `moment`, for ΔT
+fn decimal_year(moment: f64) -> f64 {
+    2000.0 + (moment - J2000) / 365.2425
+}
+
+/// ΔT = TT − UT in seconds for a decimal `year`, after Espenak & Meeus
+///
+/// The polynomials cover 1800-2150 from observations and projections, and
+/// a parabola fitted to historical records elsewhere, where the error grows
+/// to minutes within a few centuries.
+///
+/// # Examples
+///
+/// ```
+/// use thedate::astronomy::delta_t;
+///
+/// assert!((delta_t(2000.0) - 63.86).abs() < 0.01);
+/// ```
+pub fn delta_t(year: f64) -> f64 {
+    let parabola = |y: f64| {
+        let u = (y - 1820.0) / 100.0;
+        -20.0 + 32.0 * u * u
+    };
+    match year {
+        y if y < 1800.0 => parabola(y),
+        y if y < 1860.0 => {
+            let t = y - 1800.0;
+            13.72 - 0.332_447 * t + 0.006_861_2 * t.powi(2) + 0.004_111_6 * t.powi(3)
+                - 0.000_374_36 * t.powi(4)
+                + 0.000_012_127_2 * t.powi(5)
+                - 0.000_000_169_9 * t.powi(6)
+                + 0.000_000_000_875 * t.powi(7)
+        }
+        y if y < 1900.0 => {
+            let t = y - 1860.0;
+            7.62 + 0.573_7 * t - 0.251_754 * t.powi(2) + 0.016_806_68 * t.powi(3)
+                - 0.000_447_362_4 * t.powi(4)
+                + t.powi(5) / 233_174.0
+        }
+        y if y < 1920.0 => {
+            let t = y - 1900.0;
+            -2.79 + 1.494_119 * t - 0.059_893_9 * t.powi(2) + 0.006_196_6 * t.powi(3)
+                - 0.000_197 * t.powi(4)
+        }
+        y if y < 1941.0 => {
+            let t = y - 1920.0;
+            21.20 + 0.844_93 * t - 0.076_100 * t.powi(2) + 0.002_093_6 * t.powi(3)
+        }
+        y if y < 1961.0 => {
+            let t = y - 1950.0;
+            29.07 + 0.407 * t - t.powi(2) / 233.0 + t.powi(3) / 2547.0
+        }
+        y if y < 1986.0 => {
+            let t = y - 1975.0;
+            45.45 + 1.067 * t - t.powi(2) / 260.0 - t.powi(3) / 718.0
+        }
+        y if y < 2005.0 => {
+            let t = y - 2000.0;
+            63.86 + 0.334_5 * t - 0.060_374 * t.powi(2)
+                + 0.001_727_5 * t.powi(3)
+                + 0.000_651_814 * t.powi(4)
+                + 0.000_023_735_99 * t.powi(5)
+        }
+        y if y < 2050.0 => {
+            let t = y - 2000.0;
+            62.92 + 0.322_17 * t + 0.005_589 * t.powi(2)
+        }
+        y if y < 2150.0 => parabola(y) - 0.562_8 * (2150.0 - y),
+        y => parabola(y),
+    }
+}
+
+/// Terrestrial Time moment of a Universal Time one
+fn dynamical_from_universal(moment: f64) -> f64 {
+    moment + delta_t(decimal_year(moment)) / 86_400.0
+}
+
+fn universal_from_dynamical(moment: f64) -> f64 {
+    moment - delta_t(decimal_year(moment)) / 86_400.0
+}
+
+/// Julian centuries of Terrestrial Time since J2000.0
+fn julian_centuries(moment: f64) -> f64 {
+    (dynamical_from_universal(moment) - J2000) / 36_525.0
+}
+
+fn sin_degrees(degrees: f64) -> f64 {
+    degrees.to_radians().sin()
+}
+
+fn cos_degrees(degrees: f64) -> f64 {
+    degrees.to_radians().cos()
+}
+
+/// Amplitude, phase and rate (degrees, per century) of the solar series
+#[rustfmt::skip]
+const SOLAR_TERMS: [(f64, f64, f64); 49] = [
+    (403_406.0, 270.548_61, 0.928_789_2), (195_207.0, 340.191_28, 35_999.137_695_8),
+    (119_433.0, 63.918_54, 35_999.408_966_6), (112_392.0, 331.262_20, 35_998.728_738_5),
+    (3_891.0, 317.843, 71_998.202_61), (2_819.0, 86.631, 71_998.440_3),
+    (1_721.0, 240.052, 36_000.357_26), (660.0, 310.26, 71_997.481_2),
+    (350.0, 247.23, 32_964.467_8), (334.0, 260.87, -19.441_0),
+    (314.0, 297.82, 445_267.111_7), (268.0, 343.14, 45_036.884_0),
+    (242.0, 166.79, 3.100_8), (234.0, 81.53, 22_518.443_4),
+    (158.0, 3.50, -19.973_9), (132.0, 132.75, 65_928.934_5),
+    (129.0, 182.95, 9_038.029_3), (114.0, 162.03, 3_034.768_4),
+    (99.0, 29.8, 33_718.148), (93.0, 266.4, 3_034.448),
+    (86.0, 249.2, -2_280.773), (78.0, 157.6, 29_929.992),
+    (72.0, 257.8, 31_556.493), (68.0, 185.1, 149.588),
+    (64.0, 69.9, 9_037.750), (46.0, 8.0, 107_997.405),
+    (38.0, 197.1, -4_444.176), (37.0, 250.4, 151.771),
+    (32.0, 65.3, 67_555.316), (29.0, 162.7, 31_556.080),
+    (28.0, 341.5, -4_561.540), (27.0, 291.6, 107_996.706),
+    (27.0, 98.5, 1_221.655), (25.0, 146.7, 62_894.167),
+    (24.0, 110.0, 31_437.369), (21.0, 5.2, 14_578.298),
+    (21.0, 342.6, -31_931.757), (20.0, 230.9, 34_777.243),
+    (18.0, 256.1, 1_221.999), (17.0, 45.3, 62_894.511),
+    (14.0, 242.9, -4_442.039), (13.0, 115.2, 107_997.909),
+    (13.0, 151.8, 119.066), (13.0, 285.3, 16_859.071),
+    (12.0, 53.3, -4.578), (10.0, 126.6, 26_895.292),
+    (10.0, 205.7, -39.127), (10.0, 85.9, 12_297.536),
+    (10.0, 146.1, 90_073.778),
+];
+
+/// Apparent geocentric ecliptic longitude of the Sun at `moment`, in
+/// degrees from the March equinox
+pub(crate) fn solar_longitude_at(moment: f64) -> f64 {
+    let c = julian_centuries(moment);
+    let series: f64 = SOLAR_TERMS
+        .iter()
+        .map(|&(x, y, z)| x * sin_degrees(y + z * c))
+        .sum();
+    let longitude = 282.777_183_4 + 36_000.769_537_44 * c + 0.000_005_729_577_951_308_232 * series;
+    let aberration = 0.000_097_4 * cos_degrees(177.63 + 35_999.018_48 * c) - 0.005_575;
+    let a = 124.90 - 1_934.134 * c + 0.002_063 * c * c;
+    let b = 201.11 + 72_001.537_7 * c + 0.000_57 * c * c;
+    let nutation = -0.004_778 * sin_degrees(a) - 0.000_366_7 * sin_degrees(b);
+    (longitude + aberration + nutation).rem_euclid(360.0)
+}
+
+/// First moment at or after `moment` when the Sun reaches `longitude`
+pub(crate) fn solar_longitude_after_moment(longitude: f64, moment: f64) -> f64 {
+    let rate = MEAN_TROPICAL_YEAR / 360.0;
+    let estimate = moment + rate * (longitude - solar_longitude_at(moment)).rem_euclid(360.0);
+    let (mut low, mut high) = (moment.max(estimate - 5.0), estimate + 5.0);
+    // Bisect to well under a second
+    while high - low > 1e-6 {
+        let middle = (low + high) / 2.0;
+        if (solar_longitude_at(middle) - longitude).rem_euclid(360.0) < 180.0 {
+            high = middle;
+        } else {
+            low = middle;
+        }
+    }
+    (low + high) / 2.0
+}
+
+/// Moment close to and not after `moment` when the Sun was at `longitude`
+pub(crate) fn estimate_prior_solar_longitude(longitude: f64, moment: f64) -> f64 {
+    let rate = MEAN_TROPICAL_YEAR / 360.0;
+    let estimate = moment - rate * (solar_longitude_at(moment) - longitude).rem_euclid(360.0);
+    let error = (solar_longitude_at(estimate) - longitude + 180.0).rem_euclid(360.0) - 180.0;
+    moment.min(estimate - rate * error)
+}
+
+/// Periodic terms of the new moon: coefficient, power of the eccentricity
+/// factor, and multiples of the Sun's anomaly, the Moon's anomaly and the
+/// Moon's argument of latitude
+#[rustfmt::skip]
+const NEW_MOON_TERMS: [(f64, i32, f64, f64, f64); 24] = [
+    (-0.407_20, 0, 0.0, 1.0, 0.0), (0.172_41, 1, 1.0, 0.0, 0.0),
+    (0.016_08, 0, 0.0, 2.0, 0.0), (0.010_39, 0, 0.0, 0.0, 2.0),
+    (0.007_39, 1, -1.0, 1.0, 0.0), (-0.005_14, 1, 1.0, 1.0, 0.0),
+    (0.002_08, 2, 2.0, 0.0, 0.0), (-0.001_11, 0, 0.0, 1.0, -2.0),
+    (-0.000_57, 0, 0.0, 1.0, 2.0), (0.000_56, 1, 1.0, 2.0, 0.0),
+    (-0.000_42, 0, 0.0, 3.0, 0.0), (0.000_42, 1, 1.0, 0.0, 2.0),
+    (0.000_38, 1, 1.0, 0.0, -2.0), (-0.000_24, 1, -1.0, 2.0, 0.0),
+    (-0.000_07, 0, 2.0, 1.0, 0.0), (0.000_04, 0, 0.0, 2.0, -2.0),
+    (0.000_04, 0, 3.0, 0.0, 0.0), (0.000_03, 0, 1.0, 1.0, -2.0),
+    (0.000_03, 0, 0.0, 2.0, 2.0), (-0.000_03, 0, 1.0, 1.0, 2.0),
+    (0.000_03, 0, -1.0, 1.0, 2.0), (-0.000_02, 0, -1.0, 1.0, -2.0),
+    (-0.000_02, 0, 1.0, 3.0, 0.0), (0.000_02, 0, 0.0, 4.0, 0.0),
+];
+
+/// Planetary perturbations of the new moon: coefficient, phase and rate
+/// per lunation
+#[rustfmt::skip]
+const PLANETARY_TERMS: [(f64, f64, f64); 13] = [
+    (0.000_165, 251.88, 0.016_321), (0.000_164, 251.83, 26.651_886),
+    (0.000_126, 349.42, 36.412_478), (0.000_110, 84.66, 18.206_239),
+    (0.000_062, 141.74, 53.303_771), (0.000_060, 207.14, 2.453_732),
+    (0.000_056, 154.84, 7.306_860), (0.000_047, 34.52, 27.261_239),
+    (0.000_042, 207.19, 0.121_824), (0.000_040, 291.34, 1.844_379),
+    (0.000_037, 161.72, 24.198_154), (0.000_035, 239.56, 25.513_099),
+    (0.000_023, 331.55, 3.592_518),
+];
+
+/// Moment of the `k`th new moon after the one of 6 January 2000
+fn nth_new_moon(k: i64) -> f64 {
+    let k = k as f64;
+    let t = k / 1_236.85;
+    let mean = J2000 + 5.097_66 + MEAN_SYNODIC_MONTH * k + 0.000_154_37 * t.powi(2)
+        - 0.000_000_150 * t.powi(3)
+        + 0.000_000_000_73 * t.powi(4);
+    let e = 1.0 - 0.002_516 * t - 0.000_007_4 * t.powi(2);
+    let sun = 2.553_4 + 29.105_356_70 * k - 0.000_001_4 * t.powi(2) - 0.000_000_11 * t.powi(3);
+    let moon = 201.564_3 + 385.816_935_28 * k + 0.010_758_2 * t.powi(2) + 0.000_012_38 * t.powi(3)
+        - 0.000_000_058 * t.powi(4);
+    let latitude =
+        160.710_8 + 390.670_502_84 * k - 0.001_611_8 * t.powi(2) - 0.000_002_27 * t.powi(3)
+            + 0.000_000_011 * t.powi(4);
+    let node = 124.774_6 - 1.563_755_88 * k + 0.002_067_2 * t.powi(2) + 0.000_002_15 * t.powi(3);
+    let periodic: f64 = NEW_MOON_TERMS
+        .iter()
+        .map(|&(v, power, m, m_prime, f)| {
+            v * e.powi(power) * sin_degrees(m * sun + m_prime * moon + f * latitude)
+        })
+        .sum::<f64>()
+        - 0.000_17 * sin_degrees(node);
+    let planetary = 0.000_325 * sin_degrees(299.77 + 0.107_408 * k - 0.009_173 * t.powi(2))
+        + PLANETARY_TERMS
+            .iter()
+            .map(|&(v, phase, rate)| v * sin_degrees(phase + rate * k))
+            .sum::<f64>();
+    universal_from_dynamical(mean + periodic + planetary)
+}
+
+/// Index of a new moon near `moment`, within a couple of lunations
+fn lunation_near(moment: f64) -> i64 {
+    ((moment - nth_new_moon(0)) / MEAN_SYNODIC_MONTH).round() as i64
+}
+
+/// Moment of the first new moon at or after `moment`
+pub(crate) fn new_moon_at_or_after_moment(moment: f64) -> f64 {
+    let mut k = lunation_near(moment) - 2;
+    while nth_new_moon(k) < moment {
+        k += 1;
+    }
+    nth_new_moon(k)
+}
+
+/// Moment of the last new moon before `moment`
+pub(crate) fn new_moon_before_moment(moment: f64) -> f64 {
+    let mut k = lunation_near(moment) + 2;
+    while nth_new_moon(k) >= moment {
+        k -= 1;
+    }
+    nth_new_moon(k)
+}
+
+/// Apparent longitude of the Sun at `instant`, in degrees from the March
+/// equinox
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use thedate::astronomy::solar_longitude;
+///
+/// let equinox = Utc.with_ymd_and_hms(2024, 3, 20, 3, 6, 0).unwrap();
+/// let longitude = solar_longitude(equinox);
+/// assert!(longitude < 0.001 || longitude > 359.999);
+/// ```
+pub fn solar_longitude(instant: DateTime<Utc>) -> f64 {
+    solar_longitude_at(moment(instant))
+}
+
+/// First instant at or after `instant` when the Sun reaches `longitude`
+pub fn solar_longitude_after(longitude: f64, instant: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    self::instant(solar_longitude_after_moment(longitude, moment(instant)))
+}
+
+/// First new moon at or after `instant`
+pub fn new_moon_at_or_after(instant: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    self::instant(new_moon_at_or_after_moment(moment(instant)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    /// `found` is within `minutes` of `expected`
+    fn near(found: DateTime<Utc>, expected: DateTime<Utc>, minutes: i64) {
+        let error = (found - expected).num_seconds().abs();
+        assert!(
+            error <= minutes * 60,
+            "{} is {}s from {}",
+            found,
+            error,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_moment_round_trip() {
+        let instant = utc(2024, 3, 15, 12, 30);
+        assert_eq!(moment(utc(1970, 1, 1, 0, 0)), 719_163.0);
+        assert_eq!(self::instant(moment(instant)), Some(instant));
+    }
+
+    #[test]
+    fn test_equinoxes_and_solstices() {
+        // Published instants, to the minute (UTC)
+        let cases = [
+            (0.0, utc(2000, 3, 20, 7, 35)),
+            (0.0, utc(2024, 3, 20, 3, 6)),
+            (90.0, utc(2024, 6, 20, 20, 51)),
+            (180.0, utc(2024, 9, 22, 12, 44)),
+            (270.0, utc(2024, 12, 21, 9, 20)),
+            (0.0, utc(2025, 3, 20, 9, 1)),
+        ];
+        for (longitude, expected) in cases {
+            let start = expected - chrono::Duration::days(10);
+            near(
+                solar_longitude_after(longitude, start).unwrap(),
+                expected,
+                2,
+            );
+        }
+    }
+
+    #[test]
+    fn test_new_moons() {
+        // Meeus example 49.a: 1977-02-18 03:37:42 TD
+        let meeus = moment(Utc.with_ymd_and_hms(1977, 2, 18, 3, 37, 42).unwrap());
+        let found = dynamical_from_universal(nth_new_moon(-283));
+        assert!((found - meeus).abs() * 86_400.0 < 1.0);
+        let cases = [
+            utc(2000, 1, 6, 18, 14),
+            utc(2024, 1, 11, 11, 57),
+            utc(2024, 2, 9, 22, 59),
+            utc(2024, 3, 10, 9, 0),
+        ];
+        for expected in cases {
+            let start = expected - chrono::Duration::days(20);
+            near(new_moon_at_or_after(start).unwrap(), expected, 1);
+        }
+    }
+
+    /// Hours from hours, minutes and seconds
+    fn hms(h: f64, m: f64, s: f64) -> f64 {
+        h + m / 60.0 + s / 3_600.0
+    }
+
+    #[test]
+    fn test_julian_dates() {
+        assert_eq!(julian_date(utc(1970, 1, 1, 0, 0)), 2_440_587.5);
+        assert_eq!(julian_date(utc(1858, 11, 17, 0, 0)), MJD_EPOCH);
+        assert_eq!(julian_date(utc(1968, 5, 24, 0, 0)), TJD_EPOCH);
+        // Meeus, example 7.a: Sputnik 1, 1957 October 4.81
+        let sputnik = Utc.with_ymd_and_hms(1957, 10, 4, 19, 26, 24).unwrap();
+        assert!((julian_date(sputnik) - 2_436_116.31).abs() < 1e-9);
+        // Meeus, example 7.b: 333 January 27.5 (Julian), a proleptic
+        // Gregorian 28 January
+        assert_eq!(julian_date(utc(333, 1, 28, 12, 0)), 1_842_713.0);
+    }
+
+    #[test]
+    fn test_nutation() {
+        // Meeus, example 22.a: 1987 April 10, 0h TD
+        let (longitude, obliquity) = nutation(moment(utc(1987, 4, 10, 0, 0)));
+        assert!((longitude * 3_600.0 + 3.788).abs() < 0.5);
+        assert!((obliquity - (23.0 + 26.0 / 60.0 + 36.850 / 3_600.0)).abs() * 3_600.0 < 1.0);
+    }
+
+    #[test]
+    fn test_sidereal_time() {
+        // Meeus, examples 12.a and 12.b
+        let midnight = utc(1987, 4, 10, 0, 0);
+        let mean = greenwich_mean_sidereal_time(midnight);
+        assert!((mean - hms(13.0, 10.0, 46.366_8)).abs() * 3_600.0 < 0.001);
+        let apparent = greenwich_apparent_sidereal_time(midnight);
+        assert!((apparent - hms(13.0, 10.0, 46.135_1)).abs() * 3_600.0 < 0.05);
+        let evening = greenwich_mean_sidereal_time(utc(1987, 4, 10, 19, 21));
+        assert!((evening - hms(8.0, 34.0, 57.089_6)).abs() * 3_600.0 < 0.001);
+        // The IAU 1982 expression's constant term at J2000.0
+        let j2000 = greenwich_mean_sidereal_time(utc(2000, 1, 1, 12, 0));
+        assert!((j2000 - hms(18.0, 41.0, 50.548_41)).abs() * 3_600.0 < 0.001);
+    }
+
+    #[test]
+    fn test_delta_t_is_continuous() {
+        for boundary in [
+            1860.0, 1900.0, 1920.0, 1941.0, 1961.0, 1986.0, 2005.0, 2050.0,
+        ] {
+            let step = delta_t(boundary) - delta_t(boundary - 1e-6);
+            assert!(step.abs() < 1.0, "ΔT jumps {}s at {}", step, boundary);
+        }
+    }
+}
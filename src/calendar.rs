@@ -0,0 +1,1069 @@
+//! Dates in non-Gregorian calendars.
+//!
+//! Every calendar converts through R.D. (*Rata Die*) day numbers, where
+//! 1 January 1 (proleptic Gregorian) is day 1, following Reingold &
+//! Dershowitz, *Calendrical Calculations*. Conversion works on the civil
+//! date in the requested zone. The Hebrew and Islamic days begin at sunset,
+//! so after sunset their religious date is already the next one.
+//!
+//! Month names follow the response locale where the calendar has names in
+//! its language (Persian in `fa`, Saka in `hi`, Ethiopian in `am`, Chinese
+//! in `zh`, and the Gregorian months of the Thai Buddhist and Julian
+//! calendars in every locale), and are English transliterations otherwise.
+
+mod chinese;
+mod coptic;
+mod hebrew;
+mod indian;
+mod islamic;
+mod julian;
+mod persian;
+mod umalqura;
+
+pub use julian::{Cutover, CUTOVERS};
+
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+use chrono::{DateTime, Datelike, NaiveDate, SecondsFormat, TimeZone, Utc};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::astronomy::{self, solar_longitude_after_moment, solar_longitude_at};
+use crate::error::ApiError;
+use crate::locale::Locale;
+use crate::zone::local_date;
+
+/// Years between the Gregorian and Thai Buddhist eras
+const BUDDHIST_OFFSET: i64 = 543;
+/// Years read back in any calendar: wide enough for every date in chrono's
+/// range, and narrow enough that the calendar arithmetic cannot overflow
+const YEARS: RangeInclusive<i64> = -1_000_000..=1_000_000;
+
+/// A date in one calendar
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CalendarDate {
+    pub year: i64,
+    /// Month number as the calendar counts it; see each calendar's notes
+    pub month: u32,
+    pub month_name: &'static str,
+    pub day: u32,
+    /// Year has an extra month (Hebrew, Chinese) or day (the others)
+    pub leap_year: bool,
+    /// This month is the intercalated one
+    pub leap_month: bool,
+}
+
+/// Which reckoning of the Islamic calendar to use
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IslamicVariant {
+    /// Tabular, from the Friday 16 July 622 (Julian) epoch, used by most
+    /// civil converters
+    Civil,
+    /// Tabular, from the Thursday 15 July 622 (Julian) epoch, used in
+    /// astronomy
+    Astronomical,
+    /// The Umm al-Qura table of Saudi Arabia for AH 1300 to 1600, and
+    /// tabular civil outside it
+    UmmAlQura,
+}
+
+/// A supported calendar
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Calendar {
+    /// The arithmetic Hebrew calendar
+    Hebrew,
+    /// The Islamic calendar, tabular or Umm al-Qura
+    ///
+    /// Umm al-Qura dates differ from the tabular ones by up to two days.
+    Islamic(IslamicVariant),
+    /// The Persian Solar Hijri calendar
+    Persian,
+    /// The Indian National (Saka) calendar
+    Indian,
+    /// The Ethiopian calendar, Amete Mihret era
+    Ethiopic,
+    /// The Coptic calendar
+    Coptic,
+    /// The Thai solar calendar: Gregorian months and days, Buddhist era
+    /// years (543 ahead)
+    ///
+    /// Before 1941 the Thai year began on 1 April; dates are given with the
+    /// current January start throughout.
+    Buddhist,
+    /// The Chinese lunisolar calendar; `year` is the Gregorian year in which
+    /// its New Year falls
+    ///
+    /// The block adds the sexagenary stem and branch of the year, month and
+    /// day, the zodiac animal, and the current and next solar terms.
+    Chinese,
+    /// The proleptic Julian calendar
+    Julian,
+    /// The Julian calendar until a changeover, and the Gregorian from then
+    ///
+    /// The block adds the `cutover` (first Gregorian day) and which
+    /// `reckoning` the date is in. Years are counted from 1 January
+    /// throughout, although Britain began them on 25 March until 1752.
+    Historical(&'static Cutover),
+}
+
+impl Calendar {
+    /// Accepted names in the `calendars` parameter
+    pub const NAMES: &'static [&'static str] = &[
+        "hebrew",
+        "islamic",
+        "islamic-civil",
+        "islamic-tbla",
+        "islamic-umalqura",
+        "persian",
+        "indian",
+        "ethiopic",
+        "coptic",
+        "buddhist",
+        "chinese",
+        "julian",
+        "historical",
+        "historical-fr",
+        "historical-dk",
+        "historical-gb",
+        "historical-us",
+        "historical-se",
+        "historical-bg",
+        "historical-ru",
+        "historical-ro",
+        "historical-gr",
+    ];
+
+    /// Parse one calendar name; `islamic` is the civil tabular calendar,
+    /// and `historical` changes over in October 1582
+    pub fn parse(name: &str) -> Result<Self, ApiError> {
+        let name_lower = name.trim().to_ascii_lowercase();
+        if let Some(cutover) = CUTOVERS.iter().find(|c| c.name == name_lower) {
+            return Ok(Calendar::Historical(cutover));
+        }
+        match name_lower.as_str() {
+            "hebrew" => Ok(Calendar::Hebrew),
+            "islamic" | "islamic-civil" => Ok(Calendar::Islamic(IslamicVariant::Civil)),
+            "islamic-tbla" => Ok(Calendar::Islamic(IslamicVariant::Astronomical)),
+            "islamic-umalqura" => Ok(Calendar::Islamic(IslamicVariant::UmmAlQura)),
+            "persian" => Ok(Calendar::Persian),
+            "indian" => Ok(Calendar::Indian),
+            "ethiopic" => Ok(Calendar::Ethiopic),
+            "coptic" => Ok(Calendar::Coptic),
+            "buddhist" => Ok(Calendar::Buddhist),
+            "chinese" => Ok(Calendar::Chinese),
+            "julian" => Ok(Calendar::Julian),
+            _ => Err(ApiError::InvalidChoice {
+                parameter: "calendars",
+                value: name.to_string(),
+                valid: Self::NAMES,
+            }),
+        }
+    }
+
+    /// Parse a comma-separated list such as `hebrew,islamic`, dropping
+    /// duplicates and empty entries
+    pub fn parse_list(list: &str) -> Result<Vec<Self>, ApiError> {
+        let mut calendars = Vec::new();
+        for name in list.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            let calendar = Self::parse(name)?;
+            if !calendars.contains(&calendar) {
+                calendars.push(calendar);
+            }
+        }
+        Ok(calendars)
+    }
+
+    /// Key of this calendar's block in the response
+    pub fn name(self) -> &'static str {
+        match self {
+            Calendar::Hebrew => "hebrew",
+            Calendar::Islamic(IslamicVariant::Civil) => "islamic",
+            Calendar::Islamic(IslamicVariant::Astronomical) => "islamic-tbla",
+            Calendar::Islamic(IslamicVariant::UmmAlQura) => "islamic-umalqura",
+            Calendar::Persian => "persian",
+            Calendar::Indian => "indian",
+            Calendar::Ethiopic => "ethiopic",
+            Calendar::Coptic => "coptic",
+            Calendar::Buddhist => "buddhist",
+            Calendar::Chinese => "chinese",
+            Calendar::Julian => "julian",
+            Calendar::Historical(cutover) => cutover.name,
+        }
+    }
+
+    /// Reckoning used, for calendars that have more than one
+    pub fn variant(self) -> Option<&'static str> {
+        match self {
+            Calendar::Islamic(IslamicVariant::Civil) => Some("tabular-civil"),
+            Calendar::Islamic(IslamicVariant::Astronomical) => Some("tabular-astronomical"),
+            Calendar::Islamic(IslamicVariant::UmmAlQura) => Some("umalqura"),
+            _ => None,
+        }
+    }
+
+    /// The date `date` (proleptic Gregorian) falls on in this calendar
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use thedate::calendar::Calendar;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2024, 10, 3).unwrap();
+    /// let hebrew = Calendar::Hebrew.from_date(date);
+    /// assert_eq!((hebrew.year, hebrew.month_name, hebrew.day), (5785, "Tishri", 1));
+    /// ```
+    pub fn from_date(self, date: NaiveDate) -> CalendarDate {
+        let fixed = i64::from(date.num_days_from_ce());
+        match self {
+            Calendar::Hebrew => hebrew::from_fixed(fixed),
+            Calendar::Islamic(IslamicVariant::UmmAlQura) => umalqura::from_fixed(fixed),
+            Calendar::Islamic(variant) => {
+                islamic::from_fixed(variant == IslamicVariant::Civil, fixed)
+            }
+            Calendar::Persian => persian::from_fixed(fixed),
+            Calendar::Indian => indian::from_fixed(fixed),
+            Calendar::Ethiopic => coptic::from_fixed(true, fixed),
+            Calendar::Coptic => coptic::from_fixed(false, fixed),
+            Calendar::Buddhist => CalendarDate {
+                year: i64::from(date.year()) + BUDDHIST_OFFSET,
+                ..gregorian_date(date)
+            },
+            Calendar::Chinese => chinese::from_fixed(fixed).into(),
+            Calendar::Julian => julian::from_fixed(fixed),
+            Calendar::Historical(cutover) if fixed >= cutover.first_fixed() => gregorian_date(date),
+            Calendar::Historical(_) => julian::from_fixed(fixed),
+        }
+    }
+
+    /// The proleptic Gregorian date of a date in this calendar
+    ///
+    /// Returns `None` when the month or day does not exist in that year, or
+    /// the result is outside chrono's range, including dates skipped at a
+    /// historical changeover. Chinese months are the regular ones; see
+    /// [`Calendar::parse_date`] for leap months.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use thedate::calendar::Calendar;
+    ///
+    /// let ramadan = Calendar::parse("islamic").unwrap().to_date(1445, 9, 1);
+    /// assert_eq!(ramadan, NaiveDate::from_ymd_opt(2024, 3, 11));
+    /// assert_eq!(Calendar::Hebrew.to_date(5785, 13, 1), None);
+    /// ```
+    pub fn to_date(self, year: i64, month: u32, day: u32) -> Option<NaiveDate> {
+        if !YEARS.contains(&year) {
+            return None;
+        }
+        let fixed = match self {
+            Calendar::Hebrew => {
+                let months = if hebrew::is_leap_year(year) { 13 } else { 12 };
+                valid(month, months, day, || hebrew::days_in_month(year, month))?;
+                hebrew::to_fixed(year, month, day)
+            }
+            Calendar::Islamic(IslamicVariant::UmmAlQura) => {
+                valid(month, 12, day, || umalqura::days_in_month(year, month))?;
+                umalqura::to_fixed(year, month, day)
+            }
+            Calendar::Islamic(variant) => {
+                valid(month, 12, day, || islamic::days_in_month(year, month))?;
+                islamic::to_fixed(variant == IslamicVariant::Civil, year, month, day)
+            }
+            Calendar::Persian => {
+                valid(month, 12, day, || persian::days_in_month(year, month))?;
+                persian::to_fixed(year, month, day)
+            }
+            Calendar::Indian => {
+                valid(month, 12, day, || indian::days_in_month(year, month))?;
+                indian::to_fixed(year, month, day)
+            }
+            Calendar::Ethiopic | Calendar::Coptic => {
+                valid(month, 13, day, || coptic::days_in_month(year, month))?;
+                coptic::to_fixed(self == Calendar::Ethiopic, year, month, day)
+            }
+            Calendar::Buddhist => {
+                let year = i32::try_from(year - BUDDHIST_OFFSET).ok()?;
+                return NaiveDate::from_ymd_opt(year, month, day);
+            }
+            Calendar::Chinese => chinese::to_fixed(year, month, false, day)?,
+            Calendar::Julian => {
+                valid(month, 12, day, || julian::days_in_month(year, month))?;
+                julian::to_fixed(year, month, day)
+            }
+            Calendar::Historical(cutover) => {
+                let gregorian = i32::try_from(year)
+                    .ok()
+                    .and_then(|year| NaiveDate::from_ymd_opt(year, month, day));
+                if let Some(date) = gregorian.filter(|date| *date >= cutover.first_gregorian) {
+                    return Some(date);
+                }
+                valid(month, 12, day, || julian::days_in_month(year, month))?;
+                let fixed = julian::to_fixed(year, month, day);
+                (fixed < cutover.first_fixed()).then_some(fixed)?
+            }
+        };
+        NaiveDate::from_num_days_from_ce_opt(i32::try_from(fixed).ok()?)
+    }
+
+    /// Parse a `year-month-day` date in this calendar, such as `1403-01-01`,
+    /// into its proleptic Gregorian date
+    ///
+    /// The year may be negative, and a Chinese leap month is marked with a
+    /// trailing `L`, as in `2023-02L-15`. Returns `None` when the text is
+    /// malformed or the date does not exist (see [`Calendar::to_date`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use thedate::calendar::Calendar;
+    ///
+    /// let nowruz = Calendar::Persian.parse_date("1403-01-01");
+    /// assert_eq!(nowruz, NaiveDate::from_ymd_opt(2024, 3, 20));
+    /// assert_eq!(Calendar::Persian.parse_date("1403-13-01"), None);
+    /// ```
+    pub fn parse_date(self, text: &str) -> Option<NaiveDate> {
+        let (year, month, day) = split_date(text)?;
+        match month.strip_suffix(['L', 'l']) {
+            Some(leap) if self == Calendar::Chinese => {
+                let fixed = chinese::to_fixed(year, leap.parse().ok()?, true, day)?;
+                NaiveDate::from_num_days_from_ce_opt(i32::try_from(fixed).ok()?)
+            }
+            Some(_) => None,
+            None => self.to_date(year, month.parse().ok()?, day),
+        }
+    }
+
+    /// Why [`Calendar::parse_date`] rejected `text`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use thedate::calendar::Calendar;
+    ///
+    /// let britain = Calendar::parse("historical-gb").unwrap();
+    /// assert_eq!(britain.parse_date("1752-09-05"), None);
+    /// assert!(britain.date_error("1752-09-05").contains("ended on 1752-09-02"));
+    /// ```
+    pub fn date_error(self, text: &str) -> String {
+        let gap = match (self, split_date(text)) {
+            (Calendar::Historical(cutover), Some((year, month, day))) if YEARS.contains(&year) => {
+                month
+                    .parse()
+                    .ok()
+                    .and_then(|month| cutover.gap(year, month, day))
+            }
+            _ => None,
+        };
+        gap.unwrap_or_else(|| format!("expected an existing {} year-month-day date", self.name()))
+    }
+
+    /// Name of month `date.month` in `locale`'s language, where this
+    /// calendar has one
+    fn month_name(self, date: &CalendarDate, locale: &Locale) -> &'static str {
+        let language = locale.language();
+        match self {
+            Calendar::Persian => localized(persian::MONTHS, language, date.month),
+            Calendar::Indian => localized(indian::MONTHS, language, date.month),
+            Calendar::Ethiopic => localized(coptic::ETHIOPIC_MONTHS, language, date.month),
+            Calendar::Coptic => localized(coptic::COPTIC_MONTHS, language, date.month),
+            Calendar::Buddhist | Calendar::Julian | Calendar::Historical(_) => {
+                locale.month_name(date.month)
+            }
+            Calendar::Chinese if date.leap_month => {
+                localized(chinese::LEAP_MONTHS, language, date.month)
+            }
+            Calendar::Chinese => localized(chinese::MONTHS, language, date.month),
+            Calendar::Hebrew | Calendar::Islamic(_) => date.month_name,
+        }
+    }
+
+    /// This calendar's block for the civil date of `dt`: the variant, if
+    /// any, then the [`CalendarDate`] fields with the month named in
+    /// `locale`, then any fields particular to the calendar
+    ///
+    /// Null where the offset of `dt` takes that date out of range.
+    pub fn render<Tz: TimeZone>(self, dt: &DateTime<Tz>, locale: &Locale) -> Value
+    where
+        Tz::Offset: Display,
+    {
+        let Some(date) = local_date(dt) else {
+            return Value::Null;
+        };
+        let mut block = Map::new();
+        if let Some(variant) = self.variant() {
+            block.insert("variant".into(), Value::from(variant));
+        }
+        let mut converted = self.from_date(date);
+        converted.month_name = self.month_name(&converted, locale);
+        if let Ok(Value::Object(fields)) = serde_json::to_value(converted) {
+            block.extend(fields);
+        }
+        match self {
+            Calendar::Chinese => block.extend(chinese_fields(dt, date, locale.language())),
+            Calendar::Historical(cutover) => {
+                let gregorian = date >= cutover.first_gregorian;
+                block.insert(
+                    "cutover".into(),
+                    Value::from(cutover.first_gregorian.to_string()),
+                );
+                block.insert(
+                    "reckoning".into(),
+                    Value::from(if gregorian { "gregorian" } else { "julian" }),
+                );
+            }
+            _ => {}
+        }
+        Value::Object(block)
+    }
+}
+
+/// Sexagenary pillars, zodiac animal and solar terms of `dt`, whose civil
+/// date is `date`
+fn chinese_fields<Tz: TimeZone>(
+    dt: &DateTime<Tz>,
+    date: NaiveDate,
+    language: &str,
+) -> Map<String, Value>
+where
+    Tz::Offset: Display,
+{
+    let fixed = i64::from(date.num_days_from_ce());
+    let date = chinese::from_fixed(fixed);
+    let now = astronomy::moment(dt.with_timezone(&Utc));
+    let current = (solar_longitude_at(now) / 15.0).floor() * 15.0;
+    // Terms are at most 16 days apart, so this finds the latest crossing
+    let started = solar_longitude_after_moment(current, now - 17.0);
+    let next = (current + 15.0).rem_euclid(360.0);
+    let term = |longitude: f64, moment: f64| {
+        let index = (longitude / 15.0) as u32;
+        serde_json::json!({
+            "name": localized(chinese::SOLAR_TERMS, language, index + 1),
+            "longitude": longitude as u32,
+            "instant": astronomy::instant(moment).map(|instant| {
+                instant
+                    .with_timezone(&dt.timezone())
+                    .to_rfc3339_opts(SecondsFormat::Secs, false)
+            }),
+        })
+    };
+    let mut fields = Map::new();
+    fields.insert(
+        "sexagenary".into(),
+        serde_json::json!({
+            "year": date.year_pillar(),
+            "month": date.month_pillar(),
+            "day": chinese::day_pillar(fixed),
+        }),
+    );
+    fields.insert(
+        "zodiac".into(),
+        Value::from(localized(
+            chinese::ANIMALS,
+            language,
+            date.branch() as u32 + 1,
+        )),
+    );
+    fields.insert("solar_term".into(), term(current, started));
+    fields.insert(
+        "next_solar_term".into(),
+        term(next, solar_longitude_after_moment(next, now)),
+    );
+    fields
+}
+
+/// Year, month text and day of a `year-month-day` date, where the year may
+/// be negative
+fn split_date(text: &str) -> Option<(i64, &str, u32)> {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let mut parts = text.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month = parts.next()?;
+    let day = parts.next()?.parse().ok()?;
+    Some((if negative { -year } else { year }, month, day))
+}
+
+/// The date's own Gregorian fields, with English month names
+fn gregorian_date(date: NaiveDate) -> CalendarDate {
+    CalendarDate {
+        year: i64::from(date.year()),
+        month: date.month(),
+        month_name: <&Locale>::default().month_name(date.month()),
+        day: date.day(),
+        leap_year: date.leap_year(),
+        leap_month: false,
+    }
+}
+
+/// `Some(())` when `month` is within `1..=months` and `day` within its length
+fn valid(month: u32, months: u32, day: u32, days: impl FnOnce() -> u32) -> Option<()> {
+    ((1..=months).contains(&month) && day >= 1 && day <= days()).then_some(())
+}
+
+/// Entry `number` (from 1) of a table of names by language, falling back
+/// to the first (English) entry
+fn localized<const N: usize>(
+    names: &[(&str, [&'static str; N])],
+    language: &str,
+    number: u32,
+) -> &'static str {
+    let (_, entries) = names
+        .iter()
+        .find(|(own, _)| *own == language)
+        .unwrap_or(&names[0]);
+    entries[number as usize - 1]
+}
+
+fn gregorian_leap_year(year: i64) -> bool {
+    year.rem_euclid(4) == 0 && (year.rem_euclid(100) != 0 || year.rem_euclid(400) == 0)
+}
+
+/// R.D. of a proleptic Gregorian date, for years beyond chrono's range
+fn gregorian_to_fixed(year: i64, month: u32, day: u32) -> i64 {
+    let prior = year - 1;
+    let month = i64::from(month);
+    let correction = if month <= 2 {
+        0
+    } else if gregorian_leap_year(year) {
+        -1
+    } else {
+        -2
+    };
+    365 * prior + prior.div_euclid(4) - prior.div_euclid(100)
+        + prior.div_euclid(400)
+        + (367 * month - 362).div_euclid(12)
+        + correction
+        + i64::from(day)
+}
+
+/// Proleptic Gregorian year containing R.D. `fixed`
+fn gregorian_year(fixed: i64) -> i64 {
+    let days = fixed - 1;
+    let (cycles, days) = (days.div_euclid(146_097), days.rem_euclid(146_097));
+    let (centuries, days) = (days / 36_524, days % 36_524);
+    let (quads, days) = (days / 1_461, days % 1_461);
+    let years = days / 365;
+    let year = 400 * cycles + 100 * centuries + 4 * quads + years;
+    if centuries == 4 || years == 4 {
+        year
+    } else {
+        year + 1
+    }
+}
+
+/// Blocks for every calendar in `calendars` at `dt`, keyed by name, with
+/// month names in `locale`
+pub fn render_all<Tz: TimeZone>(
+    calendars: &[Calendar],
+    dt: &DateTime<Tz>,
+    locale: &Locale,
+) -> Map<String, Value>
+where
+    Tz::Offset: Display,
+{
+    calendars
+        .iter()
+        .map(|calendar| (calendar.name().to_string(), calendar.render(dt, locale)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gregorian(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn noon(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        gregorian(y, m, d).and_hms_opt(12, 0, 0).unwrap().and_utc()
+    }
+
+    /// Check `date` against an expected calendar date in both directions
+    fn check(calendar: Calendar, date: NaiveDate, expected: (i64, u32, u32)) {
+        let converted = calendar.from_date(date);
+        assert_eq!(
+            (converted.year, converted.month, converted.day),
+            expected,
+            "{} in {}",
+            date,
+            calendar.name()
+        );
+        assert_eq!(
+            calendar.to_date(expected.0, expected.1, expected.2),
+            Some(date),
+            "{:?} in {}",
+            expected,
+            calendar.name()
+        );
+    }
+
+    /// Sample dates from Calendrical Calculations, Appendix C
+    #[test]
+    fn test_reference_dates() {
+        let civil = Calendar::Islamic(IslamicVariant::Civil);
+        let rows = [
+            (gregorian(-586, 7, 24), (3174, 5, 10), (-1245, 12, 9)),
+            (gregorian(1648, 6, 10), (5408, 3, 20), (1058, 5, 18)),
+            (gregorian(1945, 11, 12), (5706, 9, 7), (1364, 12, 6)),
+            (gregorian(1992, 3, 17), (5752, 13, 12), (1412, 9, 13)),
+            (gregorian(1996, 2, 25), (5756, 12, 5), (1416, 10, 5)),
+            (gregorian(2038, 11, 10), (5799, 8, 12), (1460, 10, 12)),
+            (gregorian(2094, 7, 18), (5854, 5, 5), (1518, 3, 5)),
+        ];
+        for (date, hebrew, islamic) in rows {
+            check(Calendar::Hebrew, date, hebrew);
+            check(civil, date, islamic);
+        }
+    }
+
+    #[test]
+    fn test_hebrew_holidays() {
+        // Rosh Hashanah, Passover and Purim (in a leap year, in Adar II)
+        check(Calendar::Hebrew, gregorian(2023, 9, 16), (5784, 7, 1));
+        check(Calendar::Hebrew, gregorian(2024, 10, 3), (5785, 7, 1));
+        check(Calendar::Hebrew, gregorian(2024, 4, 23), (5784, 1, 15));
+        check(Calendar::Hebrew, gregorian(2024, 3, 24), (5784, 13, 14));
+        check(Calendar::Hebrew, gregorian(2025, 3, 14), (5785, 12, 14));
+    }
+
+    #[test]
+    fn test_hebrew_leap_month_names() {
+        let adar_i = Calendar::Hebrew.from_date(gregorian(2024, 2, 15));
+        assert_eq!((adar_i.month, adar_i.month_name), (12, "Adar I"));
+        assert!(adar_i.leap_year && adar_i.leap_month);
+        let adar = Calendar::Hebrew.from_date(gregorian(2025, 3, 14));
+        assert_eq!(adar.month_name, "Adar");
+        assert!(!adar.leap_year && !adar.leap_month);
+    }
+
+    #[test]
+    fn test_islamic_epochs() {
+        // 1 Muharram AH 1 is 16 July 622 Julian, 19 July proleptic Gregorian
+        let civil = Calendar::Islamic(IslamicVariant::Civil);
+        let tbla = Calendar::Islamic(IslamicVariant::Astronomical);
+        check(civil, gregorian(622, 7, 19), (1, 1, 1));
+        check(tbla, gregorian(622, 7, 18), (1, 1, 1));
+        assert_eq!(
+            civil.from_date(gregorian(2024, 3, 15)).month_name,
+            "Ramadan"
+        );
+    }
+
+    #[test]
+    fn test_umm_al_qura() {
+        let umalqura = Calendar::Islamic(IslamicVariant::UmmAlQura);
+        let civil = Calendar::Islamic(IslamicVariant::Civil);
+        // Ramadan, Shawwal, the Day of Arafah and Muharram as announced
+        check(umalqura, gregorian(2022, 4, 2), (1443, 9, 1));
+        check(umalqura, gregorian(2024, 3, 11), (1445, 9, 1));
+        check(umalqura, gregorian(2024, 6, 15), (1445, 12, 9));
+        check(umalqura, gregorian(2024, 7, 7), (1446, 1, 1));
+        check(umalqura, gregorian(2025, 3, 30), (1446, 10, 1));
+        check(umalqura, gregorian(1990, 8, 1), (1411, 1, 10));
+        check(civil, gregorian(2024, 7, 7), (1445, 12, 30));
+        // Ramadan 1446 had 29 days, where the tabular month has 30
+        assert_eq!(umalqura.to_date(1446, 9, 30), None);
+        assert!(civil.to_date(1446, 9, 30).is_some());
+        // The table runs from AH 1300 to 1600, tabular civil either side
+        check(umalqura, gregorian(1882, 11, 11), (1299, 12, 29));
+        check(umalqura, gregorian(1882, 11, 12), (1300, 1, 1));
+        check(umalqura, gregorian(2174, 11, 25), (1600, 12, 30));
+        check(umalqura, gregorian(2174, 11, 26), (1601, 1, 1));
+        check(umalqura, gregorian(622, 7, 19), (1, 1, 1));
+        assert_eq!(
+            umalqura.from_date(gregorian(2024, 3, 15)).month_name,
+            "Ramadan"
+        );
+    }
+
+    #[test]
+    fn test_solar_calendars() {
+        // Nowruz, including the leap Esfand 30 of 1403
+        check(Calendar::Persian, gregorian(1921, 3, 21), (1300, 1, 1));
+        check(Calendar::Persian, gregorian(1945, 11, 12), (1324, 8, 21));
+        check(Calendar::Persian, gregorian(2024, 3, 20), (1403, 1, 1));
+        check(Calendar::Persian, gregorian(2025, 3, 20), (1403, 12, 30));
+        check(Calendar::Persian, gregorian(2025, 3, 21), (1404, 1, 1));
+        // Chaitra 1 falls a day early in Gregorian leap years
+        check(Calendar::Indian, gregorian(2024, 3, 15), (1945, 12, 25));
+        check(Calendar::Indian, gregorian(2024, 3, 21), (1946, 1, 1));
+        check(Calendar::Indian, gregorian(2025, 3, 22), (1947, 1, 1));
+        // Enkutatash follows a six-day Pagumen in 2023
+        check(Calendar::Ethiopic, gregorian(1945, 11, 12), (1938, 3, 3));
+        check(Calendar::Ethiopic, gregorian(2023, 9, 12), (2016, 1, 1));
+        check(Calendar::Ethiopic, gregorian(2024, 9, 10), (2016, 13, 5));
+        check(Calendar::Ethiopic, gregorian(2024, 9, 11), (2017, 1, 1));
+        check(Calendar::Coptic, gregorian(1945, 11, 12), (1662, 3, 3));
+        check(Calendar::Coptic, gregorian(2024, 9, 11), (1741, 1, 1));
+        check(Calendar::Buddhist, gregorian(2024, 2, 29), (2567, 2, 29));
+    }
+
+    #[test]
+    fn test_chinese_new_years() {
+        // Hong Kong Observatory tables
+        let new_years = [
+            (1900, 1, 31),
+            (1950, 2, 17),
+            (2000, 2, 5),
+            (2001, 1, 24),
+            (2004, 1, 22),
+            (2012, 1, 23),
+            (2017, 1, 28),
+            (2020, 1, 25),
+            (2023, 1, 22),
+            (2024, 2, 10),
+            (2025, 1, 29),
+            (2026, 2, 17),
+            (2033, 1, 31),
+            (2034, 2, 19),
+            (2050, 1, 23),
+            (2051, 2, 11),
+            (2052, 2, 1),
+            (2053, 2, 19),
+            (2054, 2, 8),
+            (2055, 1, 28),
+            (2056, 2, 15),
+            (2057, 2, 4),
+            (2058, 1, 24),
+            (2059, 2, 12),
+            (2060, 2, 2),
+            (2061, 1, 21),
+            (2062, 2, 9),
+            (2063, 1, 29),
+            (2064, 2, 17),
+            (2065, 2, 5),
+            (2066, 1, 26),
+            (2067, 2, 14),
+            (2068, 2, 3),
+            (2069, 1, 23),
+            (2070, 2, 11),
+            (2071, 1, 31),
+            (2072, 2, 19),
+            (2073, 2, 7),
+            (2074, 1, 27),
+            (2075, 2, 15),
+            (2076, 2, 5),
+            (2077, 1, 24),
+            (2078, 2, 12),
+            (2079, 2, 2),
+            (2080, 1, 22),
+            (2081, 2, 9),
+            (2082, 1, 29),
+            (2083, 2, 17),
+            (2084, 2, 6),
+            (2085, 1, 26),
+            (2086, 2, 14),
+            (2087, 2, 3),
+            (2088, 1, 24),
+            (2089, 2, 10),
+            (2090, 1, 30),
+            (2091, 2, 18),
+            (2092, 2, 7),
+            (2093, 1, 27),
+            (2094, 2, 15),
+            (2095, 2, 5),
+            (2096, 1, 25),
+            (2097, 2, 12),
+            (2098, 2, 1),
+            (2099, 1, 21),
+            (2100, 2, 9),
+        ];
+        for (y, m, d) in new_years {
+            check(Calendar::Chinese, gregorian(y, m, d), (i64::from(y), 1, 1));
+            let eve = Calendar::Chinese.from_date(gregorian(y, m, d).pred_opt().unwrap());
+            assert_eq!(eve.year, i64::from(y) - 1, "{}", y);
+            assert_eq!(eve.month, 12, "{}", y);
+        }
+        // Mid-Autumn and Dragon Boat festivals
+        check(Calendar::Chinese, gregorian(2024, 9, 17), (2024, 8, 15));
+        check(Calendar::Chinese, gregorian(2024, 6, 10), (2024, 5, 5));
+        // New moons close to midnight in Beijing, where other tables differ
+        check(Calendar::Chinese, gregorian(2057, 9, 28), (2057, 9, 1));
+        check(Calendar::Chinese, gregorian(2097, 8, 7), (2097, 7, 1));
+    }
+
+    #[test]
+    fn test_chinese_leap_months() {
+        // Hong Kong Observatory tables
+        let leap_months = [
+            (1984, 10),
+            (1987, 6),
+            (1990, 5),
+            (1995, 8),
+            (2001, 4),
+            (2004, 2),
+            (2006, 7),
+            (2009, 5),
+            (2012, 4),
+            (2014, 9),
+            (2017, 6),
+            (2020, 4),
+            (2023, 2),
+            (2025, 6),
+            (2028, 5),
+            (2031, 3),
+            (2033, 11),
+            (2036, 6),
+            (2039, 5),
+            (2042, 2),
+            (2044, 7),
+            (2047, 5),
+            (2050, 3),
+            (2052, 8),
+            (2055, 6),
+            (2058, 4),
+            (2061, 3),
+            (2063, 7),
+            (2066, 5),
+            (2069, 4),
+            (2071, 8),
+            (2074, 6),
+            (2077, 4),
+            (2080, 3),
+            (2082, 7),
+            (2085, 5),
+            (2088, 4),
+            (2090, 8),
+            (2093, 6),
+            (2096, 4),
+            (2099, 2),
+        ];
+        for (year, month) in leap_months {
+            let text = format!("{}-{:02}L-01", year, month);
+            let date = Calendar::Chinese.parse_date(&text).expect(&text);
+            let converted = Calendar::Chinese.from_date(date);
+            assert!(converted.leap_month && converted.leap_year, "{}", text);
+            assert_eq!(converted.month, month, "{}", text);
+            // The regular month of the same number comes just before
+            let regular = Calendar::Chinese
+                .to_date(i64::from(year), month, 1)
+                .unwrap();
+            assert!(regular < date && date - regular < chrono::Duration::days(31));
+        }
+        for (year, month) in [(2024, 1), (2026, 6), (2023, 3)] {
+            let text = format!("{}-{:02}L-01", year, month);
+            assert_eq!(Calendar::Chinese.parse_date(&text), None, "{}", text);
+        }
+        assert!(
+            !Calendar::Chinese
+                .from_date(gregorian(2024, 3, 15))
+                .leap_year
+        );
+        assert_eq!(Calendar::Hebrew.parse_date("5784-06L-01"), None);
+        assert_eq!(Calendar::Chinese.parse_date("50001-02L-01"), None);
+        assert_eq!(
+            Calendar::Chinese.parse_date("9223372036854775807-01-01"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_years_out_of_range() {
+        for name in Calendar::NAMES {
+            let calendar = Calendar::parse(name).unwrap();
+            for text in ["9223372036854775807-01-01", "-9223372036854775807-01-01"] {
+                assert_eq!(calendar.parse_date(text), None, "{}:{}", name, text);
+                assert!(
+                    calendar.date_error(text).starts_with("expected"),
+                    "{}",
+                    name
+                );
+            }
+            assert_eq!(calendar.to_date(i64::MIN, 1, 1), None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_chinese_block() {
+        let block = Calendar::Chinese.render(&noon(2024, 3, 15), Default::default());
+        assert_eq!(block["year"], 2024);
+        assert_eq!(block["month"], 2);
+        assert_eq!(block["day"], 6);
+        assert_eq!(block["month_name"], "Second Month");
+        assert_eq!(block["sexagenary"]["year"], "甲辰");
+        assert_eq!(block["sexagenary"]["month"], "丁卯");
+        assert_eq!(block["zodiac"], "Dragon");
+        assert_eq!(block["solar_term"]["longitude"], 345);
+        // Published to the minute; the computed instants are within one
+        let minute = |term: &str| block[term]["instant"].as_str().unwrap()[..16].to_string();
+        assert_eq!(minute("solar_term"), "2024-03-05T02:22");
+        assert_eq!(block["next_solar_term"]["name"], "Spring Equinox");
+        assert_eq!(block["next_solar_term"]["longitude"], 0);
+        assert_eq!(minute("next_solar_term"), "2024-03-20T03:06");
+
+        let zh = Calendar::Chinese.render(&noon(2024, 3, 15), Locale::find("zh").unwrap());
+        assert_eq!(zh["month_name"], "二月");
+        assert_eq!(zh["zodiac"], "龙");
+        assert_eq!(zh["solar_term"]["name"], "惊蛰");
+    }
+
+    #[test]
+    fn test_solar_terms_on_published_days() {
+        // Qingming, summer solstice and winter solstice of 2024 in Beijing
+        let beijing = chrono::FixedOffset::east_opt(8 * 3600).unwrap();
+        for (date, longitude) in [
+            ((2024, 4, 4), 15),
+            ((2024, 6, 21), 90),
+            ((2024, 12, 21), 270),
+        ] {
+            let (y, m, d) = date;
+            let dt = gregorian(y, m, d)
+                .and_hms_opt(23, 59, 0)
+                .unwrap()
+                .and_local_timezone(beijing)
+                .unwrap();
+            let block = Calendar::Chinese.render(&dt, Default::default());
+            assert_eq!(block["solar_term"]["longitude"], longitude);
+            let instant = block["solar_term"]["instant"].as_str().unwrap();
+            assert!(
+                instant.starts_with(&format!("{}-{:02}-{:02}T", y, m, d)),
+                "{}",
+                instant
+            );
+            assert!(instant.ends_with("+08:00"));
+        }
+    }
+
+    #[test]
+    fn test_julian_dates() {
+        // Ides of March, 44 BCE
+        check(Calendar::Julian, gregorian(-43, 3, 13), (-43, 3, 15));
+        check(Calendar::Julian, gregorian(1582, 10, 14), (1582, 10, 4));
+        check(Calendar::Julian, gregorian(1752, 9, 13), (1752, 9, 2));
+        // The October Revolution
+        check(Calendar::Julian, gregorian(1917, 11, 7), (1917, 10, 25));
+        check(Calendar::Julian, gregorian(2024, 3, 15), (2024, 3, 2));
+        // 1900 is a Julian leap year only
+        check(Calendar::Julian, gregorian(1900, 3, 13), (1900, 2, 29));
+        assert!(Calendar::Julian.from_date(gregorian(1900, 3, 13)).leap_year);
+        assert_eq!(Calendar::Julian.to_date(1901, 2, 29), None);
+    }
+
+    #[test]
+    fn test_historical_cutovers() {
+        let rome = Calendar::parse("historical").unwrap();
+        check(rome, gregorian(1582, 10, 14), (1582, 10, 4));
+        check(rome, gregorian(1582, 10, 15), (1582, 10, 15));
+        check(rome, gregorian(1066, 10, 20), (1066, 10, 14));
+        assert_eq!(rome.parse_date("1582-10-10"), None);
+        assert_eq!(
+            rome.date_error("1582-10-10"),
+            "1582-10-10 was skipped in the Papal States, Spain, Portugal and Poland: \
+             the Julian calendar ended on 1582-10-04 and the Gregorian began on 1582-10-15"
+        );
+
+        let britain = Calendar::parse("historical-gb").unwrap();
+        check(britain, gregorian(1582, 10, 15), (1582, 10, 5));
+        check(britain, gregorian(1752, 9, 13), (1752, 9, 2));
+        check(britain, gregorian(1752, 9, 14), (1752, 9, 14));
+        for day in 3..=13 {
+            let text = format!("1752-09-{:02}", day);
+            assert_eq!(britain.parse_date(&text), None, "{}", text);
+            assert!(britain.date_error(&text).contains("Britain"), "{}", text);
+        }
+        // Invalid for reasons other than the changeover
+        assert_eq!(
+            britain.date_error("1753-02-29"),
+            "expected an existing historical-gb year-month-day date"
+        );
+
+        let russia = Calendar::parse("historical-ru").unwrap();
+        check(russia, gregorian(1918, 2, 13), (1918, 1, 31));
+        check(russia, gregorian(1918, 2, 14), (1918, 2, 14));
+        assert_eq!(russia.parse_date("1918-02-01"), None);
+    }
+
+    #[test]
+    fn test_historical_block() {
+        let britain = Calendar::parse("historical-gb").unwrap();
+        let block = britain.render(&noon(1752, 9, 13), Default::default());
+        assert_eq!(block["cutover"], "1752-09-14");
+        assert_eq!(block["reckoning"], "julian");
+        assert_eq!(block["day"], 2);
+        let block = britain.render(&noon(1752, 9, 14), Default::default());
+        assert_eq!(block["reckoning"], "gregorian");
+        assert_eq!(block["month_name"], "September");
+    }
+
+    #[test]
+    fn test_localized_month_names() {
+        let date = noon(2024, 3, 15);
+        let name = |calendar: Calendar, tag: &str| {
+            calendar.render(&date, Locale::find(tag).unwrap())["month_name"].clone()
+        };
+        assert_eq!(name(Calendar::Persian, "en"), "Esfand");
+        assert_eq!(name(Calendar::Persian, "fa"), "اسفند");
+        assert_eq!(name(Calendar::Indian, "hi"), "फाल्गुन");
+        assert_eq!(name(Calendar::Ethiopic, "am"), "መጋቢት");
+        assert_eq!(name(Calendar::Ethiopic, "de"), "Megabit");
+        assert_eq!(name(Calendar::Buddhist, "th"), "มีนาคม");
+        assert_eq!(name(Calendar::Hebrew, "fa"), "Adar II");
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(
+            Calendar::Ethiopic.parse_date("2017-01-01"),
+            Some(gregorian(2024, 9, 11))
+        );
+        assert_eq!(
+            Calendar::Buddhist.parse_date("2567-03-15"),
+            Some(gregorian(2024, 3, 15))
+        );
+        assert_eq!(
+            Calendar::Hebrew.parse_date("-1-01-01"),
+            Calendar::Hebrew.to_date(-1, 1, 1)
+        );
+        assert_eq!(Calendar::Coptic.parse_date("1741-13-07"), None);
+        assert_eq!(Calendar::Indian.parse_date("1946-01"), None);
+        assert_eq!(Calendar::Indian.parse_date("1946-01-01-01"), None);
+    }
+
+    #[test]
+    fn test_invalid_dates_have_no_gregorian_date() {
+        let civil = Calendar::Islamic(IslamicVariant::Civil);
+        assert_eq!(civil.to_date(1445, 13, 1), None);
+        assert_eq!(civil.to_date(1445, 2, 30), None);
+        // 1445 is a leap year, when Dhu al-Hijjah has 30 days; 1446 is not
+        assert!(civil.to_date(1445, 12, 30).is_some());
+        assert_eq!(civil.to_date(1446, 12, 30), None);
+        assert_eq!(Calendar::Hebrew.to_date(5784, 13, 30), None);
+        assert_eq!(Calendar::Hebrew.to_date(5784, 0, 1), None);
+    }
+
+    #[test]
+    fn test_parse_list() {
+        let calendars = Calendar::parse_list("hebrew, islamic-civil,islamic,").unwrap();
+        assert_eq!(
+            calendars,
+            [Calendar::Hebrew, Calendar::Islamic(IslamicVariant::Civil)]
+        );
+        assert!(matches!(
+            Calendar::parse_list("hebrew,islamic-rgsa"),
+            Err(ApiError::InvalidChoice {
+                parameter: "calendars",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_render_names_variant() {
+        let block =
+            Calendar::Islamic(IslamicVariant::Civil).render(&noon(2024, 3, 15), Default::default());
+        assert_eq!(block["variant"], "tabular-civil");
+        assert_eq!(block["year"], 1445);
+        assert_eq!(block["month"], 9);
+        assert!(Calendar::Hebrew
+            .render(&noon(2024, 3, 15), Default::default())
+            .get("variant")
+            .is_none());
+    }
+}
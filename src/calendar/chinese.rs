@@ -0,0 +1,424 @@
+//! The Chinese lunisolar calendar (农历), by the rules in use since 1645.
+//!
+//! Months begin on the day of the new moon in China. The year is counted
+//! from one winter solstice (always in month 11) to the next: when that
+//! span holds 13 new moons, the first month without a major solar term
+//! (a multiple of 30° of solar longitude) is a leap month, numbered like
+//! the month before it. Days are reckoned in Beijing local mean time
+//! (UTC+7:45:40) before 1929 and in UTC+8 since, following Reingold &
+//! Dershowitz, which matches the published tables for 1900-2100. Dates
+//! are read back only in years -50000 to 50000, beyond which the
+//! astronomy drifts too far to number the months consistently.
+
+use std::ops::RangeInclusive;
+
+use super::CalendarDate;
+use crate::astronomy::{
+    estimate_prior_solar_longitude, new_moon_at_or_after_moment, new_moon_before_moment,
+    solar_longitude_at, MEAN_SYNODIC_MONTH, MEAN_TROPICAL_YEAR,
+};
+
+/// R.D. of the first day of the cycle count, in 2637 BCE
+const EPOCH: i64 = -963_099;
+/// Years from the cycle count to the Gregorian year of the same New Year
+const GREGORIAN_OFFSET: i64 = 2_637;
+/// R.D. of 1929-01-01, when China moved from Beijing time to UTC+8
+const STANDARD_TIME: i64 = 704_188;
+/// R.D. of a 甲子 day, such as 1949-10-01 (less 60 × 11861)
+const DAY_EPOCH: i64 = 46;
+/// Years `to_fixed` accepts; further out the astronomy drifts so far that
+/// new moons and solstices no longer give each date a single month
+const YEARS: RangeInclusive<i64> = -50_000..=50_000;
+
+pub(super) const STEMS: [&str; 10] = ["甲", "乙", "丙", "丁", "戊", "己", "庚", "辛", "壬", "癸"];
+pub(super) const BRANCHES: [&str; 12] = [
+    "子", "丑", "寅", "卯", "辰", "巳", "午", "未", "申", "酉", "戌", "亥",
+];
+
+pub(super) const MONTHS: &[(&str, [&str; 12])] = &[
+    (
+        "en",
+        [
+            "First Month",
+            "Second Month",
+            "Third Month",
+            "Fourth Month",
+            "Fifth Month",
+            "Sixth Month",
+            "Seventh Month",
+            "Eighth Month",
+            "Ninth Month",
+            "Tenth Month",
+            "Eleventh Month",
+            "Twelfth Month",
+        ],
+    ),
+    (
+        "zh",
+        [
+            "正月", "二月", "三月", "四月", "五月", "六月", "七月", "八月", "九月", "十月", "冬月",
+            "腊月",
+        ],
+    ),
+];
+
+pub(super) const LEAP_MONTHS: &[(&str, [&str; 12])] = &[
+    (
+        "en",
+        [
+            "Leap First Month",
+            "Leap Second Month",
+            "Leap Third Month",
+            "Leap Fourth Month",
+            "Leap Fifth Month",
+            "Leap Sixth Month",
+            "Leap Seventh Month",
+            "Leap Eighth Month",
+            "Leap Ninth Month",
+            "Leap Tenth Month",
+            "Leap Eleventh Month",
+            "Leap Twelfth Month",
+        ],
+    ),
+    (
+        "zh",
+        [
+            "闰正月",
+            "闰二月",
+            "闰三月",
+            "闰四月",
+            "闰五月",
+            "闰六月",
+            "闰七月",
+            "闰八月",
+            "闰九月",
+            "闰十月",
+            "闰冬月",
+            "闰腊月",
+        ],
+    ),
+];
+
+/// Zodiac animals by earthly branch
+pub(super) const ANIMALS: &[(&str, [&str; 12])] = &[
+    (
+        "en",
+        [
+            "Rat", "Ox", "Tiger", "Rabbit", "Dragon", "Snake", "Horse", "Goat", "Monkey",
+            "Rooster", "Dog", "Pig",
+        ],
+    ),
+    (
+        "zh",
+        [
+            "鼠", "牛", "虎", "兔", "龙", "蛇", "马", "羊", "猴", "鸡", "狗", "猪",
+        ],
+    ),
+];
+
+/// The 24 solar terms, every 15° of solar longitude from the March
+/// equinox
+pub(super) const SOLAR_TERMS: &[(&str, [&str; 24])] = &[
+    (
+        "en",
+        [
+            "Spring Equinox",
+            "Pure Brightness",
+            "Grain Rain",
+            "Start of Summer",
+            "Grain Buds",
+            "Grain in Ear",
+            "Summer Solstice",
+            "Minor Heat",
+            "Major Heat",
+            "Start of Autumn",
+            "End of Heat",
+            "White Dew",
+            "Autumn Equinox",
+            "Cold Dew",
+            "Frost's Descent",
+            "Start of Winter",
+            "Minor Snow",
+            "Major Snow",
+            "Winter Solstice",
+            "Minor Cold",
+            "Major Cold",
+            "Start of Spring",
+            "Rain Water",
+            "Awakening of Insects",
+        ],
+    ),
+    (
+        "zh",
+        [
+            "春分", "清明", "谷雨", "立夏", "小满", "芒种", "夏至", "小暑", "大暑", "立秋", "处暑",
+            "白露", "秋分", "寒露", "霜降", "立冬", "小雪", "大雪", "冬至", "小寒", "大寒", "立春",
+            "雨水", "惊蛰",
+        ],
+    ),
+];
+
+/// A date in the Chinese calendar
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct ChineseDate {
+    /// Gregorian year in which this year's New Year falls
+    pub year: i64,
+    pub month: u32,
+    pub leap_month: bool,
+    pub day: u32,
+    /// The year has 13 months
+    pub leap_year: bool,
+}
+
+/// Hours east of UTC of civil time in China on R.D. `date`
+fn utc_offset_hours(date: i64) -> f64 {
+    if date < STANDARD_TIME {
+        1_397.0 / 180.0
+    } else {
+        8.0
+    }
+}
+
+/// Universal moment at which R.D. `date` begins in China
+fn midnight_in_china(date: i64) -> f64 {
+    date as f64 - utc_offset_hours(date) / 24.0
+}
+
+/// R.D. date in China at universal `moment`
+fn date_in_china(moment: f64) -> i64 {
+    let date = moment.floor() as i64;
+    (moment + utc_offset_hours(date) / 24.0).floor() as i64
+}
+
+/// Index 1-12 of the last major solar term at the start of `date`, where
+/// 1 is Rain Water (330°)
+fn major_solar_term(date: i64) -> i64 {
+    let longitude = solar_longitude_at(midnight_in_china(date));
+    (2 + (longitude / 30.0).floor() as i64 - 1).rem_euclid(12) + 1
+}
+
+fn winter_solstice_on_or_before(date: i64) -> i64 {
+    let estimate = estimate_prior_solar_longitude(270.0, midnight_in_china(date + 1));
+    let mut day = estimate.floor() as i64 - 1;
+    while solar_longitude_at(midnight_in_china(day + 1)) <= 270.0 {
+        day += 1;
+    }
+    day
+}
+
+fn new_moon_on_or_after(date: i64) -> i64 {
+    date_in_china(new_moon_at_or_after_moment(midnight_in_china(date)))
+}
+
+fn new_moon_before(date: i64) -> i64 {
+    date_in_china(new_moon_before_moment(midnight_in_china(date)))
+}
+
+/// The month starting on `month_start` contains no major solar term
+fn no_major_solar_term(month_start: i64) -> bool {
+    major_solar_term(month_start) == major_solar_term(new_moon_on_or_after(month_start + 1))
+}
+
+/// A leap month falls between the months starting `earlier` and `later`,
+/// inclusive
+fn prior_leap_month(earlier: i64, mut later: i64) -> bool {
+    while later >= earlier {
+        if no_major_solar_term(later) {
+            return true;
+        }
+        later = new_moon_before(later);
+    }
+    false
+}
+
+/// Lunations between two month starts
+fn lunations(from: i64, to: i64) -> i64 {
+    ((to - from) as f64 / MEAN_SYNODIC_MONTH).round() as i64
+}
+
+/// New Year of the year running from the winter solstice on or before
+/// `date`
+fn new_year_in_sui(date: i64) -> i64 {
+    let solstice = winter_solstice_on_or_before(date);
+    let next_solstice = winter_solstice_on_or_before(solstice + 370);
+    let month_12 = new_moon_on_or_after(solstice + 1);
+    let month_13 = new_moon_on_or_after(month_12 + 1);
+    let next_month_11 = new_moon_before(next_solstice + 1);
+    if lunations(month_12, next_month_11) == 12
+        && (no_major_solar_term(month_12) || no_major_solar_term(month_13))
+    {
+        new_moon_on_or_after(month_13 + 1)
+    } else {
+        month_13
+    }
+}
+
+fn new_year_on_or_before(date: i64) -> i64 {
+    let new_year = new_year_in_sui(date);
+    if date >= new_year {
+        new_year
+    } else {
+        new_year_in_sui(date - 180)
+    }
+}
+
+pub(super) fn from_fixed(date: i64) -> ChineseDate {
+    let solstice = winter_solstice_on_or_before(date);
+    let next_solstice = winter_solstice_on_or_before(solstice + 370);
+    let month_12 = new_moon_on_or_after(solstice + 1);
+    let next_month_11 = new_moon_before(next_solstice + 1);
+    let month_start = new_moon_before(date + 1);
+    let leap_sui = lunations(month_12, next_month_11) == 12;
+    let after_leap = leap_sui && prior_leap_month(month_12, month_start);
+    let month = (lunations(month_12, month_start) - i64::from(after_leap) - 1).rem_euclid(12) + 1;
+    let leap_month = leap_sui
+        && no_major_solar_term(month_start)
+        && !prior_leap_month(month_12, new_moon_before(month_start));
+    let elapsed =
+        (1.5 - month as f64 / 12.0 + (date - EPOCH) as f64 / MEAN_TROPICAL_YEAR).floor() as i64;
+    let year = elapsed - GREGORIAN_OFFSET;
+    let new_year = new_year_on_or_before(date);
+    let next_new_year = new_year_on_or_before(new_year + 400);
+    ChineseDate {
+        year,
+        month: month as u32,
+        leap_month,
+        day: (date - month_start + 1) as u32,
+        leap_year: lunations(new_year, next_new_year) == 13,
+    }
+}
+
+/// R.D. of a Chinese date, or `None` if it does not exist
+pub(super) fn to_fixed(year: i64, month: u32, leap_month: bool, day: u32) -> Option<i64> {
+    if !YEARS.contains(&year) || !(1..=12).contains(&month) || !(1..=30).contains(&day) {
+        return None;
+    }
+    let elapsed = (year + GREGORIAN_OFFSET) as f64;
+    let mid_year = (EPOCH as f64 + (elapsed - 0.5) * MEAN_TROPICAL_YEAR).floor() as i64;
+    let new_year = new_year_on_or_before(mid_year);
+    let guess = new_moon_on_or_after(new_year + 29 * i64::from(month - 1));
+    let found = from_fixed(guess);
+    let month_start = if found.month == month && found.leap_month == leap_month {
+        guess
+    } else {
+        new_moon_on_or_after(guess + 1)
+    };
+    let date = month_start + i64::from(day) - 1;
+    let check = from_fixed(date);
+    (check.year == year
+        && check.month == month
+        && check.leap_month == leap_month
+        && check.day == day)
+        .then_some(date)
+}
+
+impl ChineseDate {
+    /// Index 0-59 of this year in the sexagenary cycle; 0 is 甲子
+    pub fn year_index(&self) -> usize {
+        (self.year - 4).rem_euclid(60) as usize
+    }
+
+    /// Stem and branch of the year, such as `甲辰`
+    pub fn year_pillar(&self) -> String {
+        sexagenary(self.year_index())
+    }
+
+    /// Stem and branch of the month; a leap month shares its predecessor's
+    pub fn month_pillar(&self) -> String {
+        let stem = (self.year_index() % 10 * 2 + 2 + self.month as usize - 1) % 10;
+        let branch = (self.month as usize + 1) % 12;
+        format!("{}{}", STEMS[stem], BRANCHES[branch])
+    }
+
+    /// Index 0-11 of the year's earthly branch, which names its animal
+    pub fn branch(&self) -> usize {
+        self.year_index() % 12
+    }
+}
+
+/// Stem and branch of the day R.D. `date`, such as `甲子`
+pub(super) fn day_pillar(date: i64) -> String {
+    sexagenary((date - DAY_EPOCH).rem_euclid(60) as usize)
+}
+
+fn sexagenary(index: usize) -> String {
+    format!("{}{}", STEMS[index % 10], BRANCHES[index % 12])
+}
+
+impl From<ChineseDate> for CalendarDate {
+    fn from(date: ChineseDate) -> Self {
+        let names = if date.leap_month { LEAP_MONTHS } else { MONTHS };
+        CalendarDate {
+            year: date.year,
+            month: date.month,
+            month_name: names[0].1[date.month as usize - 1],
+            day: date.day,
+            leap_year: date.leap_year,
+            leap_month: date.leap_month,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn fixed(y: i32, m: u32, d: u32) -> i64 {
+        use chrono::Datelike;
+        i64::from(NaiveDate::from_ymd_opt(y, m, d).unwrap().num_days_from_ce())
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // Two years either side of the 2033 leap eleventh month
+        for date in fixed(2032, 6, 1)..fixed(2034, 6, 1) {
+            let chinese = from_fixed(date);
+            let back = to_fixed(chinese.year, chinese.month, chinese.leap_month, chinese.day);
+            assert_eq!(back, Some(date), "{:?}", chinese);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_in_distant_years() {
+        // Proleptic years long before 1645, before year zero, and at both
+        // ends of the years to_fixed accepts
+        for (year, month) in [(1000, 1), (-500, 6), (-50_000, 3), (50_000, 6)] {
+            let start = fixed(year, month, 1);
+            for date in start..start + 120 {
+                let chinese = from_fixed(date);
+                assert!((1..=12).contains(&chinese.month), "{:?}", chinese);
+                assert!((1..=30).contains(&chinese.day), "{:?}", chinese);
+                assert!(
+                    (i64::from(year) - 1..=i64::from(year)).contains(&chinese.year),
+                    "{:?}",
+                    chinese
+                );
+                let back = to_fixed(chinese.year, chinese.month, chinese.leap_month, chinese.day);
+                assert_eq!(back, Some(date), "{:?}", chinese);
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_fixed_rejects_years_out_of_range() {
+        for year in [
+            i64::MIN,
+            -50_001,
+            50_001,
+            i64::MAX - GREGORIAN_OFFSET,
+            i64::MAX,
+        ] {
+            assert_eq!(to_fixed(year, 1, false, 1), None, "{}", year);
+        }
+    }
+
+    #[test]
+    fn test_pillars() {
+        let new_year = from_fixed(fixed(2024, 2, 10));
+        assert_eq!(new_year.year_pillar(), "甲辰");
+        assert_eq!(new_year.month_pillar(), "丙寅");
+        assert_eq!(day_pillar(fixed(1949, 10, 1)), "甲子");
+        assert_eq!(day_pillar(fixed(2000, 1, 1)), "戊午");
+    }
+}
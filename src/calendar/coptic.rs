@@ -0,0 +1,126 @@
+//! The Coptic and Ethiopian calendars.
+//!
+//! Both have twelve months of 30 days followed by a short thirteenth month
+//! of 5 days, or 6 in a leap year (every fourth year, with no century
+//! exceptions, so they drift against the Gregorian calendar as the Julian
+//! does). They differ only in epoch and month names: the Coptic era counts
+//! from 284 CE (Era of Martyrs), the Ethiopian from 8 CE (Amete Mihret).
+
+use super::CalendarDate;
+
+/// R.D. of 1 Thout AM 1 (29 August 284, Julian)
+const COPTIC_EPOCH: i64 = 103_605;
+/// R.D. of 1 Meskerem 1 (29 August 8, Julian)
+const ETHIOPIC_EPOCH: i64 = 2_796;
+
+pub(super) const COPTIC_MONTHS: &[(&str, [&str; 13])] = &[(
+    "en",
+    [
+        "Tout",
+        "Baba",
+        "Hator",
+        "Kiahk",
+        "Toba",
+        "Amshir",
+        "Baramhat",
+        "Baramouda",
+        "Bashans",
+        "Paona",
+        "Epep",
+        "Mesra",
+        "Nasie",
+    ],
+)];
+
+pub(super) const ETHIOPIC_MONTHS: &[(&str, [&str; 13])] = &[
+    (
+        "en",
+        [
+            "Meskerem", "Tekemt", "Hedar", "Tahsas", "Ter", "Yekatit", "Megabit", "Miazia",
+            "Genbot", "Sene", "Hamle", "Nehasse", "Pagumen",
+        ],
+    ),
+    (
+        "am",
+        [
+            "መስከረም",
+            "ጥቅምት",
+            "ኅዳር",
+            "ታኅሣሥ",
+            "ጥር",
+            "የካቲት",
+            "መጋቢት",
+            "ሚያዝያ",
+            "ግንቦት",
+            "ሰኔ",
+            "ሐምሌ",
+            "ነሐሴ",
+            "ጳጉሜን",
+        ],
+    ),
+];
+
+/// Epoch of the Ethiopian (`true`) or Coptic (`false`) era
+fn epoch(ethiopic: bool) -> i64 {
+    if ethiopic {
+        ETHIOPIC_EPOCH
+    } else {
+        COPTIC_EPOCH
+    }
+}
+
+pub(super) fn is_leap_year(year: i64) -> bool {
+    year.rem_euclid(4) == 3
+}
+
+pub(super) fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        13 if is_leap_year(year) => 6,
+        13 => 5,
+        _ => 30,
+    }
+}
+
+/// R.D. of a Coptic or Ethiopian date, which must be valid
+pub(super) fn to_fixed(ethiopic: bool, year: i64, month: u32, day: u32) -> i64 {
+    epoch(ethiopic) - 1
+        + 365 * (year - 1)
+        + year.div_euclid(4)
+        + 30 * (i64::from(month) - 1)
+        + i64::from(day)
+}
+
+pub(super) fn from_fixed(ethiopic: bool, fixed: i64) -> CalendarDate {
+    let year = (4 * (fixed - epoch(ethiopic)) + 1463).div_euclid(1461);
+    let month = 1 + (fixed - to_fixed(ethiopic, year, 1, 1)).div_euclid(30);
+    let day = fixed + 1 - to_fixed(ethiopic, year, month as u32, 1);
+    let names = if ethiopic {
+        ETHIOPIC_MONTHS
+    } else {
+        COPTIC_MONTHS
+    };
+    CalendarDate {
+        year,
+        month: month as u32,
+        month_name: names[0].1[month as usize - 1],
+        day: day as u32,
+        leap_year: is_leap_year(year),
+        leap_month: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for ethiopic in [true, false] {
+            for fixed in 700_000..750_000 {
+                let date = from_fixed(ethiopic, fixed);
+                assert!(date.day <= days_in_month(date.year, date.month));
+                assert_eq!(to_fixed(ethiopic, date.year, date.month, date.day), fixed);
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! The arithmetic Hebrew calendar.
+//!
+//! Months are numbered from Nisan (1), as in the Bible and in Reingold &
+//! Dershowitz, *Calendrical Calculations*; the year begins with Tishri (7).
+//! Leap years, 7 in every 19, have a thirteenth month: Adar I (12) is the
+//! added month and Adar II (13) keeps the festivals of Adar.
+
+use super::CalendarDate;
+
+/// R.D. of 1 Tishri AM 1 (7 October 3761 BCE, Julian)
+const EPOCH: i64 = -1_373_427;
+
+const NISAN: u32 = 1;
+const TISHRI: u32 = 7;
+
+const MONTHS: [&str; 13] = [
+    "Nisan", "Iyyar", "Sivan", "Tammuz", "Av", "Elul", "Tishri", "Heshvan", "Kislev", "Tevet",
+    "Shevat", "Adar", "Adar II",
+];
+
+pub(super) fn is_leap_year(year: i64) -> bool {
+    (7 * year + 1).rem_euclid(19) < 7
+}
+
+fn last_month(year: i64) -> u32 {
+    if is_leap_year(year) {
+        13
+    } else {
+        12
+    }
+}
+
+/// Days from the epoch to the molad-based start of `year`, with the
+/// "molad zaken" and weekday postponements applied
+fn elapsed_days(year: i64) -> i64 {
+    let months = (235 * year - 234).div_euclid(19);
+    let parts = 12_084 + 13_753 * months;
+    let days = 29 * months + parts.div_euclid(25_920);
+    if (3 * (days + 1)).rem_euclid(7) < 3 {
+        days + 1
+    } else {
+        days
+    }
+}
+
+/// Delay that keeps year lengths within 353-355 or 383-385 days
+fn year_length_correction(year: i64) -> i64 {
+    let (before, this, after) = (
+        elapsed_days(year - 1),
+        elapsed_days(year),
+        elapsed_days(year + 1),
+    );
+    if after - this == 356 {
+        2
+    } else if this - before == 382 {
+        1
+    } else {
+        0
+    }
+}
+
+fn new_year(year: i64) -> i64 {
+    EPOCH + elapsed_days(year) + year_length_correction(year)
+}
+
+fn days_in_year(year: i64) -> i64 {
+    new_year(year + 1) - new_year(year)
+}
+
+pub(super) fn days_in_month(year: i64, month: u32) -> u32 {
+    let length = days_in_year(year);
+    let short = match month {
+        2 | 4 | 6 | 10 | 13 => true,
+        12 => !is_leap_year(year),
+        8 => length % 10 != 5,
+        9 => length % 10 == 3,
+        _ => false,
+    };
+    if short {
+        29
+    } else {
+        30
+    }
+}
+
+/// R.D. of a Hebrew date, which must be valid
+pub(super) fn to_fixed(year: i64, month: u32, day: u32) -> i64 {
+    let months_before: i64 = if month < TISHRI {
+        (TISHRI..=last_month(year))
+            .chain(NISAN..month)
+            .map(|m| i64::from(days_in_month(year, m)))
+            .sum()
+    } else {
+        (TISHRI..month)
+            .map(|m| i64::from(days_in_month(year, m)))
+            .sum()
+    };
+    new_year(year) + months_before + i64::from(day) - 1
+}
+
+pub(super) fn from_fixed(fixed: i64) -> CalendarDate {
+    // Mean year length is 35975351/98496 days
+    let approx = ((fixed - EPOCH) * 98_496).div_euclid(35_975_351) + 1;
+    let mut year = approx - 1;
+    while new_year(year + 1) <= fixed {
+        year += 1;
+    }
+    let start = if fixed < to_fixed(year, NISAN, 1) {
+        TISHRI
+    } else {
+        NISAN
+    };
+    let month = (start..=last_month(year))
+        .find(|&m| fixed <= to_fixed(year, m, days_in_month(year, m)))
+        .expect("a Hebrew year covers every day up to the next new year");
+    let day = (fixed - to_fixed(year, month, 1) + 1) as u32;
+    let leap_year = is_leap_year(year);
+    CalendarDate {
+        year,
+        month,
+        month_name: if leap_year && month == 12 {
+            "Adar I"
+        } else {
+            MONTHS[month as usize - 1]
+        },
+        day,
+        leap_year,
+        leap_month: leap_year && month == 12,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_year_lengths_are_valid() {
+        for year in 5600..6000 {
+            let length = days_in_year(year);
+            let valid: &[i64] = if is_leap_year(year) {
+                &[383, 384, 385]
+            } else {
+                &[353, 354, 355]
+            };
+            assert!(valid.contains(&length), "{} has {} days", year, length);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for fixed in 700_000..750_000 {
+            let date = from_fixed(fixed);
+            assert_eq!(to_fixed(date.year, date.month, date.day), fixed);
+        }
+    }
+}
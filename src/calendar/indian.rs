@@ -0,0 +1,101 @@
+//! The Indian National (Saka) calendar.
+//!
+//! Adopted in 1957 and kept in step with the Gregorian calendar: Saka years
+//! are 78 behind, and the year begins on 22 March, or 21 March when the
+//! Gregorian year is a leap year, in which case Chaitra has 31 days rather
+//! than 30. Vaisakha to Bhadra have 31 days and the rest 30.
+
+use super::{gregorian_leap_year, gregorian_to_fixed, gregorian_year, CalendarDate};
+
+/// Years between the Gregorian and Saka eras
+const OFFSET: i64 = 78;
+
+pub(super) const MONTHS: &[(&str, [&str; 12])] = &[
+    (
+        "en",
+        [
+            "Chaitra",
+            "Vaisakha",
+            "Jyaistha",
+            "Asadha",
+            "Sravana",
+            "Bhadra",
+            "Asvina",
+            "Kartika",
+            "Agrahayana",
+            "Pausa",
+            "Magha",
+            "Phalguna",
+        ],
+    ),
+    (
+        "hi",
+        [
+            "चैत्र",
+            "वैशाख",
+            "ज्येष्ठ",
+            "आषाढ़",
+            "श्रावण",
+            "भाद्रपद",
+            "आश्विन",
+            "कार्तिक",
+            "अग्रहायण",
+            "पौष",
+            "माघ",
+            "फाल्गुन",
+        ],
+    ),
+];
+
+pub(super) fn is_leap_year(year: i64) -> bool {
+    gregorian_leap_year(year + OFFSET)
+}
+
+pub(super) fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        1 if is_leap_year(year) => 31,
+        2..=6 => 31,
+        _ => 30,
+    }
+}
+
+/// R.D. of a Saka date, which must be valid
+pub(super) fn to_fixed(year: i64, month: u32, day: u32) -> i64 {
+    let start = if is_leap_year(year) { 21 } else { 22 };
+    let before: i64 = (1..month).map(|m| i64::from(days_in_month(year, m))).sum();
+    gregorian_to_fixed(year + OFFSET, 3, start) + before + i64::from(day) - 1
+}
+
+pub(super) fn from_fixed(fixed: i64) -> CalendarDate {
+    let mut year = gregorian_year(fixed) - OFFSET;
+    if fixed < to_fixed(year, 1, 1) {
+        year -= 1;
+    }
+    let mut day = fixed - to_fixed(year, 1, 1);
+    let mut month = 1;
+    while day >= i64::from(days_in_month(year, month)) {
+        day -= i64::from(days_in_month(year, month));
+        month += 1;
+    }
+    CalendarDate {
+        year,
+        month,
+        month_name: MONTHS[0].1[month as usize - 1],
+        day: day as u32 + 1,
+        leap_year: is_leap_year(year),
+        leap_month: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for fixed in 700_000..750_000 {
+            let date = from_fixed(fixed);
+            assert_eq!(to_fixed(date.year, date.month, date.day), fixed);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! The tabular (arithmetic) Islamic calendar.
+//!
+//! Years have 354 days, or 355 in the 11 leap years of each 30-year cycle
+//! (years 2, 5, 7, 10, 13, 16, 18, 21, 24, 26 and 29), when Dhu al-Hijjah
+//! gains a thirtieth day. Months alternate 30 and 29 days starting with
+//! Muharram. The civil epoch is Friday 16 July 622 (Julian); the
+//! astronomical one is the day before.
+
+use super::CalendarDate;
+
+/// R.D. of 1 Muharram AH 1 in the civil (Friday) epoch
+const CIVIL_EPOCH: i64 = 227_015;
+
+pub(super) const MONTHS: [&str; 12] = [
+    "Muharram",
+    "Safar",
+    "Rabi' al-Awwal",
+    "Rabi' al-Thani",
+    "Jumada al-Awwal",
+    "Jumada al-Thani",
+    "Rajab",
+    "Sha'ban",
+    "Ramadan",
+    "Shawwal",
+    "Dhu al-Qi'dah",
+    "Dhu al-Hijjah",
+];
+
+/// Epoch of the civil (`true`) or astronomical (`false`) reckoning
+fn epoch(civil: bool) -> i64 {
+    if civil {
+        CIVIL_EPOCH
+    } else {
+        CIVIL_EPOCH - 1
+    }
+}
+
+pub(super) fn is_leap_year(year: i64) -> bool {
+    (14 + 11 * year).rem_euclid(30) < 11
+}
+
+pub(super) fn days_in_month(year: i64, month: u32) -> u32 {
+    if month % 2 == 1 || (month == 12 && is_leap_year(year)) {
+        30
+    } else {
+        29
+    }
+}
+
+/// R.D. of an Islamic date, which must be valid
+pub(super) fn to_fixed(civil: bool, year: i64, month: u32, day: u32) -> i64 {
+    let month = i64::from(month);
+    epoch(civil) - 1
+        + (year - 1) * 354
+        + (3 + 11 * year).div_euclid(30)
+        + 29 * (month - 1)
+        + (6 * month - 1).div_euclid(11)
+        + i64::from(day)
+}
+
+pub(super) fn from_fixed(civil: bool, fixed: i64) -> CalendarDate {
+    let year = (30 * (fixed - epoch(civil)) + 10_646).div_euclid(10_631);
+    let into_year = fixed - to_fixed(civil, year, 1, 1);
+    let month = (11 * into_year + 330).div_euclid(325) as u32;
+    let day = (fixed - to_fixed(civil, year, month, 1) + 1) as u32;
+    CalendarDate {
+        year,
+        month,
+        month_name: MONTHS[month as usize - 1],
+        day,
+        leap_year: is_leap_year(year),
+        leap_month: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_has_eleven_leap_years() {
+        let leap: Vec<i64> = (1..=30).filter(|&y| is_leap_year(y)).collect();
+        assert_eq!(leap, [2, 5, 7, 10, 13, 16, 18, 21, 24, 26, 29]);
+        let cycle: i64 = (1..=30)
+            .map(|y| {
+                (1..=12)
+                    .map(|m| i64::from(days_in_month(y, m)))
+                    .sum::<i64>()
+            })
+            .sum();
+        assert_eq!(cycle, 10_631);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for civil in [true, false] {
+            for fixed in 700_000..750_000 {
+                let date = from_fixed(civil, fixed);
+                assert_eq!(to_fixed(civil, date.year, date.month, date.day), fixed);
+                assert!(date.day <= days_in_month(date.year, date.month));
+            }
+        }
+    }
+}
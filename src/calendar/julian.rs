@@ -0,0 +1,197 @@
+//! The Julian calendar, and the historical changeover to the Gregorian.
+//!
+//! Julian years are numbered astronomically like the Gregorian ones here:
+//! year 0 is 1 BCE. Every fourth year is a leap year, so the Julian date
+//! drifts a day behind the Gregorian every century not divisible by 400.
+//!
+//! Countries switched at different times by skipping the days that had
+//! drifted; the first Gregorian day of each changeover is in [`CUTOVERS`].
+//! Dates between the last Julian day and that first Gregorian day never
+//! existed there.
+
+use chrono::{Datelike, NaiveDate};
+
+use super::CalendarDate;
+use crate::locale::Locale;
+
+/// R.D. of 1 January 1 (Julian), which is 30 December 0 (Gregorian)
+const EPOCH: i64 = -1;
+
+/// A changeover from the Julian to the Gregorian calendar
+#[derive(Debug, PartialEq, Eq)]
+pub struct Cutover {
+    /// Calendar name, such as `historical-gb`
+    pub name: &'static str,
+    /// Where the changeover applied, for error messages
+    pub region: &'static str,
+    /// First day of the Gregorian calendar
+    pub first_gregorian: NaiveDate,
+}
+
+const fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("cutover is a valid date")
+}
+
+/// Known changeovers; the first, the papal reform of 1582, is the default
+pub static CUTOVERS: &[Cutover] = &[
+    Cutover {
+        name: "historical",
+        region: "the Papal States, Spain, Portugal and Poland",
+        first_gregorian: ymd(1582, 10, 15),
+    },
+    Cutover {
+        name: "historical-fr",
+        region: "France",
+        first_gregorian: ymd(1582, 12, 20),
+    },
+    Cutover {
+        name: "historical-dk",
+        region: "Denmark-Norway",
+        first_gregorian: ymd(1700, 3, 1),
+    },
+    Cutover {
+        name: "historical-gb",
+        region: "Britain and its colonies",
+        first_gregorian: ymd(1752, 9, 14),
+    },
+    Cutover {
+        name: "historical-us",
+        region: "Britain and its colonies",
+        first_gregorian: ymd(1752, 9, 14),
+    },
+    Cutover {
+        name: "historical-se",
+        region: "Sweden",
+        first_gregorian: ymd(1753, 3, 1),
+    },
+    Cutover {
+        name: "historical-bg",
+        region: "Bulgaria",
+        first_gregorian: ymd(1916, 4, 14),
+    },
+    Cutover {
+        name: "historical-ru",
+        region: "Russia",
+        first_gregorian: ymd(1918, 2, 14),
+    },
+    Cutover {
+        name: "historical-ro",
+        region: "Romania",
+        first_gregorian: ymd(1919, 4, 14),
+    },
+    Cutover {
+        name: "historical-gr",
+        region: "Greece",
+        first_gregorian: ymd(1923, 3, 1),
+    },
+];
+
+impl Cutover {
+    /// R.D. of the first Gregorian day
+    pub(super) fn first_fixed(&self) -> i64 {
+        i64::from(self.first_gregorian.num_days_from_ce())
+    }
+
+    /// Why `year-month-day` does not exist here, when it is a valid Julian
+    /// date in the skipped days
+    pub(super) fn gap(&self, year: i64, month: u32, day: u32) -> Option<String> {
+        let first = self.first_fixed();
+        let fixed = to_fixed(year, month, day);
+        let gregorian_after = i32::try_from(year)
+            .ok()
+            .and_then(|year| NaiveDate::from_ymd_opt(year, month, day))
+            .is_some_and(|date| date >= self.first_gregorian);
+        if !is_valid(year, month, day) || fixed < first || gregorian_after {
+            return None;
+        }
+        let last = from_fixed(first - 1);
+        Some(format!(
+            "{:04}-{:02}-{:02} was skipped in {}: the Julian calendar ended on \
+             {:04}-{:02}-{:02} and the Gregorian began on {}",
+            year, month, day, self.region, last.year, last.month, last.day, self.first_gregorian
+        ))
+    }
+}
+
+pub(super) fn is_leap_year(year: i64) -> bool {
+    year.rem_euclid(4) == 0
+}
+
+pub(super) fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+pub(super) fn is_valid(year: i64, month: u32, day: u32) -> bool {
+    (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month)
+}
+
+/// R.D. of a Julian date, which must be valid
+pub(super) fn to_fixed(year: i64, month: u32, day: u32) -> i64 {
+    let prior = year - 1;
+    let month = i64::from(month);
+    let correction = if month <= 2 {
+        0
+    } else if is_leap_year(year) {
+        -1
+    } else {
+        -2
+    };
+    EPOCH - 1
+        + 365 * prior
+        + prior.div_euclid(4)
+        + (367 * month - 362).div_euclid(12)
+        + correction
+        + i64::from(day)
+}
+
+pub(super) fn from_fixed(fixed: i64) -> CalendarDate {
+    let year = (4 * (fixed - EPOCH) + 1464).div_euclid(1461);
+    let prior_days = fixed - to_fixed(year, 1, 1);
+    let correction = if fixed < to_fixed(year, 3, 1) {
+        0
+    } else if is_leap_year(year) {
+        1
+    } else {
+        2
+    };
+    let month = (12 * (prior_days + correction) + 373).div_euclid(367) as u32;
+    let day = (fixed - to_fixed(year, month, 1) + 1) as u32;
+    CalendarDate {
+        year,
+        month,
+        month_name: <&Locale>::default().month_name(month),
+        day,
+        leap_year: is_leap_year(year),
+        leap_month: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for fixed in -800_000..750_000 {
+            let date = from_fixed(fixed);
+            assert!(is_valid(date.year, date.month, date.day));
+            assert_eq!(to_fixed(date.year, date.month, date.day), fixed);
+        }
+    }
+
+    #[test]
+    fn test_cutovers_follow_their_last_julian_day() {
+        for cutover in CUTOVERS {
+            let last = from_fixed(cutover.first_fixed() - 1);
+            assert_eq!(cutover.gap(last.year, last.month, last.day), None);
+            let skipped = from_fixed(cutover.first_fixed());
+            let reason = cutover.gap(skipped.year, skipped.month, skipped.day);
+            assert!(reason.is_some(), "{}", cutover.name);
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! The Persian (Solar Hijri) calendar of Iran and Afghanistan.
+//!
+//! The year begins at Nowruz, the day of the March equinox as observed in
+//! Tehran. The first six months have 31 days, the next five 30, and Esfand
+//! has 29, or 30 in a leap year. Leap years follow Borkowski's break table,
+//! which reproduces the astronomical rule for AP -61 to 3177 (about 560 to
+//! 3798 CE); outside it the 33-year cycle of the last segment is continued.
+
+use super::{gregorian_to_fixed, gregorian_year, CalendarDate};
+
+/// Years at which the 33-year leap pattern restarts
+const BREAKS: [i64; 20] = [
+    -61, 9, 38, 199, 426, 686, 756, 818, 1111, 1181, 1210, 1635, 2060, 2097, 2192, 2262, 2324,
+    2394, 2456, 3178,
+];
+
+pub(super) const MONTHS: &[(&str, [&str; 12])] = &[
+    (
+        "en",
+        [
+            "Farvardin",
+            "Ordibehesht",
+            "Khordad",
+            "Tir",
+            "Mordad",
+            "Shahrivar",
+            "Mehr",
+            "Aban",
+            "Azar",
+            "Dey",
+            "Bahman",
+            "Esfand",
+        ],
+    ),
+    (
+        "fa",
+        [
+            "فروردین",
+            "اردیبهشت",
+            "خرداد",
+            "تیر",
+            "مرداد",
+            "شهریور",
+            "مهر",
+            "آبان",
+            "آذر",
+            "دی",
+            "بهمن",
+            "اسفند",
+        ],
+    ),
+];
+
+/// Whether `year` is a leap year, and the R.D. of its Nowruz
+fn year_start(year: i64) -> (bool, i64) {
+    // Leap days counted from AP -61 up to the segment holding `year`
+    let mut leap_days = -14;
+    let mut segment_start = BREAKS[0];
+    // Length of that segment; `None` beyond either end of the table
+    let mut jump = None;
+    if year >= BREAKS[0] {
+        for &next in &BREAKS[1..] {
+            if year < next {
+                jump = Some(next - segment_start);
+                break;
+            }
+            let length = next - segment_start;
+            leap_days += length / 33 * 8 + length % 33 / 4;
+            segment_start = next;
+        }
+    }
+    let mut n = year - segment_start;
+    leap_days += n.div_euclid(33) * 8 + (n.rem_euclid(33) + 3) / 4;
+    if jump.is_some_and(|jump| jump % 33 == 4 && jump - n == 4) {
+        leap_days += 1;
+    }
+
+    let gregorian = year + 621;
+    let gregorian_leap_days =
+        gregorian.div_euclid(4) - ((gregorian.div_euclid(100) + 1) * 3).div_euclid(4) - 150;
+    let march_day = 20 + leap_days - gregorian_leap_days;
+
+    if let Some(jump) = jump.filter(|&jump| jump - n < 6) {
+        n = n - jump + (jump + 4) / 33 * 33;
+    }
+    let position = (n + 1).rem_euclid(33) - 1;
+    let leap = position >= 0 && position % 4 == 0;
+    (leap, gregorian_to_fixed(gregorian, 3, 1) + march_day - 1)
+}
+
+pub(super) fn is_leap_year(year: i64) -> bool {
+    year_start(year).0
+}
+
+pub(super) fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        1..=6 => 31,
+        7..=11 => 30,
+        _ if is_leap_year(year) => 30,
+        _ => 29,
+    }
+}
+
+/// R.D. of a Persian date, which must be valid
+pub(super) fn to_fixed(year: i64, month: u32, day: u32) -> i64 {
+    let month = i64::from(month);
+    let before = if month <= 7 {
+        31 * (month - 1)
+    } else {
+        186 + 30 * (month - 7)
+    };
+    year_start(year).1 + before + i64::from(day) - 1
+}
+
+pub(super) fn from_fixed(fixed: i64) -> CalendarDate {
+    let mut year = gregorian_year(fixed) - 621;
+    let (mut leap_year, mut start) = year_start(year);
+    if fixed < start {
+        year -= 1;
+        (leap_year, start) = year_start(year);
+    }
+    let into_year = fixed - start;
+    let (month, day) = if into_year < 186 {
+        (1 + into_year / 31, 1 + into_year % 31)
+    } else {
+        (7 + (into_year - 186) / 30, 1 + (into_year - 186) % 30)
+    };
+    CalendarDate {
+        year,
+        month: month as u32,
+        month_name: MONTHS[0].1[month as usize - 1],
+        day: day as u32,
+        leap_year,
+        leap_month: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leap_years() {
+        let leap: Vec<i64> = (1390..1412).filter(|&y| is_leap_year(y)).collect();
+        assert_eq!(leap, [1391, 1395, 1399, 1403, 1408]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // Across the end of the break table and back before its start
+        for fixed in (150_000..250_000).chain(1_370_000..1_390_000) {
+            let date = from_fixed(fixed);
+            assert!(date.day <= days_in_month(date.year, date.month));
+            assert_eq!(to_fixed(date.year, date.month, date.day), fixed);
+        }
+        for fixed in 700_000..750_000 {
+            let date = from_fixed(fixed);
+            assert_eq!(to_fixed(date.year, date.month, date.day), fixed);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! The Umm al-Qura calendar of Saudi Arabia.
+//!
+//! Its months begin by a published table rather than a rule: a month has 30
+//! days when the moon sets after the sun at Mecca on its 29th evening, which
+//! the table records for AH 1300 to 1600 (1882-11-12 to 2174-11-25). Outside
+//! those years dates follow the civil tabular calendar, which meets the
+//! table without a gap at both ends.
+
+use super::{islamic, CalendarDate};
+
+/// First and last years in the table
+const FIRST_YEAR: i64 = 1300;
+const LAST_YEAR: i64 = 1600;
+/// R.D. of 1 Muharram AH 1300
+const FIRST_DAY: i64 = 687337;
+
+/// The 30-day months of each year from AH 1300, with Muharram in the lowest
+/// bit; the others have 29 days
+const MONTH_LENGTHS: [u16; 301] = [
+    0x555, 0x2AB, 0x937, 0x2B6, 0x576, 0x36C, 0xB55, 0xAAA, 0x956, 0x49E, // 1300
+    0x95D, 0x2BA, 0x5B5, 0x3AA, 0xB4B, 0xA96, 0x52E, 0x2AD, 0x56D, 0xB5A, // 1310
+    0x752, 0xF25, 0xE8A, 0xD16, 0xA56, 0xAB5, 0x6B4, 0xDA9, 0xB92, 0xB25, // 1320
+    0x64B, 0xA9B, 0x35A, 0x6D9, 0x5D4, 0xDA5, 0xD4A, 0xA95, 0x536, 0x975, // 1330
+    0x2F4, 0x6E9, 0x6D4, 0x6A9, 0x535, 0x25D, 0x4BD, 0x9BA, 0x3B4, 0xB69, // 1340
+    0xB2A, 0xA55, 0x4AD, 0xA5D, 0x2DA, 0x6D9, 0xEAA, 0xE94, 0xD2A, 0xC56, // 1350
+    0x4AE, 0xA6D, 0x56A, 0xD55, 0xD4A, 0xA93, 0x52B, 0xA5B, 0x53A, 0x6B5, // 1360
+    0xEA9, 0xD52, 0xD29, 0xA55, 0x4AD, 0x56D, 0xAEA, 0x6E4, 0xED1, 0xDA2, // 1370
+    0xAAA, 0x95A, 0x2DA, 0x5B9, 0xBB2, 0x764, 0x6C9, 0x555, 0x2AB, 0x4DB, // 1380
+    0xABA, 0x5B4, 0xDA9, 0xD52, 0xAA5, 0x92D, 0x26D, 0x8ED, 0x2DA, 0xAD5, // 1390
+    0xAA5, 0xA4B, 0x497, 0x937, 0x2B6, 0x975, 0xD69, 0xD52, 0xC95, 0x92B, // 1400
+    0x25B, 0x4DB, 0x9D5, 0x5D2, 0xDA5, 0xD4A, 0xA95, 0x54D, 0xAAD, 0x3AA, // 1410
+    0xBD2, 0xBC4, 0xB89, 0xA95, 0x52D, 0x5AD, 0xB6A, 0x6D4, 0xDC9, 0xD92, // 1420
+    0xAA6, 0x956, 0x2AE, 0x56D, 0x36A, 0xB55, 0xAAA, 0x94D, 0x49D, 0x95D, // 1430
+    0x2BA, 0x5B5, 0x5AA, 0xD55, 0xA9A, 0x92E, 0x26E, 0x55D, 0xADA, 0x6D4, // 1440
+    0x6A5, 0xB27, 0xA4D, 0x4AD, 0x56D, 0xB5A, 0x754, 0xF49, 0xE92, 0xD26, // 1450
+    0xA56, 0x356, 0x6B5, 0xBAA, 0xB92, 0xB25, 0x68B, 0xA9B, 0x55A, 0xADA, // 1460
+    0x5B4, 0xDA9, 0xB52, 0xA9A, 0x536, 0x276, 0x575, 0xAF2, 0x6D4, 0x6A9, // 1470
+    0x555, 0x2AD, 0x4BD, 0x9BA, 0x574, 0xB69, 0xB52, 0xA95, 0x52D, 0xA5D, // 1480
+    0x4DA, 0xAD9, 0x6B2, 0xE95, 0xE2A, 0xC96, 0x92E, 0xAAD, 0x56A, 0xD65, // 1490
+    0xD4A, 0xD15, 0x62B, 0xC5B, 0x53A, 0x6B5, 0xDB2, 0xD64, 0xD29, 0xA55, // 1500
+    0x4AD, 0x96D, 0xAEA, 0x6E8, 0xED1, 0xDA4, 0xD4A, 0xA6A, 0x2DA, 0x5B9, // 1510
+    0xB72, 0xB68, 0x6D1, 0x655, 0x4AB, 0x95B, 0x2BA, 0x5B5, 0xDA9, 0xD52, // 1520
+    0xCA6, 0x94E, 0x46E, 0x95D, 0x4DA, 0xAD5, 0xAAA, 0xA4D, 0x49B, 0x937, // 1530
+    0x4B6, 0x975, 0xD6A, 0xD52, 0xAA5, 0x94B, 0x2AB, 0x55B, 0xAD9, 0x5D2, // 1540
+    0xDC5, 0xD92, 0xB25, 0x555, 0xAB5, 0x5B4, 0xBA9, 0x7A2, 0x745, 0x593, // 1550
+    0xAAB, 0x4D6, 0x9D6, 0x5D2, 0xBA5, 0xB4A, 0xA95, 0x4AD, 0x15D, 0x2DD, // 1560
+    0x9DA, 0x5B4, 0x5A9, 0x52D, 0x25B, 0x8B7, 0x176, 0x56D, 0xB6A, 0xACA, // 1570
+    0xA96, 0x52B, 0x15B, 0x2BB, 0x5B6, 0xDAA, 0xB94, 0xD46, 0xA8D, 0x52D, // 1580
+    0xA9D, 0x55A, 0x755, 0x749, 0xF13, 0xE4A, 0xA96, 0x556, 0x6B5, 0xBAA, // 1590
+    0xB94, // 1600
+];
+
+/// Thirty-day months of `year`, which must be in the table
+fn long_months(year: i64) -> u16 {
+    MONTH_LENGTHS[(year - FIRST_YEAR) as usize]
+}
+
+fn in_table(year: i64) -> bool {
+    (FIRST_YEAR..=LAST_YEAR).contains(&year)
+}
+
+fn days_in_year(year: i64) -> i64 {
+    29 * 12 + i64::from(long_months(year).count_ones())
+}
+
+pub(super) fn is_leap_year(year: i64) -> bool {
+    if in_table(year) {
+        days_in_year(year) == 355
+    } else {
+        islamic::is_leap_year(year)
+    }
+}
+
+pub(super) fn days_in_month(year: i64, month: u32) -> u32 {
+    if !in_table(year) {
+        islamic::days_in_month(year, month)
+    } else if long_months(year) & (1 << (month - 1)) != 0 {
+        30
+    } else {
+        29
+    }
+}
+
+/// R.D. of an Umm al-Qura date, which must be valid
+pub(super) fn to_fixed(year: i64, month: u32, day: u32) -> i64 {
+    if !in_table(year) {
+        return islamic::to_fixed(true, year, month, day);
+    }
+    let years: i64 = (FIRST_YEAR..year).map(days_in_year).sum();
+    let months: i64 = (1..month).map(|m| i64::from(days_in_month(year, m))).sum();
+    FIRST_DAY + years + months + i64::from(day) - 1
+}
+
+pub(super) fn from_fixed(fixed: i64) -> CalendarDate {
+    if fixed < FIRST_DAY {
+        return islamic::from_fixed(true, fixed);
+    }
+    let mut remaining = fixed - FIRST_DAY;
+    for year in FIRST_YEAR..=LAST_YEAR {
+        if remaining >= days_in_year(year) {
+            remaining -= days_in_year(year);
+            continue;
+        }
+        let mut month = 1;
+        while remaining >= i64::from(days_in_month(year, month)) {
+            remaining -= i64::from(days_in_month(year, month));
+            month += 1;
+        }
+        return CalendarDate {
+            year,
+            month,
+            month_name: islamic::MONTHS[month as usize - 1],
+            day: remaining as u32 + 1,
+            leap_year: is_leap_year(year),
+            leap_month: false,
+        };
+    }
+    islamic::from_fixed(true, fixed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_meets_the_tabular_calendar() {
+        assert_eq!(FIRST_DAY, islamic::to_fixed(true, FIRST_YEAR, 1, 1));
+        assert_eq!(
+            to_fixed(LAST_YEAR, 12, days_in_month(LAST_YEAR, 12)) + 1,
+            islamic::to_fixed(true, LAST_YEAR + 1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let last = to_fixed(LAST_YEAR + 1, 1, 1) + 400;
+        for fixed in FIRST_DAY - 400..last {
+            let date = from_fixed(fixed);
+            assert_eq!(to_fixed(date.year, date.month, date.day), fixed);
+            assert!(date.day <= days_in_month(date.year, date.month));
+        }
+    }
+}
@@ -9,11 +9,15 @@ use std::env;
 /// Reads from environment variables:
 /// - `HOST`: Server bind address (default: "0.0.0.0")
 /// - `PORT`: Server bind port (default: "8080")
+/// - `LEAP_SECONDS_FILE`: IERS `leap-seconds.list` to use instead of the
+///   built-in one, when it is newer (default: unset)
 pub struct Config {
     /// Host address to bind to
     pub host: String,
     /// Port number to bind to
     pub port: u16,
+    /// Path to a newer leap second list, if any
+    pub leap_seconds_file: Option<String>,
 }
 
 impl Config {
@@ -39,6 +43,7 @@ impl Config {
                 .unwrap_or_else(|_| "8080".to_string())
                 .parse()
                 .expect("PORT must be a valid number"),
+            leap_seconds_file: env::var("LEAP_SECONDS_FILE").ok(),
         }
     }
 
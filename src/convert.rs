@@ -0,0 +1,306 @@
+//! Conversion of local wall-clock times between time zones.
+//!
+//! A wall-clock time is resolved in its source zone before conversion. Around
+//! DST transitions that resolution is not always unique: a time repeated when
+//! clocks fall back is *ambiguous* and yields both candidates, and a time
+//! skipped when clocks spring forward does not exist and is rejected along
+//! with the nearest valid times on either side of the gap.
+
+use chrono::{
+    DateTime, Duration, LocalResult, NaiveDateTime, SecondsFormat, TimeZone, Timelike, Utc,
+};
+use serde::Serialize;
+
+use crate::error::ApiError;
+use crate::zone::{local_datetime, Zone};
+
+/// Formats accepted for a local wall-clock time
+const LOCAL_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+];
+
+/// How far either side of a nonexistent time to look for valid times; wide
+/// enough for the largest real gap (Samoa skipped 2011-12-30 entirely)
+const GAP_SEARCH_WINDOW: Duration = Duration::days(2);
+
+/// Parse a wall-clock time without offset, such as `2024-03-31T02:30`
+///
+/// # Examples
+///
+/// ```
+/// use thedate::convert::parse_local;
+///
+/// assert!(parse_local("2024-03-31T02:30").is_ok());
+/// assert!(parse_local("2024-03-31 02:30:15.250").is_ok());
+/// assert!(parse_local("2024-03-31T02:30Z").is_err());
+/// ```
+pub fn parse_local(input: &str) -> Result<NaiveDateTime, ApiError> {
+    LOCAL_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(input.trim(), format).ok())
+        .ok_or_else(|| ApiError::InvalidLocalTime(input.to_string()))
+}
+
+/// Which of two readings of an ambiguous wall-clock time a candidate is
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Fold {
+    /// The first occurrence, before clocks fall back
+    Earlier,
+    /// The second occurrence, after clocks fall back
+    Later,
+}
+
+/// One resolved reading of a wall-clock time, expressed in both zones
+#[derive(Debug, Serialize)]
+pub struct Candidate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fold: Option<Fold>,
+    pub from: String,
+    pub from_abbreviation: String,
+    pub to: String,
+    pub to_abbreviation: String,
+    pub utc: String,
+    pub unix_timestamp: i64,
+}
+
+impl Candidate {
+    /// `source` in both zones, or [`ApiError::OutOfRange`] when either
+    /// zone's wall clock reads past the ends of chrono's range
+    fn new(source: DateTime<Zone>, target: Zone, fold: Option<Fold>) -> Result<Self, ApiError> {
+        let converted = source.with_timezone(&target);
+        if local_datetime(&source).is_none() || local_datetime(&converted).is_none() {
+            return Err(ApiError::OutOfRange);
+        }
+        Ok(Candidate {
+            fold,
+            from: source.to_rfc3339_opts(SecondsFormat::AutoSi, false),
+            from_abbreviation: source.format("%Z").to_string(),
+            to: converted.to_rfc3339_opts(SecondsFormat::AutoSi, false),
+            to_abbreviation: converted.format("%Z").to_string(),
+            utc: source
+                .with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            unix_timestamp: source.timestamp(),
+        })
+    }
+}
+
+/// Whether a wall-clock time mapped to one instant or two
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Unique,
+    Ambiguous,
+}
+
+/// Result of converting a wall-clock time from one zone to another
+#[derive(Debug, Serialize)]
+pub struct Conversion {
+    pub from_zone: String,
+    pub to_zone: String,
+    pub local: String,
+    pub status: Status,
+    pub candidates: Vec<Candidate>,
+}
+
+/// Convert the wall-clock time `local` in zone `from` to zone `to`
+///
+/// # Errors
+///
+/// Returns [`ApiError::NonexistentLocalTime`] if `local` falls in a DST gap
+/// in `from`; the error carries the last valid time before the gap and the
+/// first valid time after it. Returns [`ApiError::OutOfRange`] when `local`
+/// is so close to either end of chrono's range that an offset takes it, or
+/// its reading in `to`, past the end.
+pub fn convert(from: Zone, to: Zone, local: NaiveDateTime) -> Result<Conversion, ApiError> {
+    let (status, candidates) = match from.from_local_datetime(&local) {
+        LocalResult::Single(dt) => (Status::Unique, vec![Candidate::new(dt, to, None)?]),
+        LocalResult::Ambiguous(earlier, later) => (
+            Status::Ambiguous,
+            vec![
+                Candidate::new(earlier, to, Some(Fold::Earlier))?,
+                Candidate::new(later, to, Some(Fold::Later))?,
+            ],
+        ),
+        LocalResult::None => {
+            let nearest = nearest_valid(from, local)?
+                .into_iter()
+                .map(|dt| Candidate::new(dt, to, None))
+                .collect::<Result<_, _>>()?;
+            return Err(ApiError::NonexistentLocalTime {
+                local: format_local(local),
+                zone: from.to_string(),
+                nearest,
+            });
+        }
+    };
+    Ok(Conversion {
+        from_zone: from.to_string(),
+        to_zone: to.to_string(),
+        local: format_local(local),
+        status,
+        candidates,
+    })
+}
+
+fn format_local(local: NaiveDateTime) -> String {
+    local.format("%Y-%m-%dT%H:%M:%S%.f").to_string()
+}
+
+fn exists(zone: Zone, local: NaiveDateTime) -> bool {
+    !matches!(zone.from_local_datetime(&local), LocalResult::None)
+}
+
+/// The last valid second before, and the first valid second after, the gap
+/// containing the nonexistent time `local`
+///
+/// Within the search window of either end of chrono's range, where `local`
+/// is missing because its offset takes it past the end rather than because
+/// of a gap, this is [`ApiError::OutOfRange`].
+fn nearest_valid(zone: Zone, local: NaiveDateTime) -> Result<Vec<DateTime<Zone>>, ApiError> {
+    let local = local.with_nanosecond(0).unwrap_or(local);
+
+    // Binary search for the boundary where `exists` flips, to the second
+    let boundary = |mut valid: NaiveDateTime, mut invalid: NaiveDateTime| {
+        if !exists(zone, valid) {
+            return None;
+        }
+        while (valid - invalid).num_seconds().abs() > 1 {
+            let mid = invalid + Duration::seconds((valid - invalid).num_seconds() / 2);
+            if exists(zone, mid) {
+                valid = mid;
+            } else {
+                invalid = mid;
+            }
+        }
+        zone.from_local_datetime(&valid).earliest()
+    };
+
+    let start = local.checked_sub_signed(GAP_SEARCH_WINDOW);
+    let end = local.checked_add_signed(GAP_SEARCH_WINDOW);
+    let (Some(start), Some(end)) = (start, end) else {
+        return Err(ApiError::OutOfRange);
+    };
+    let before = boundary(start, local);
+    let after = boundary(end, local);
+    Ok(before.into_iter().chain(after).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zone(name: &str) -> Zone {
+        Zone::parse(name).unwrap()
+    }
+
+    fn local(s: &str) -> NaiveDateTime {
+        parse_local(s).unwrap()
+    }
+
+    #[test]
+    fn test_unique_conversion() {
+        let conversion = convert(
+            zone("Europe/Berlin"),
+            zone("Asia/Tokyo"),
+            local("2024-03-15T12:00"),
+        )
+        .unwrap();
+        assert_eq!(conversion.status, Status::Unique);
+        assert_eq!(conversion.candidates.len(), 1);
+        let candidate = &conversion.candidates[0];
+        assert_eq!(candidate.from, "2024-03-15T12:00:00+01:00");
+        assert_eq!(candidate.from_abbreviation, "CET");
+        assert_eq!(candidate.to, "2024-03-15T20:00:00+09:00");
+        assert_eq!(candidate.to_abbreviation, "JST");
+        assert_eq!(candidate.utc, "2024-03-15T11:00:00Z");
+    }
+
+    #[test]
+    fn test_ambiguous_time_returns_both_candidates() {
+        // Berlin falls back from 03:00 CEST to 02:00 CET on 2024-10-27
+        let conversion = convert(
+            zone("Europe/Berlin"),
+            zone("UTC"),
+            local("2024-10-27T02:30"),
+        )
+        .unwrap();
+        assert_eq!(conversion.status, Status::Ambiguous);
+        let [earlier, later] = &conversion.candidates[..] else {
+            panic!("expected two candidates");
+        };
+        assert_eq!(earlier.fold, Some(Fold::Earlier));
+        assert_eq!(earlier.from, "2024-10-27T02:30:00+02:00");
+        assert_eq!(earlier.utc, "2024-10-27T00:30:00Z");
+        assert_eq!(later.fold, Some(Fold::Later));
+        assert_eq!(later.from, "2024-10-27T02:30:00+01:00");
+        assert_eq!(later.utc, "2024-10-27T01:30:00Z");
+    }
+
+    #[test]
+    fn test_nonexistent_time_reports_nearest_valid_times() {
+        // Berlin springs forward from 02:00 CET to 03:00 CEST on 2024-03-31
+        let err = convert(
+            zone("Europe/Berlin"),
+            zone("Asia/Tokyo"),
+            local("2024-03-31T02:30"),
+        )
+        .unwrap_err();
+        let ApiError::NonexistentLocalTime { nearest, .. } = err else {
+            panic!("expected NonexistentLocalTime");
+        };
+        assert_eq!(nearest.len(), 2);
+        assert_eq!(nearest[0].from, "2024-03-31T01:59:59+01:00");
+        assert_eq!(nearest[1].from, "2024-03-31T03:00:00+02:00");
+        assert_eq!(nearest[1].to, "2024-03-31T10:00:00+09:00");
+    }
+
+    #[test]
+    fn test_whole_day_gap() {
+        // Samoa skipped 2011-12-30 when it crossed the date line
+        let err =
+            convert(zone("Pacific/Apia"), zone("UTC"), local("2011-12-30T12:00")).unwrap_err();
+        let ApiError::NonexistentLocalTime { nearest, .. } = err else {
+            panic!("expected NonexistentLocalTime");
+        };
+        assert_eq!(nearest[0].from, "2011-12-29T23:59:59-10:00");
+        assert_eq!(nearest[1].from, "2011-12-31T00:00:00+14:00");
+    }
+
+    #[test]
+    fn test_fixed_offset_zones() {
+        let conversion = convert(
+            zone("+05:30"),
+            zone("-08:00"),
+            local("2024-03-15T12:00:00.5"),
+        )
+        .unwrap();
+        assert_eq!(conversion.candidates[0].to, "2024-03-14T22:30:00.500-08:00");
+    }
+
+    #[test]
+    fn test_range_edges_are_out_of_range() {
+        // The target zone reads past the last date
+        let err = convert(zone("UTC"), zone("+14:00"), local("+262142-12-31T23:00"));
+        assert!(matches!(err, Err(ApiError::OutOfRange)));
+        // The source zone's offset takes the time before the first date
+        let err = convert(zone("+14:00"), zone("UTC"), local("-262143-01-01T01:00"));
+        assert!(matches!(err, Err(ApiError::OutOfRange)));
+        let err = convert(zone("-12:00"), zone("UTC"), local("+262142-12-31T23:00"));
+        assert!(matches!(err, Err(ApiError::OutOfRange)));
+        // Representable in both zones, however close to the edge
+        let conversion =
+            convert(zone("+14:00"), zone("UTC"), local("-262143-01-01T14:00")).unwrap();
+        assert_eq!(conversion.candidates[0].utc, "-262143-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn test_parse_local_rejects_offsets_and_garbage() {
+        assert!(parse_local("2024-03-31T02:30:00+01:00").is_err());
+        assert!(parse_local("tomorrow").is_err());
+    }
+}
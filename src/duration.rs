@@ -0,0 +1,503 @@
+//! Calendar-aware durations between instants.
+//!
+//! A [`CalendarDuration`] splits the span between two instants into years,
+//! months, days, hours, minutes and seconds. Years and months are counted on
+//! the calendar (January 31 plus one month is February 29 in a leap year),
+//! and the remainder follows wall-clock time in the zone the instants are
+//! expressed in, so a day across a DST change is still one day.
+//!
+//! A [`CalendarOffset`] is the signed counterpart used as input to date
+//! arithmetic, parsed from an ISO 8601 duration such as `P1M3D`.
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Months, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use serde::Serialize;
+
+use crate::error::ApiError;
+use crate::zone::Zone;
+
+/// Span between two instants broken down into calendar units
+///
+/// All components are non-negative; `negative` records whether the end came
+/// before the start.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct CalendarDuration {
+    pub negative: bool,
+    pub years: u32,
+    pub months: u32,
+    pub days: u32,
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: u32,
+    pub nanoseconds: u32,
+}
+
+/// Add `months` calendar months, clamping the day to the end of the month
+pub(crate) fn add_months_clamped(date: NaiveDate, months: u32) -> Option<NaiveDate> {
+    date.checked_add_months(Months::new(months))
+}
+
+/// Add `months` to a wall-clock time, keeping the time of day
+fn add_months_local(local: NaiveDateTime, months: u32) -> Option<NaiveDateTime> {
+    add_months_clamped(local.date(), months).map(|date| date.and_time(local.time()))
+}
+
+impl CalendarDuration {
+    /// Break down the span from `start` to `end`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use thedate::duration::CalendarDuration;
+    ///
+    /// let start = Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2024, 3, 4, 4, 0, 0).unwrap();
+    /// let d = CalendarDuration::between(&start, &end);
+    /// assert_eq!((d.years, d.months, d.days, d.hours), (1, 2, 3, 4));
+    /// assert_eq!(d.to_iso8601(), "P1Y2M3DT4H");
+    /// ```
+    pub fn between<Tz: TimeZone>(start: &DateTime<Tz>, end: &DateTime<Tz>) -> Self {
+        let negative = end < start;
+        let (a, b) = if negative {
+            (end.naive_local(), start.naive_local())
+        } else {
+            (start.naive_local(), end.naive_local())
+        };
+
+        let mut total_months = (b.year() - a.year()) * 12 + b.month() as i32 - a.month() as i32;
+        let mut anchor = a;
+        while total_months > 0 {
+            match add_months_local(a, total_months as u32) {
+                Some(candidate) if candidate <= b => {
+                    anchor = candidate;
+                    break;
+                }
+                _ => total_months -= 1,
+            }
+        }
+        let total_months = total_months.max(0) as u32;
+
+        // Wall-clock remainder; across a DST change the local difference can
+        // be negative for an instant-ordered pair, so clamp at zero
+        let rest = (b - anchor).max(chrono::Duration::zero());
+        let secs = rest.num_seconds();
+        CalendarDuration {
+            negative: negative && start != end,
+            years: total_months / 12,
+            months: total_months % 12,
+            days: (secs / 86_400) as u32,
+            hours: (secs % 86_400 / 3_600) as u32,
+            minutes: (secs % 3_600 / 60) as u32,
+            seconds: (secs % 60) as u32,
+            nanoseconds: rest.subsec_nanos().max(0) as u32,
+        }
+    }
+
+    /// Whether every component is zero
+    pub fn is_zero(&self) -> bool {
+        *self
+            == CalendarDuration {
+                negative: self.negative,
+                ..Default::default()
+            }
+    }
+
+    /// ISO 8601 duration such as `P1Y2M3DT4H5M6.5S`, or `PT0S` when empty
+    ///
+    /// A negative duration is prefixed with `-`, as in ISO 8601-2.
+    pub fn to_iso8601(&self) -> String {
+        if self.is_zero() {
+            return "PT0S".to_string();
+        }
+        let mut out = String::from(if self.negative { "-P" } else { "P" });
+        for (value, unit) in [(self.years, 'Y'), (self.months, 'M'), (self.days, 'D')] {
+            if value > 0 {
+                let _ = write!(out, "{}{}", value, unit);
+            }
+        }
+        if self.hours > 0 || self.minutes > 0 || self.seconds > 0 || self.nanoseconds > 0 {
+            out.push('T');
+            for (value, unit) in [(self.hours, 'H'), (self.minutes, 'M')] {
+                if value > 0 {
+                    let _ = write!(out, "{}{}", value, unit);
+                }
+            }
+            if self.nanoseconds > 0 {
+                let fraction = format!("{:09}", self.nanoseconds);
+                let _ = write!(out, "{}.{}S", self.seconds, fraction.trim_end_matches('0'));
+            } else if self.seconds > 0 {
+                let _ = write!(out, "{}S", self.seconds);
+            }
+        }
+        out
+    }
+
+    /// English phrase such as `1 year, 2 months and 3 days`
+    ///
+    /// Sub-second parts are omitted; an empty span reads `0 seconds`.
+    pub fn to_human(&self) -> String {
+        let parts: Vec<String> = [
+            (self.years, "year"),
+            (self.months, "month"),
+            (self.days, "day"),
+            (self.hours, "hour"),
+            (self.minutes, "minute"),
+            (self.seconds, "second"),
+        ]
+        .iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| plural(*value, unit))
+        .collect();
+        match parts.as_slice() {
+            [] => "0 seconds".to_string(),
+            [only] => only.clone(),
+            [init @ .., last] => format!("{} and {}", init.join(", "), last),
+        }
+    }
+}
+
+/// Difference between two instants, as totals and as a calendar breakdown
+#[derive(Debug, Serialize)]
+pub struct Difference {
+    pub start: String,
+    pub end: String,
+    pub zone: String,
+    pub total_seconds: i64,
+    pub total_milliseconds: i64,
+    pub total_days: f64,
+    pub calendar: CalendarDuration,
+    pub iso8601: String,
+    pub human: String,
+}
+
+/// Difference from `start` to `end`, with the calendar breakdown taken on
+/// wall-clock time in `zone`
+///
+/// Totals are signed and truncated toward zero; `total_days` counts exact
+/// 86 400-second days.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use thedate::duration::difference;
+/// use thedate::zone::Zone;
+///
+/// let start = Utc.with_ymd_and_hms(2024, 3, 15, 0, 0, 0).unwrap();
+/// let end = Utc.with_ymd_and_hms(2024, 3, 14, 12, 0, 0).unwrap();
+/// let diff = difference(start, end, Zone::default());
+/// assert_eq!(diff.total_seconds, -43_200);
+/// assert_eq!(diff.total_days, -0.5);
+/// assert_eq!(diff.iso8601, "-PT12H");
+/// ```
+pub fn difference(start: DateTime<Utc>, end: DateTime<Utc>, zone: Zone) -> Difference {
+    let span = end - start;
+    let calendar = CalendarDuration::between(&zone.at(start), &zone.at(end));
+    Difference {
+        start: zone.at(start).to_rfc3339_opts(SecondsFormat::AutoSi, false),
+        end: zone.at(end).to_rfc3339_opts(SecondsFormat::AutoSi, false),
+        zone: zone.to_string(),
+        total_seconds: span.num_seconds(),
+        total_milliseconds: span.num_milliseconds(),
+        total_days: span.num_milliseconds() as f64 / 86_400_000.0,
+        iso8601: calendar.to_iso8601(),
+        human: calendar.to_human(),
+        calendar,
+    }
+}
+
+/// Signed amount of each calendar unit to add to an instant
+///
+/// Unlike [`CalendarDuration`] the units are kept apart, so `P1M` stays one
+/// month rather than a number of days, and each unit may carry its own sign
+/// when assembled from separate query parameters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct CalendarOffset {
+    pub years: i64,
+    pub months: i64,
+    pub weeks: i64,
+    pub days: i64,
+    pub hours: i64,
+    pub minutes: i64,
+    pub seconds: i64,
+    pub nanoseconds: i64,
+}
+
+impl CalendarOffset {
+    /// Component-wise sum, or `None` on overflow
+    pub fn checked_add(&self, other: &CalendarOffset) -> Option<CalendarOffset> {
+        Some(CalendarOffset {
+            years: self.years.checked_add(other.years)?,
+            months: self.months.checked_add(other.months)?,
+            weeks: self.weeks.checked_add(other.weeks)?,
+            days: self.days.checked_add(other.days)?,
+            hours: self.hours.checked_add(other.hours)?,
+            minutes: self.minutes.checked_add(other.minutes)?,
+            seconds: self.seconds.checked_add(other.seconds)?,
+            nanoseconds: self.nanoseconds.checked_add(other.nanoseconds)?,
+        })
+    }
+}
+
+impl FromStr for CalendarOffset {
+    type Err = ApiError;
+
+    /// Parse an ISO 8601 duration: `[-]P[nY][nM][nW][nD][T[nH][nM][n[.f]S]]`
+    ///
+    /// A leading `-` negates every component; only seconds may carry a
+    /// fraction, of up to nine digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use thedate::duration::CalendarOffset;
+    ///
+    /// let offset: CalendarOffset = "P1M3DT1.5S".parse().unwrap();
+    /// assert_eq!((offset.months, offset.days, offset.seconds), (1, 3, 1));
+    /// assert_eq!(offset.nanoseconds, 500_000_000);
+    /// assert_eq!("-P2W".parse::<CalendarOffset>().unwrap().weeks, -2);
+    /// assert!("P1H".parse::<CalendarOffset>().is_err());
+    /// ```
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| ApiError::InvalidDuration {
+            input: input.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let trimmed = input.trim();
+        let (sign, rest) = match trimmed.strip_prefix('-') {
+            Some(rest) => (-1, rest),
+            None => (1, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+        let rest = rest
+            .strip_prefix(['P', 'p'])
+            .ok_or_else(|| invalid("expected a leading 'P'"))?;
+        if rest.is_empty() || rest.eq_ignore_ascii_case("T") {
+            return Err(invalid("no components given"));
+        }
+
+        let mut offset = CalendarOffset::default();
+        let mut in_time = false;
+        // Index into the designators of the current part, to enforce order
+        let mut next = 0;
+        let mut number = String::new();
+        for c in rest.chars() {
+            if c.is_ascii_digit() || c == '.' || c == ',' {
+                number.push(if c == ',' { '.' } else { c });
+                continue;
+            }
+            let c = c.to_ascii_uppercase();
+            if c == 'T' {
+                if in_time || !number.is_empty() {
+                    return Err(invalid("misplaced 'T'"));
+                }
+                in_time = true;
+                next = 0;
+                continue;
+            }
+            let designators: &[char] = if in_time {
+                &['H', 'M', 'S']
+            } else {
+                &['Y', 'M', 'W', 'D']
+            };
+            let index = designators[next..]
+                .iter()
+                .position(|&d| d == c)
+                .map(|i| i + next)
+                .ok_or_else(|| invalid(&format!("unexpected or out-of-order '{}'", c)))?;
+            next = index + 1;
+            if number.is_empty() {
+                return Err(invalid(&format!("'{}' has no number", c)));
+            }
+
+            let (whole, fraction) = match number.split_once('.') {
+                Some((whole, fraction)) => (whole, Some(fraction)),
+                None => (number.as_str(), None),
+            };
+            let value: i64 = whole
+                .parse()
+                .map_err(|_| invalid(&format!("'{}{}' is not a valid amount", number, c)))?;
+            let value = value * sign;
+            match (in_time, c) {
+                (false, 'Y') => offset.years = value,
+                (false, 'M') => offset.months = value,
+                (false, 'W') => offset.weeks = value,
+                (false, 'D') => offset.days = value,
+                (true, 'H') => offset.hours = value,
+                (true, 'M') => offset.minutes = value,
+                _ => offset.seconds = value,
+            }
+            if let Some(fraction) = fraction {
+                if c != 'S' || !in_time {
+                    return Err(invalid("only seconds may have a fraction"));
+                }
+                if fraction.is_empty()
+                    || fraction.len() > 9
+                    || !fraction.bytes().all(|b| b.is_ascii_digit())
+                {
+                    return Err(invalid("fraction must be 1 to 9 digits"));
+                }
+                let nanos: i64 = format!("{:0<9}", fraction).parse().unwrap_or(0);
+                offset.nanoseconds = nanos * sign;
+            }
+            number.clear();
+        }
+        if !number.is_empty() {
+            return Err(invalid("trailing number without a unit"));
+        }
+        if in_time && next == 0 {
+            return Err(invalid("'T' must be followed by a time component"));
+        }
+        Ok(offset)
+    }
+}
+
+pub(crate) fn plural(value: u32, unit: &str) -> String {
+    if value == 1 {
+        format!("1 {}", unit)
+    } else {
+        format!("{} {}s", value, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, s).unwrap()
+    }
+
+    #[test]
+    fn test_full_breakdown() {
+        let d = CalendarDuration::between(&utc(2023, 1, 1, 0, 0, 0), &utc(2024, 3, 4, 5, 6, 7));
+        assert_eq!(
+            (d.years, d.months, d.days, d.hours, d.minutes, d.seconds),
+            (1, 2, 3, 5, 6, 7)
+        );
+        assert_eq!(d.to_iso8601(), "P1Y2M3DT5H6M7S");
+        assert_eq!(
+            d.to_human(),
+            "1 year, 2 months, 3 days, 5 hours, 6 minutes and 7 seconds"
+        );
+    }
+
+    #[test]
+    fn test_month_end_clamps() {
+        let d = CalendarDuration::between(&utc(2024, 1, 31, 0, 0, 0), &utc(2024, 2, 29, 0, 0, 0));
+        assert_eq!((d.months, d.days), (1, 0));
+        let d = CalendarDuration::between(&utc(2024, 1, 31, 0, 0, 0), &utc(2024, 3, 1, 0, 0, 0));
+        assert_eq!((d.months, d.days), (1, 1));
+    }
+
+    #[test]
+    fn test_partial_month_is_days() {
+        let d = CalendarDuration::between(&utc(2024, 1, 15, 12, 0, 0), &utc(2024, 2, 15, 11, 0, 0));
+        assert_eq!((d.months, d.days, d.hours), (0, 30, 23));
+    }
+
+    #[test]
+    fn test_negative_span() {
+        let d = CalendarDuration::between(&utc(2024, 1, 2, 0, 0, 0), &utc(2024, 1, 1, 0, 0, 0));
+        assert!(d.negative);
+        assert_eq!(d.days, 1);
+        assert_eq!(d.to_iso8601(), "-P1D");
+    }
+
+    #[test]
+    fn test_zero_span() {
+        let d = CalendarDuration::between(&utc(2024, 1, 1, 0, 0, 0), &utc(2024, 1, 1, 0, 0, 0));
+        assert!(d.is_zero());
+        assert_eq!(d.to_iso8601(), "PT0S");
+        assert_eq!(d.to_human(), "0 seconds");
+    }
+
+    #[test]
+    fn test_fractional_seconds() {
+        let start = utc(2024, 1, 1, 0, 0, 0);
+        let end = start + chrono::Duration::milliseconds(1500);
+        let d = CalendarDuration::between(&start, &end);
+        assert_eq!(d.to_iso8601(), "PT1.5S");
+    }
+
+    #[test]
+    fn test_day_across_dst_is_one_day_in_zone() {
+        let zone = Zone::parse("Europe/Berlin").unwrap();
+        let start = zone.at(utc(2024, 3, 30, 11, 0, 0)); // 12:00 CET
+        let end = zone.at(utc(2024, 3, 31, 10, 0, 0)); // 12:00 CEST
+        let d = CalendarDuration::between(&start, &end);
+        assert_eq!((d.days, d.hours), (1, 0));
+    }
+
+    #[test]
+    fn test_parse_offset_all_components() {
+        let offset: CalendarOffset = "P1Y2M3W4DT5H6M7.25S".parse().unwrap();
+        assert_eq!(
+            offset,
+            CalendarOffset {
+                years: 1,
+                months: 2,
+                weeks: 3,
+                days: 4,
+                hours: 5,
+                minutes: 6,
+                seconds: 7,
+                nanoseconds: 250_000_000,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_offset_negative_and_minutes() {
+        let offset: CalendarOffset = "-PT90M".parse().unwrap();
+        assert_eq!(offset.minutes, -90);
+        assert_eq!(offset.months, 0);
+    }
+
+    #[test]
+    fn test_parse_offset_round_trips_formatted_duration() {
+        let d = CalendarDuration::between(&utc(2023, 1, 1, 0, 0, 0), &utc(2024, 3, 4, 5, 6, 7));
+        let offset: CalendarOffset = d.to_iso8601().parse().unwrap();
+        assert_eq!((offset.years, offset.months, offset.days), (1, 2, 3));
+        assert_eq!((offset.hours, offset.minutes, offset.seconds), (5, 6, 7));
+    }
+
+    #[test]
+    fn test_parse_offset_rejects_malformed() {
+        for input in [
+            "", "P", "PT", "1D", "P1", "PD", "P1D2Y", "P1.5D", "PT1H2H", "P1DT",
+        ] {
+            assert!(
+                matches!(
+                    input.parse::<CalendarOffset>(),
+                    Err(ApiError::InvalidDuration { .. })
+                ),
+                "{} should be rejected",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_difference_totals() {
+        let start = utc(2024, 3, 15, 12, 0, 0);
+        let end = start + chrono::Duration::milliseconds(90_061_500);
+        let diff = difference(start, end, Zone::default());
+        assert_eq!(diff.total_seconds, 90_061);
+        assert_eq!(diff.total_milliseconds, 90_061_500);
+        assert_eq!(diff.iso8601, "P1DT1H1M1.5S");
+        assert_eq!(diff.human, "1 day, 1 hour, 1 minute and 1 second");
+    }
+
+    #[test]
+    fn test_difference_renders_endpoints_in_zone() {
+        let zone = Zone::parse("Asia/Tokyo").unwrap();
+        let diff = difference(utc(2024, 3, 15, 0, 0, 0), utc(2024, 3, 16, 0, 0, 0), zone);
+        assert_eq!(diff.start, "2024-03-15T09:00:00+09:00");
+        assert_eq!(diff.zone, "Asia/Tokyo");
+        assert_eq!(diff.total_days, 1.0);
+    }
+}
@@ -0,0 +1,335 @@
+//! Era-based year numbering: Japanese imperial eras and the Republic of
+//! China (Minguo) calendar.
+//!
+//! Both keep Gregorian months and days and only count years differently.
+//! Japanese eras are listed in [`JAPANESE_ERAS`]; a new era is supported by
+//! appending its row. Dates before 1873, when Japan adopted the Gregorian
+//! calendar, are still read as Gregorian dates.
+//!
+//! Era dates are parsed in these forms, with Latin or native digits:
+//! - Initial and dotted date: `R6.03.15`, `H31.4.30` (`/` and `-` also
+//!   separate)
+//! - Kanji: `令和6年3月15日`, `令和元年5月1日`
+//! - ROC: `民國113年3月15日` (or `民国`), `民前1年1月1日` for 1911
+
+use chrono::{Datelike, NaiveDate};
+
+/// One Japanese imperial era
+#[derive(Debug, PartialEq, Eq)]
+pub struct Era {
+    /// Hepburn romanization, such as `Shōwa`
+    pub name: &'static str,
+    pub kanji: &'static str,
+    /// Initial used in abbreviated dates such as `R6.03.15`
+    pub initial: char,
+    /// First day of the era
+    pub start: NaiveDate,
+}
+
+const fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("era start is a valid date")
+}
+
+/// Japanese eras since the Meiji Restoration, oldest first
+pub static JAPANESE_ERAS: &[Era] = &[
+    Era {
+        name: "Meiji",
+        kanji: "明治",
+        initial: 'M',
+        start: ymd(1868, 10, 23),
+    },
+    Era {
+        name: "Taishō",
+        kanji: "大正",
+        initial: 'T',
+        start: ymd(1912, 7, 30),
+    },
+    Era {
+        name: "Shōwa",
+        kanji: "昭和",
+        initial: 'S',
+        start: ymd(1926, 12, 25),
+    },
+    Era {
+        name: "Heisei",
+        kanji: "平成",
+        initial: 'H',
+        start: ymd(1989, 1, 8),
+    },
+    Era {
+        name: "Reiwa",
+        kanji: "令和",
+        initial: 'R',
+        start: ymd(2019, 5, 1),
+    },
+];
+
+/// A year within a Japanese era
+#[derive(Debug, PartialEq, Eq)]
+pub struct EraYear {
+    pub era: &'static Era,
+    /// Year of the era, counting the partial first year as 1
+    pub year: u32,
+}
+
+impl EraYear {
+    /// The era and era year `date` falls in; `None` before Meiji
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use thedate::era::EraYear;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2019, 4, 30).unwrap();
+    /// let era = EraYear::of(date).unwrap();
+    /// assert_eq!((era.era.name, era.year), ("Heisei", 31));
+    /// assert_eq!(era.kanji(), "平成31年");
+    /// ```
+    pub fn of(date: NaiveDate) -> Option<Self> {
+        let era = JAPANESE_ERAS.iter().rev().find(|era| era.start <= date)?;
+        let year = (date.year() - era.start.year() + 1) as u32;
+        Some(EraYear { era, year })
+    }
+
+    /// Kanji form, such as `令和6年`; the first year is written `元年`
+    pub fn kanji(&self) -> String {
+        if self.year == 1 {
+            format!("{}元年", self.era.kanji)
+        } else {
+            format!("{}{}年", self.era.kanji, self.year)
+        }
+    }
+
+    /// Romanized form, such as `Reiwa 6`
+    pub fn romaji(&self) -> String {
+        format!("{} {}", self.era.name, self.year)
+    }
+
+    /// Abbreviated date, such as `R6.03.15`, for a `date` in this year
+    pub fn abbreviated(&self, date: NaiveDate) -> String {
+        format!(
+            "{}{}.{:02}.{:02}",
+            self.era.initial,
+            self.year,
+            date.month(),
+            date.day()
+        )
+    }
+}
+
+/// Minguo year of a Gregorian year: 1912 is 1, and 1911 is 0 (民前1年)
+pub fn roc_year(year: i32) -> i32 {
+    year - 1911
+}
+
+/// Minguo year in Chinese, such as `民國113年`, or `民前1年` before 1912
+pub fn roc_year_chinese(year: i32) -> String {
+    let roc = roc_year(year);
+    if roc >= 1 {
+        format!("民國{}年", roc)
+    } else {
+        format!("民前{}年", 1 - roc)
+    }
+}
+
+/// Parse an era-based date in one of the forms listed in the module docs
+///
+/// Returns `None` when `input` is not era-shaped at all, and `Some(Err)`
+/// with a reason when it is but names no existing date.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use thedate::era::parse;
+///
+/// let date = NaiveDate::from_ymd_opt(2024, 3, 15);
+/// assert_eq!(parse("R6.03.15").and_then(Result::ok), date);
+/// assert_eq!(parse("令和6年3月15日").and_then(Result::ok), date);
+/// assert_eq!(parse("民國113年3月15日").and_then(Result::ok), date);
+/// assert!(parse("R1.04.30").unwrap().is_err());
+/// assert!(parse("2024-03-15").is_none());
+/// ```
+pub fn parse(input: &str) -> Option<Result<NaiveDate, String>> {
+    let input = input.trim();
+    if let Some(rest) = ["民國", "民国"]
+        .iter()
+        .find_map(|prefix| input.strip_prefix(prefix))
+    {
+        let (year, month, day) = kanji_parts(rest)?;
+        return Some(roc_date(false, year, month, day));
+    }
+    if let Some(rest) = input.strip_prefix("民前") {
+        let (year, month, day) = kanji_parts(rest)?;
+        return Some(roc_date(true, year, month, day));
+    }
+    if let Some((era, rest)) = JAPANESE_ERAS
+        .iter()
+        .find_map(|era| Some((era, input.strip_prefix(era.kanji)?)))
+    {
+        let (year, month, day) = kanji_parts(rest)?;
+        return Some(era_date(era, year, month, day));
+    }
+    let mut chars = input.chars();
+    let initial = chars.next()?.to_ascii_uppercase();
+    let era = JAPANESE_ERAS.iter().find(|era| era.initial == initial)?;
+    let mut parts = chars.as_str().split(['.', '/', '-']);
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(era_date(era, year, month, day))
+}
+
+/// Year, month and day from `6年3月15日`, reading `元` as year 1
+fn kanji_parts(text: &str) -> Option<(i64, u32, u32)> {
+    let (year, rest) = text.split_once('年')?;
+    let (month, rest) = rest.split_once('月')?;
+    let day = rest.strip_suffix('日')?;
+    let year = if year == "元" { 1 } else { year.parse().ok()? };
+    Some((year, month.parse().ok()?, day.parse().ok()?))
+}
+
+fn date(year: i32, month: u32, day: u32) -> Result<NaiveDate, String> {
+    NaiveDate::from_ymd_opt(year, month, day)
+        .ok_or_else(|| format!("{:04}-{:02}-{:02} does not exist", year, month, day))
+}
+
+/// The Gregorian date of a Minguo date, counted back from 1912 when
+/// `before` (`民前`)
+fn roc_date(before: bool, year: i64, month: u32, day: u32) -> Result<NaiveDate, String> {
+    let prefix = if before { "民前" } else { "民國" };
+    if year < 1 {
+        return Err(format!("{} years start at 1", prefix));
+    }
+    let gregorian = if before {
+        1912_i64.checked_sub(year)
+    } else {
+        year.checked_add(1911)
+    };
+    let gregorian = gregorian
+        .and_then(|year| i32::try_from(year).ok())
+        .ok_or_else(|| format!("{}{}年 is out of range", prefix, year))?;
+    date(gregorian, month, day)
+}
+
+/// The Gregorian date of a date in `era`, which must fall within the era
+fn era_date(era: &'static Era, year: i64, month: u32, day: u32) -> Result<NaiveDate, String> {
+    if year < 1 {
+        return Err(format!("{} years start at 1", era.name));
+    }
+    let gregorian = year
+        .checked_add(i64::from(era.start.year() - 1))
+        .and_then(|year| i32::try_from(year).ok())
+        .ok_or_else(|| format!("{} {} is out of range", era.name, year))?;
+    let found = date(gregorian, month, day)?;
+    if found < era.start {
+        return Err(format!("{} began on {}", era.name, era.start));
+    }
+    if let Some(next) = JAPANESE_ERAS
+        .iter()
+        .find(|next| next.start > era.start && next.start <= found)
+    {
+        return Err(format!(
+            "{} ended on {}",
+            era.name,
+            next.start.pred_opt().unwrap_or(next.start)
+        ));
+    }
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_era_boundaries() {
+        let era = |date| EraYear::of(date).map(|e| e.romaji());
+        assert_eq!(era(day(1868, 10, 22)), None);
+        assert_eq!(era(day(1868, 10, 23)).as_deref(), Some("Meiji 1"));
+        assert_eq!(era(day(1912, 7, 29)).as_deref(), Some("Meiji 45"));
+        assert_eq!(era(day(1912, 7, 30)).as_deref(), Some("Taishō 1"));
+        assert_eq!(era(day(1989, 1, 7)).as_deref(), Some("Shōwa 64"));
+        assert_eq!(era(day(1989, 1, 8)).as_deref(), Some("Heisei 1"));
+        assert_eq!(era(day(2019, 5, 1)).as_deref(), Some("Reiwa 1"));
+        assert_eq!(era(day(2024, 3, 15)).as_deref(), Some("Reiwa 6"));
+    }
+
+    #[test]
+    fn test_forms() {
+        let reiwa = EraYear::of(day(2019, 5, 1)).unwrap();
+        assert_eq!(reiwa.kanji(), "令和元年");
+        assert_eq!(reiwa.abbreviated(day(2019, 5, 1)), "R1.05.01");
+        assert_eq!(roc_year_chinese(2024), "民國113年");
+        assert_eq!(roc_year_chinese(1912), "民國1年");
+        assert_eq!(roc_year_chinese(1911), "民前1年");
+        assert_eq!(roc_year(1900), -11);
+    }
+
+    #[test]
+    fn test_parse_forms() {
+        let parsed = |input| parse(input).unwrap().unwrap();
+        assert_eq!(parsed("R6.03.15"), day(2024, 3, 15));
+        assert_eq!(parsed("r6/3/15"), day(2024, 3, 15));
+        assert_eq!(parsed("H31-04-30"), day(2019, 4, 30));
+        assert_eq!(parsed("S64.01.07"), day(1989, 1, 7));
+        assert_eq!(parsed("令和元年5月1日"), day(2019, 5, 1));
+        assert_eq!(parsed("民国113年3月15日"), day(2024, 3, 15));
+        assert_eq!(parsed("民前1年1月1日"), day(1911, 1, 1));
+    }
+
+    #[test]
+    fn test_parse_rejects_dates_outside_era() {
+        assert!(parse("R1.04.30").unwrap().is_err());
+        assert!(parse("H31.05.01").unwrap().is_err());
+        assert!(parse("S64.01.08").unwrap().is_err());
+        assert!(parse("R0.01.01").unwrap().is_err());
+        assert!(parse("R6.02.30").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_roc_years_out_of_range() {
+        for input in [
+            "民國0年1月1日",
+            "民國-5年1月1日",
+            "民前0年1月1日",
+            "民前-1年1月1日",
+            "民國2147483647年1月1日",
+            "民前99999999999年1月1日",
+            "民國9223372036854775807年1月1日",
+            "H9223372036854775807.01.01",
+            "R99999999999.01.01",
+            "令和2147483647年1月1日",
+        ] {
+            let err = parse(input).expect(input).unwrap_err();
+            assert!(
+                err.contains("start at 1") || err.contains("out of range"),
+                "{}: {}",
+                input,
+                err
+            );
+        }
+        assert_eq!(parse("民國1年1月1日"), Some(Ok(day(1912, 1, 1))));
+    }
+
+    #[test]
+    fn test_parse_ignores_other_input() {
+        assert_eq!(parse("2024-03-15"), None);
+        assert_eq!(parse("Reiwa"), None);
+        assert_eq!(parse("R6.03.15.01"), None);
+        assert_eq!(parse("X6.03.15"), None);
+    }
+
+    #[test]
+    fn test_eras_are_in_order() {
+        assert!(JAPANESE_ERAS.windows(2).all(|w| w[0].start < w[1].start));
+    }
+}
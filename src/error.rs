@@ -0,0 +1,341 @@
+//! Error type returned by thedate's HTTP handlers.
+//!
+//! Every error renders as a JSON body with a stable, machine-readable `error`
+//! code and a human-readable `message`, so clients never have to scrape text.
+//! Some errors add structured context, such as the list of valid field names.
+
+use std::fmt;
+
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+use serde_json::{json, Map, Value};
+
+use crate::convert::Candidate;
+use crate::locale::Locale;
+use crate::output::OutputFormat;
+use crate::timestamp::{Field, TWO_DIGIT_YEAR};
+
+/// Errors surfaced to API clients
+///
+/// # Example Response
+///
+/// ```json
+/// {
+///   "error": "unknown_time_zone",
+///   "message": "unknown time zone 'Mars/Olympus_Mons'"
+/// }
+/// ```
+#[derive(Debug)]
+pub enum ApiError {
+    /// The `tz` parameter is neither an IANA zone name nor a fixed UTC offset
+    UnknownTimeZone(String),
+    /// An instant could not be parsed or is out of range
+    InvalidInstant { input: String, reason: String },
+    /// The `fields` parameter named fields that `Timestamp` does not have
+    UnknownFields(Vec<String>),
+    /// A path named a single field that `Timestamp` does not have
+    UnknownField(String),
+    /// A required query parameter was not supplied
+    MissingParameter(&'static str),
+    /// The query string is malformed or a parameter has the wrong type
+    InvalidQuery { input: String, reason: String },
+    /// A single field has no value at the requested instant
+    NullField { field: Field, reason: &'static str },
+    /// A strftime pattern contains a token chrono cannot format
+    InvalidPattern { token: String, position: usize },
+    /// A strftime pattern abbreviates a year outside 0000 to 9999
+    UnrepresentableYear {
+        token: String,
+        position: usize,
+        year: i32,
+    },
+    /// A strftime pattern is longer than the accepted maximum
+    PatternTooLong { max: usize },
+    /// A strftime pattern would render more output than the accepted maximum
+    OutputTooLarge { max: usize },
+    /// The `format` parameter names an encoding thedate does not produce
+    UnknownFormat(String),
+    /// No media range in the `Accept` header is one thedate can produce
+    NotAcceptable(String),
+    /// A wall-clock time could not be parsed
+    InvalidLocalTime(String),
+    /// A wall-clock time falls in a DST gap and never occurs in its zone
+    NonexistentLocalTime {
+        local: String,
+        zone: String,
+        nearest: Vec<Candidate>,
+    },
+    /// An ISO 8601 duration could not be parsed
+    InvalidDuration { input: String, reason: String },
+    /// A query parameter is not one of the values it accepts
+    InvalidChoice {
+        parameter: &'static str,
+        value: String,
+        valid: &'static [&'static str],
+    },
+    /// Month arithmetic landed on a day its month does not have
+    DayOutOfRange {
+        year: i32,
+        month: u32,
+        day: u32,
+        days_in_month: u32,
+    },
+    /// Date arithmetic left the range of representable instants
+    OutOfRange,
+    /// A free-form timestamp matched none of the known formats
+    UnrecognizedTimestamp { input: String, reason: String },
+    /// A relative date expression contains a word that does not fit
+    InvalidExpression {
+        word: String,
+        position: usize,
+        expected: &'static str,
+    },
+    /// A relative-time threshold entry is malformed
+    InvalidThresholds { entry: String, reason: String },
+    /// The requested locale has no name table
+    UnsupportedLocale(String),
+    /// A week rule is neither `iso`, a region code nor `<day>:<minimal days>`
+    InvalidWeekRule(String),
+    /// A longitude is not a number of degrees from -180 to 180
+    InvalidLongitude(String),
+}
+
+impl ApiError {
+    /// Stable machine-readable error code
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::UnknownTimeZone(_) => "unknown_time_zone",
+            ApiError::InvalidInstant { .. } => "invalid_instant",
+            ApiError::UnknownFields(_) => "unknown_fields",
+            ApiError::UnknownField(_) => "unknown_field",
+            ApiError::MissingParameter(_) => "missing_parameter",
+            ApiError::InvalidQuery { .. } => "invalid_query",
+            ApiError::NullField { .. } => "null_field",
+            ApiError::InvalidPattern { .. } => "invalid_pattern",
+            ApiError::UnrepresentableYear { .. } => "unrepresentable_year",
+            ApiError::PatternTooLong { .. } => "pattern_too_long",
+            ApiError::OutputTooLarge { .. } => "output_too_large",
+            ApiError::UnknownFormat(_) => "unknown_format",
+            ApiError::NotAcceptable(_) => "not_acceptable",
+            ApiError::InvalidLocalTime(_) => "invalid_local_time",
+            ApiError::NonexistentLocalTime { .. } => "nonexistent_local_time",
+            ApiError::InvalidDuration { .. } => "invalid_duration",
+            ApiError::InvalidChoice { .. } => "invalid_parameter",
+            ApiError::DayOutOfRange { .. } => "day_out_of_range",
+            ApiError::OutOfRange => "out_of_range",
+            ApiError::UnrecognizedTimestamp { .. } => "unrecognized_timestamp",
+            ApiError::InvalidExpression { .. } => "invalid_expression",
+            ApiError::InvalidThresholds { .. } => "invalid_thresholds",
+            ApiError::UnsupportedLocale(_) => "unsupported_locale",
+            ApiError::InvalidWeekRule(_) => "invalid_week_rule",
+            ApiError::InvalidLongitude(_) => "invalid_longitude",
+        }
+    }
+
+    /// Extra structured context merged into the JSON error body
+    fn details(&self) -> Map<String, Value> {
+        let mut details = Map::new();
+        match self {
+            ApiError::UnknownFields(unknown) => {
+                details.insert("unknown".into(), json!(unknown));
+                details.insert("valid".into(), json!(field_names()));
+            }
+            ApiError::UnknownField(_) => {
+                details.insert("valid".into(), json!(field_names()));
+            }
+            ApiError::NullField { field, reason } => {
+                details.insert("field".into(), json!(field.name()));
+                details.insert("reason".into(), json!(reason));
+            }
+            ApiError::InvalidPattern { token, position }
+            | ApiError::UnrepresentableYear {
+                token, position, ..
+            } => {
+                details.insert("token".into(), json!(token));
+                details.insert("position".into(), json!(position));
+            }
+            ApiError::UnknownFormat(_) | ApiError::NotAcceptable(_) => {
+                let names: Vec<_> = OutputFormat::ALL.iter().map(|f| f.name()).collect();
+                details.insert("supported".into(), json!(names));
+            }
+            ApiError::NonexistentLocalTime { nearest, .. } => {
+                details.insert("nearest".into(), json!(nearest));
+            }
+            ApiError::InvalidExpression { word, position, .. } => {
+                details.insert("word".into(), json!(word));
+                details.insert("position".into(), json!(position));
+            }
+            ApiError::InvalidChoice { valid, .. } => {
+                details.insert("valid".into(), json!(valid));
+            }
+            ApiError::UnsupportedLocale(_) => {
+                details.insert("supported".into(), json!(Locale::tags()));
+            }
+            _ => {}
+        }
+        details
+    }
+}
+
+fn field_names() -> Vec<&'static str> {
+    Field::ALL.iter().map(|f| f.name()).collect()
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::UnknownTimeZone(tz) => write!(f, "unknown time zone '{}'", tz),
+            ApiError::InvalidInstant { input, reason } => {
+                write!(f, "invalid instant '{}': {}", input, reason)
+            }
+            ApiError::UnknownFields(unknown) => {
+                write!(f, "unknown fields: {}", unknown.join(", "))
+            }
+            ApiError::UnknownField(field) => write!(f, "no such field '{}'", field),
+            ApiError::MissingParameter(name) => {
+                write!(f, "missing required query parameter '{}'", name)
+            }
+            ApiError::InvalidQuery { input, reason } => {
+                write!(f, "invalid query string '{}': {}", input, reason)
+            }
+            ApiError::NullField { field, reason } => {
+                write!(f, "field '{}' has no value at this instant: {}", field, reason)
+            }
+            ApiError::InvalidPattern { token, position } => write!(
+                f,
+                "unsupported format specifier '{}' at byte {}",
+                token, position
+            ),
+            ApiError::UnrepresentableYear {
+                token,
+                position,
+                year,
+            } => write!(
+                f,
+                "format specifier '{}' at byte {} cannot write year {}: {}",
+                token, position, year, TWO_DIGIT_YEAR
+            ),
+            ApiError::PatternTooLong { max } => {
+                write!(f, "pattern is longer than {} bytes", max)
+            }
+            ApiError::OutputTooLarge { max } => {
+                write!(f, "formatted output would exceed {} bytes", max)
+            }
+            ApiError::UnknownFormat(name) => write!(f, "unknown output format '{}'", name),
+            ApiError::NotAcceptable(accept) => {
+                write!(f, "cannot produce any type listed in Accept '{}'", accept)
+            }
+            ApiError::InvalidLocalTime(input) => write!(
+                f,
+                "invalid local time '{}': expected YYYY-MM-DDTHH:MM[:SS[.fff]] without offset",
+                input
+            ),
+            ApiError::NonexistentLocalTime { local, zone, .. } => write!(
+                f,
+                "local time {} does not exist in {} (skipped by a clock change)",
+                local, zone
+            ),
+            ApiError::InvalidDuration { input, reason } => {
+                write!(f, "invalid ISO 8601 duration '{}': {}", input, reason)
+            }
+            ApiError::InvalidChoice {
+                parameter,
+                value,
+                valid,
+            } => write!(
+                f,
+                "invalid value '{}' for '{}': expected one of {}",
+                value,
+                parameter,
+                valid.join(", ")
+            ),
+            ApiError::DayOutOfRange {
+                year,
+                month,
+                day,
+                days_in_month,
+            } => write!(
+                f,
+                "{:04}-{:02}-{:02} does not exist: the month has {} days",
+                year, month, day, days_in_month
+            ),
+            ApiError::OutOfRange => write!(f, "result is outside the supported date range"),
+            ApiError::UnrecognizedTimestamp { input, reason } => {
+                write!(f, "unrecognized timestamp '{}': {}", input, reason)
+            }
+            ApiError::InvalidExpression {
+                word,
+                position,
+                expected,
+            } if word.is_empty() => write!(
+                f,
+                "unexpected end of expression at byte {}: expected {}",
+                position, expected
+            ),
+            ApiError::InvalidExpression {
+                word,
+                position,
+                expected,
+            } => write!(
+                f,
+                "unexpected '{}' at byte {}: expected {}",
+                word, position, expected
+            ),
+            ApiError::InvalidThresholds { entry, reason } => {
+                write!(f, "invalid threshold '{}': {}", entry, reason)
+            }
+            ApiError::UnsupportedLocale(tag) => write!(f, "unsupported locale '{}'", tag),
+            ApiError::InvalidWeekRule(spec) => write!(
+                f,
+                "invalid week rule '{}': expected iso, a region code such as US, or <day>:<minimal days>",
+                spec
+            ),
+            ApiError::InvalidLongitude(input) => write!(
+                f,
+                "invalid longitude '{}': expected degrees from -180 to 180, east positive",
+                input
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ApiError::UnknownTimeZone(_)
+            | ApiError::InvalidInstant { .. }
+            | ApiError::UnknownFields(_)
+            | ApiError::MissingParameter(_)
+            | ApiError::InvalidQuery { .. }
+            | ApiError::InvalidPattern { .. }
+            | ApiError::UnrepresentableYear { .. }
+            | ApiError::PatternTooLong { .. }
+            | ApiError::OutputTooLarge { .. }
+            | ApiError::UnknownFormat(_)
+            | ApiError::InvalidLocalTime(_)
+            | ApiError::NonexistentLocalTime { .. }
+            | ApiError::InvalidDuration { .. }
+            | ApiError::InvalidChoice { .. }
+            | ApiError::DayOutOfRange { .. }
+            | ApiError::OutOfRange
+            | ApiError::UnrecognizedTimestamp { .. }
+            | ApiError::InvalidExpression { .. }
+            | ApiError::InvalidThresholds { .. }
+            | ApiError::UnsupportedLocale(_)
+            | ApiError::InvalidWeekRule(_)
+            | ApiError::InvalidLongitude(_) => StatusCode::BAD_REQUEST,
+            ApiError::UnknownField(_) => StatusCode::NOT_FOUND,
+            ApiError::NullField { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::NotAcceptable(_) => StatusCode::NOT_ACCEPTABLE,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let mut body = Map::new();
+        body.insert("error".into(), json!(self.code()));
+        body.insert("message".into(), json!(self.to_string()));
+        body.extend(self.details());
+        HttpResponse::build(self.status_code()).json(body)
+    }
+}
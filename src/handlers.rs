@@ -1,15 +1,276 @@
 //! HTTP request handlers for thedate service.
 
-use actix_web::{HttpResponse, Responder};
-use chrono::Utc;
+use actix_web::{
+    error::QueryPayloadError,
+    http::header::{self, ContentType},
+    web, HttpRequest, HttpResponse, Responder,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Deserialize;
+use serde_json::{Map, Value};
 
-use crate::timestamp::from_chrono;
+use crate::arithmetic::{self, Clock, MonthEnd};
+use crate::astronomy;
+use crate::calendar::{self, Calendar};
+use crate::convert::{self, Status};
+use crate::duration::{self, CalendarOffset};
+use crate::error::ApiError;
+use crate::humanize::{humanize, Thresholds};
+use crate::instant::{parse_instant, parse_instant_in};
+use crate::leap_seconds;
+use crate::locale::Locale;
+use crate::natural;
+use crate::numerals::Numerals;
+use crate::output::OutputFormat;
+use crate::parse;
+use crate::pattern;
+use crate::spoken;
+use crate::timestamp::{from_chrono_fields, parse_fields, Field, LOCAL_DATE_RANGE};
+use crate::week::WeekRule;
+use crate::zone::{self, Zone};
+
+/// Query parameters choosing the instant to render and the zone to render in
+#[derive(Debug, Default, Deserialize)]
+pub struct TimeQuery {
+    /// IANA zone name (`America/New_York`) or fixed offset (`+05:30`)
+    pub tz: Option<String>,
+    /// Instant to render instead of now, in any form `/at/{instant}` accepts
+    pub at: Option<String>,
+    /// Digits for string output: `latn` (default), `arab`, `arabext`, `deva`
+    /// or `thai`
+    pub numerals: Option<String>,
+}
+
+impl TimeQuery {
+    /// Resolve the requested zone, defaulting to UTC
+    pub fn zone(&self) -> Result<Zone, ApiError> {
+        self.tz.as_deref().map_or(Ok(Zone::default()), Zone::parse)
+    }
+
+    /// Resolve the requested instant, defaulting to now
+    pub fn instant(&self) -> Result<DateTime<Utc>, ApiError> {
+        self.at
+            .as_deref()
+            .map_or_else(|| Ok(Utc::now()), parse_instant)
+    }
+
+    /// Resolve the requested numbering system, defaulting to ASCII digits
+    pub fn numerals(&self) -> Result<Numerals, ApiError> {
+        self.numerals
+            .as_deref()
+            .map_or(Ok(Numerals::default()), Numerals::parse)
+    }
+}
+
+/// Query parameters accepted by the timestamp endpoints
+#[derive(Debug, Default, Deserialize)]
+pub struct TimestampQuery {
+    #[serde(flatten)]
+    pub time: TimeQuery,
+    /// Comma-separated list of fields to return (default: all)
+    pub fields: Option<String>,
+    /// Output encoding, overriding the `Accept` header
+    pub format: Option<String>,
+    /// Reference instant; adds a humanized `relative` block when given
+    pub relative_to: Option<String>,
+    /// Overrides for the relative-time rounding thresholds (`hours:24,days:30`)
+    pub thresholds: Option<String>,
+    /// Language for month and weekday names, overriding `Accept-Language`
+    pub locale: Option<String>,
+    /// Week numbering rule: `iso`, a region code or `<day>:<minimal days>`
+    pub week_rule: Option<String>,
+    /// `true` adds a `spoken` block of speakable phrases
+    pub spoken: Option<String>,
+    /// Comma-separated calendars to add, such as `hebrew,persian`
+    pub calendars: Option<String>,
+    /// Degrees east; adds a `local_sidereal_time` block
+    pub longitude: Option<String>,
+}
+
+impl TimestampQuery {
+    /// Resolve the requested fields, defaulting to every field
+    pub fn fields(&self) -> Result<Vec<Field>, ApiError> {
+        self.fields
+            .as_deref()
+            .map_or(Ok(Field::ALL.to_vec()), parse_fields)
+    }
+
+    /// Render `instant` according to this query, in the negotiated encoding
+    fn render(&self, req: &HttpRequest, instant: DateTime<Utc>) -> Result<HttpResponse, ApiError> {
+        let output = OutputFormat::negotiate(req, self.format.as_deref())?;
+        let zone = self.time.zone()?;
+        let fields = self.fields()?;
+        let thresholds = self
+            .thresholds
+            .as_deref()
+            .map_or(Ok(Thresholds::default()), Thresholds::parse)?;
+        let locale = Locale::negotiate(req, self.locale.as_deref())?;
+        let week_rule = self.week_rule.as_deref().map(WeekRule::parse).transpose()?;
+        let numerals = self.time.numerals()?;
+        let calendars = self
+            .calendars
+            .as_deref()
+            .map_or(Ok(Vec::new()), Calendar::parse_list)?;
+        let spoken = self
+            .spoken
+            .as_deref()
+            .map_or(Ok(false), |value| flag("spoken", value))?;
+        let longitude = self.longitude.as_deref().map(longitude).transpose()?;
+        let dt = zone.at(instant);
+        // Where the zone's offset takes the civil date out of range, the
+        // blocks built on it are null too
+        let local_date = zone::local_date(&dt);
+        let mut body = from_chrono_fields(&dt, &fields);
+        locale.localize(&dt, &mut body);
+        numerals.apply(&mut body);
+        let mut null_reasons: Map<String, Value> = fields
+            .iter()
+            .filter_map(|field| {
+                let reason = field.null_reason_at(&dt)?;
+                Some((field.name().to_string(), Value::from(reason)))
+            })
+            .collect();
+        if local_date.is_none() {
+            let blocks = [
+                ("calendars", !calendars.is_empty()),
+                ("spoken", spoken),
+                ("week_rule", week_rule.is_some()),
+            ];
+            for (block, requested) in blocks {
+                if requested {
+                    null_reasons.insert(block.into(), Value::from(LOCAL_DATE_RANGE));
+                }
+            }
+        }
+        if !null_reasons.is_empty() {
+            body.insert("null_reasons".into(), Value::Object(null_reasons));
+        }
+        if !calendars.is_empty() {
+            let blocks = match local_date {
+                Some(_) => Value::Object(calendar::render_all(&calendars, &dt, locale)),
+                None => Value::Null,
+            };
+            body.insert("calendars".into(), blocks);
+        }
+        if spoken {
+            let phrases = local_date.map(|date| spoken::speak(date, dt.time(), locale.language()));
+            body.insert("spoken".into(), serde_json::json!(phrases));
+        }
+        if let Some(rule) = week_rule {
+            let week = local_date.map(|date| rule.info(date));
+            body.insert("week_rule".into(), serde_json::json!(week));
+        }
+        if let Some(longitude) = longitude {
+            let mean = astronomy::greenwich_mean_sidereal_time(instant);
+            let apparent = astronomy::greenwich_apparent_sidereal_time(instant);
+            body.insert(
+                "local_sidereal_time".into(),
+                serde_json::json!({
+                    "longitude": longitude,
+                    "mean": astronomy::local_sidereal_time(mean, longitude),
+                    "apparent": astronomy::local_sidereal_time(apparent, longitude),
+                }),
+            );
+        }
+        if let Some(reference) = self.relative_to.as_deref() {
+            let relative = humanize(instant, parse_instant(reference)?, &thresholds);
+            body.insert("relative".into(), serde_json::json!(relative));
+        }
+        let mut response = output.respond(&body);
+        response.headers_mut().insert(
+            header::CONTENT_LANGUAGE,
+            header::HeaderValue::from_static(locale.tag),
+        );
+        Ok(response)
+    }
+}
+
+/// Parse a boolean query parameter: `true`/`false`, `1`/`0`, or empty for true
+fn flag(parameter: &'static str, value: &str) -> Result<bool, ApiError> {
+    match value.to_ascii_lowercase().as_str() {
+        "" | "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        _ => Err(ApiError::InvalidChoice {
+            parameter,
+            value: value.to_string(),
+            valid: &["true", "false"],
+        }),
+    }
+}
+
+/// Parse a longitude in degrees east, from -180 to 180
+fn longitude(value: &str) -> Result<f64, ApiError> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|degrees| (-180.0..=180.0).contains(degrees))
+        .ok_or_else(|| ApiError::InvalidLongitude(value.to_string()))
+}
+
+/// Query parameters accepted by `/format`
+#[derive(Debug, Default, Deserialize)]
+pub struct FormatQuery {
+    #[serde(flatten)]
+    pub time: TimeQuery,
+    /// chrono strftime pattern such as `%Y-%m-%dT%H`
+    pub pattern: Option<String>,
+}
+
+/// Query extractor settings for every route
+///
+/// A query string that is not valid percent-encoding, or a parameter of the
+/// wrong type such as `/add?days=abc`, is a 400 with error code
+/// `invalid_query` rather than actix's plain-text body.
+pub fn query_config() -> web::QueryConfig {
+    web::QueryConfig::default().error_handler(|err, req| {
+        let reason = match err {
+            QueryPayloadError::Deserialize(err) => err.to_string(),
+            err => err.to_string(),
+        };
+        ApiError::InvalidQuery {
+            input: req.query_string().to_string(),
+            reason,
+        }
+        .into()
+    })
+}
 
 /// Main endpoint handler - returns current timestamp in 61+ formats
 ///
+/// Accepts an optional `tz` query parameter; every field is then rendered in
+/// that zone, with DST and the zone abbreviation applied. An optional
+/// `fields` parameter (`?fields=unix_timestamp,full_iso`) limits the response
+/// to those keys, in that order, and `at` renders another instant instead of
+/// now. `relative_to` adds a `relative` block (`"3 hours ago"`, `"3h ago"`,
+/// value and unit) measured from that reference, with rounding thresholds
+/// adjustable through `thresholds` (see [`crate::humanize`]). Month and
+/// weekday names follow `locale`, or else `Accept-Language` (see
+/// [`crate::locale`]). `week_rule` adds week numbers under a regional
+/// first day and minimal days (see [`crate::week`]). `numerals` renders the
+/// digits of every string field in another script, keeping integer fields
+/// numeric (see [`crate::numerals`]). `spoken=true` adds exact, fuzzy and
+/// date phrases for speech, in the locale's language where a phrasebook
+/// exists and English otherwise (see [`crate::spoken`]). `calendars` adds the
+/// date in other calendars, such as `hebrew,islamic,persian` (see
+/// [`crate::calendar`]). Fields that cannot write a year outside 0000 to
+/// 9999 are null there, explained in a `null_reasons` block; so are the
+/// era fields and the `calendars`, `spoken` and `week_rule` blocks within a
+/// day of either end of the supported range, where the zone's offset takes
+/// the civil date out of it. `longitude`
+/// (degrees east) adds the mean and apparent `local_sidereal_time` there, in
+/// hours (see [`crate::astronomy`]).
+///
+/// The encoding follows the `Accept` header (`text/csv`, `application/xml`,
+/// `application/yaml`, `text/plain`), or `?format=csv|xml|yaml|text|json`
+/// when given. `Accept: */*` or no header keeps JSON.
+///
 /// # Returns
 ///
-/// JSON response containing timestamp in all supported formats.
+/// Response containing timestamp in all supported formats, a 400 with error
+/// code `unknown_time_zone`, `unknown_fields`, `invalid_instant`,
+/// `invalid_thresholds`, `unsupported_locale`, `invalid_week_rule` or
+/// `unknown_format`, or a 406 with `not_acceptable`.
 ///
 /// # Example Response
 ///
@@ -21,23 +282,596 @@ use crate::timestamp::from_chrono;
 ///   ...
 /// }
 /// ```
-pub async fn home() -> impl Responder {
-    HttpResponse::Ok().json(from_chrono(Utc::now()))
+pub async fn home(
+    req: HttpRequest,
+    query: web::Query<TimestampQuery>,
+) -> Result<HttpResponse, ApiError> {
+    query.render(&req, query.time.instant()?)
+}
+
+/// Render an arbitrary instant - `GET /at/{instant}`
+///
+/// Returns the same payload as [`home`] for the instant in the path instead
+/// of the current time. The instant may be Unix seconds (`1710504045`), Unix
+/// time with an explicit unit (`@1710504045123ms`; units `s`, `ms`, `us`,
+/// `ns`) or RFC 3339 (`2024-03-15T12:30:45Z`). The `tz`, `fields` and
+/// `format` query parameters and the `Accept` header are honoured as on `/`.
+///
+/// # Returns
+///
+/// Response containing the instant in all supported formats, or the same
+/// errors as [`home`].
+pub async fn at_instant(
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<TimestampQuery>,
+) -> Result<HttpResponse, ApiError> {
+    query.render(&req, parse_instant(&path)?)
+}
+
+/// Single field as plain text - `GET /f/{field}`
+///
+/// Returns the raw value of one field with no JSON wrapping, for shell
+/// scripts (`curl -s localhost:8080/f/yyyymmdd`). Honours the `tz`, `at`
+/// and `numerals` query parameters.
+///
+/// # Returns
+///
+/// `text/plain` body containing just the value, a 404 with error code
+/// `unknown_field` (listing the valid names) if the field does not exist, a
+/// 422 with error code `null_field` and the reason if the field has no value
+/// at that instant, or a 400 for an invalid `tz` or `at`.
+pub async fn field_value(
+    path: web::Path<String>,
+    query: web::Query<TimeQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let field: Field = path
+        .parse()
+        .map_err(|_| ApiError::UnknownField(path.into_inner()))?;
+    let numerals = query.numerals()?;
+    let dt = query.zone()?.at(query.instant()?);
+    let body = match field.render(&dt) {
+        Value::String(s) => numerals.render(&s).into_owned(),
+        Value::Null => {
+            return Err(ApiError::NullField {
+                field,
+                reason: field.null_reason_at(&dt).unwrap_or("no reason recorded"),
+            })
+        }
+        other => other.to_string(),
+    };
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::plaintext())
+        .body(body))
+}
+
+/// Custom strftime pattern - `GET /format?pattern=...`
+///
+/// Renders a caller-supplied chrono strftime pattern. The pattern is
+/// validated before formatting, so an unsupported specifier is reported by
+/// name instead of failing mid-response. Patterns are capped at
+/// [`pattern::MAX_PATTERN_LEN`] bytes and output at
+/// [`pattern::MAX_OUTPUT_LEN`] bytes. Honours the `tz`, `at` and `numerals`
+/// query parameters.
+///
+/// # Returns
+///
+/// JSON response with the pattern and its rendering, or a 400 with error
+/// code `missing_parameter`, `invalid_pattern` or `unrepresentable_year`
+/// (naming the token), `pattern_too_long` or `output_too_large`.
+///
+/// # Example Response
+///
+/// ```json
+/// {
+///   "pattern": "%Y-%m-%dT%H",
+///   "formatted": "2024-03-15T12"
+/// }
+/// ```
+pub async fn format_pattern(query: web::Query<FormatQuery>) -> Result<HttpResponse, ApiError> {
+    let pattern = query
+        .pattern
+        .as_deref()
+        .ok_or(ApiError::MissingParameter("pattern"))?;
+    let numerals = query.time.numerals()?;
+    let dt = query.time.zone()?.at(query.time.instant()?);
+    let formatted = pattern::render(&dt, pattern)?;
+    let formatted = numerals.render(&formatted);
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "pattern": pattern,
+        "formatted": formatted,
+    })))
+}
+
+/// Query parameters accepted by `/convert`
+#[derive(Debug, Default, Deserialize)]
+pub struct ConvertQuery {
+    /// Zone the wall-clock time is in
+    pub from: Option<String>,
+    /// Zone to convert to
+    pub to: Option<String>,
+    /// Local wall-clock time without offset, such as `2024-03-31T02:30`
+    pub time: Option<String>,
+}
+
+/// Convert a wall-clock time between zones - `GET /convert`
+///
+/// Resolves `time` as local time in `from` and expresses the result in `to`.
+/// A time repeated when clocks fall back has status `ambiguous` and two
+/// candidates, labelled by `fold`; a time skipped when clocks spring forward
+/// is rejected.
+///
+/// # Returns
+///
+/// JSON response with the candidate reading(s), or a 400 with error code
+/// `missing_parameter`, `unknown_time_zone`, `invalid_local_time`, or
+/// `nonexistent_local_time` (with the `nearest` valid times either side of
+/// the gap).
+///
+/// # Example Response
+///
+/// ```json
+/// {
+///   "from_zone": "Europe/Berlin",
+///   "to_zone": "Asia/Tokyo",
+///   "local": "2024-03-15T12:00:00",
+///   "status": "unique",
+///   "candidates": [
+///     {
+///       "from": "2024-03-15T12:00:00+01:00",
+///       "from_abbreviation": "CET",
+///       "to": "2024-03-15T20:00:00+09:00",
+///       "to_abbreviation": "JST",
+///       "utc": "2024-03-15T11:00:00Z",
+///       "unix_timestamp": 1710500400
+///     }
+///   ]
+/// }
+/// ```
+pub async fn convert_time(query: web::Query<ConvertQuery>) -> Result<HttpResponse, ApiError> {
+    let from = query
+        .from
+        .as_deref()
+        .ok_or(ApiError::MissingParameter("from"))?;
+    let to = query
+        .to
+        .as_deref()
+        .ok_or(ApiError::MissingParameter("to"))?;
+    let time = query
+        .time
+        .as_deref()
+        .ok_or(ApiError::MissingParameter("time"))?;
+    let conversion = convert::convert(
+        Zone::parse(from)?,
+        Zone::parse(to)?,
+        convert::parse_local(time)?,
+    )?;
+    Ok(HttpResponse::Ok().json(conversion))
+}
+
+/// Query parameters accepted by `/diff`
+#[derive(Debug, Default, Deserialize)]
+pub struct DiffQuery {
+    /// Instant the span starts at, in any form `/at/{instant}` accepts
+    pub start: Option<String>,
+    /// Instant the span ends at, in any form `/at/{instant}` accepts
+    pub end: Option<String>,
+    /// Zone whose calendar the breakdown is counted in (default: UTC)
+    pub tz: Option<String>,
+}
+
+/// Difference between two instants - `GET /diff?start=...&end=...`
+///
+/// Reports the span as signed totals in seconds, milliseconds and days, and
+/// as a calendar breakdown in years, months, days, hours, minutes and
+/// seconds. Months are counted on the calendar of `tz` (January 31 to
+/// February 29 is one month) and the remainder on its wall clock, so a day
+/// across a DST change is one day. An end before the start gives negative
+/// totals and `"negative": true` in the breakdown.
+///
+/// # Returns
+///
+/// JSON response with the totals, breakdown, ISO 8601 duration and an
+/// English phrase, or a 400 with error code `missing_parameter`,
+/// `invalid_instant` or `unknown_time_zone`.
+///
+/// # Example Response
+///
+/// ```json
+/// {
+///   "start": "2023-01-01T00:00:00+00:00",
+///   "end": "2024-03-04T04:00:00+00:00",
+///   "zone": "UTC",
+///   "total_seconds": 36993600,
+///   "total_milliseconds": 36993600000,
+///   "total_days": 428.1666666666667,
+///   "calendar": {
+///     "negative": false,
+///     "years": 1,
+///     "months": 2,
+///     "days": 3,
+///     "hours": 4,
+///     "minutes": 0,
+///     "seconds": 0,
+///     "nanoseconds": 0
+///   },
+///   "iso8601": "P1Y2M3DT4H",
+///   "human": "1 year, 2 months, 3 days and 4 hours"
+/// }
+/// ```
+pub async fn diff(query: web::Query<DiffQuery>) -> Result<HttpResponse, ApiError> {
+    let start = query
+        .start
+        .as_deref()
+        .ok_or(ApiError::MissingParameter("start"))?;
+    let end = query
+        .end
+        .as_deref()
+        .ok_or(ApiError::MissingParameter("end"))?;
+    let zone = query
+        .tz
+        .as_deref()
+        .map_or(Ok(Zone::default()), Zone::parse)?;
+    let start = parse_instant_in(start, zone)?.to_utc();
+    let end = parse_instant_in(end, zone)?.to_utc();
+    let difference = duration::difference(start, end, zone);
+    Ok(HttpResponse::Ok().json(difference))
+}
+
+/// Query parameters accepted by `/add`
+#[derive(Debug, Default, Deserialize)]
+pub struct AddQuery {
+    /// Starting instant, or a local date/time read in `tz` (default: now)
+    pub instant: Option<String>,
+    /// Zone the calendar arithmetic and the result are in (default: UTC)
+    pub tz: Option<String>,
+    /// ISO 8601 duration such as `P1M3D`, added to the separate units
+    pub duration: Option<String>,
+    pub years: Option<i64>,
+    pub months: Option<i64>,
+    pub weeks: Option<i64>,
+    pub days: Option<i64>,
+    pub hours: Option<i64>,
+    pub minutes: Option<i64>,
+    pub seconds: Option<i64>,
+    /// `clamp` (default), `overflow` or `error`
+    pub month_end: Option<String>,
+    /// `wall` (default) or `absolute`
+    pub clock: Option<String>,
+    /// Comma-separated list of result fields to return (default: all)
+    pub fields: Option<String>,
+    /// Output encoding, overriding the `Accept` header
+    pub format: Option<String>,
+}
+
+impl AddQuery {
+    /// The offset to add: `duration` plus the separate unit parameters
+    fn offset(&self) -> Result<CalendarOffset, ApiError> {
+        let units = CalendarOffset {
+            years: self.years.unwrap_or(0),
+            months: self.months.unwrap_or(0),
+            weeks: self.weeks.unwrap_or(0),
+            days: self.days.unwrap_or(0),
+            hours: self.hours.unwrap_or(0),
+            minutes: self.minutes.unwrap_or(0),
+            seconds: self.seconds.unwrap_or(0),
+            nanoseconds: 0,
+        };
+        match self.duration.as_deref() {
+            Some(duration) => duration
+                .parse::<CalendarOffset>()?
+                .checked_add(&units)
+                .ok_or(ApiError::OutOfRange),
+            None => Ok(units),
+        }
+    }
+}
+
+/// Calendar-aware date arithmetic - `GET /add`
+///
+/// Adds `years`, `months`, `weeks`, `days`, `hours`, `minutes` and `seconds`
+/// (each optional and signed), and/or an ISO 8601 `duration`, to `instant`.
+/// Calendar units are applied in that order on the date in `tz`; hours and
+/// smaller are exact elapsed time. `month_end` chooses what happens when a
+/// month step lands past the end of a shorter month, and `clock` whether a
+/// calendar step across DST keeps the wall-clock time (`wall`) or the number
+/// of elapsed hours (`absolute`). Under `wall`, a result that falls in a DST
+/// gap is shifted forward past it, and one that is ambiguous takes the
+/// earlier reading, as reported by `resolution`.
+///
+/// # Returns
+///
+/// The applied settings and the `result` as a full timestamp (projected by
+/// `fields`, encoded per `format` or `Accept`), or a 400 with error code
+/// `invalid_instant`, `unknown_time_zone`, `invalid_duration`,
+/// `invalid_parameter`, `day_out_of_range` or `out_of_range`.
+///
+/// # Example Response
+///
+/// ```json
+/// {
+///   "instant": "2024-01-31T00:00:00+00:00",
+///   "offset": { "years": 0, "months": 1, "weeks": 0, "days": -3, ... },
+///   "month_end": "clamp",
+///   "month_end_adjusted": true,
+///   "clock": "wall",
+///   "resolution": "unique",
+///   "result": {
+///     "yyyy_mm_dd": "2024_02_26",
+///     ...
+///   }
+/// }
+/// ```
+pub async fn add(req: HttpRequest, query: web::Query<AddQuery>) -> Result<HttpResponse, ApiError> {
+    let output = OutputFormat::negotiate(&req, query.format.as_deref())?;
+    let zone = query
+        .tz
+        .as_deref()
+        .map_or(Ok(Zone::default()), Zone::parse)?;
+    let start = match query.instant.as_deref() {
+        Some(instant) => arithmetic::parse_start(instant, zone)?,
+        None => zone.at(Utc::now()),
+    };
+    let offset = query.offset()?;
+    let month_end = query
+        .month_end
+        .as_deref()
+        .map_or(Ok(MonthEnd::default()), MonthEnd::parse)?;
+    let clock = query
+        .clock
+        .as_deref()
+        .map_or(Ok(Clock::default()), Clock::parse)?;
+    let fields = query
+        .fields
+        .as_deref()
+        .map_or(Ok(Field::ALL.to_vec()), parse_fields)?;
+
+    let sum = arithmetic::add(&start, &offset, month_end, clock)?;
+    let mut body = Map::new();
+    body.insert(
+        "instant".into(),
+        Value::String(start.to_rfc3339_opts(SecondsFormat::AutoSi, false)),
+    );
+    body.insert("offset".into(), serde_json::json!(offset));
+    body.insert("month_end".into(), serde_json::json!(month_end));
+    body.insert(
+        "month_end_adjusted".into(),
+        Value::Bool(sum.month_end_adjusted),
+    );
+    body.insert("clock".into(), serde_json::json!(clock));
+    if let Some(resolution) = sum.resolution {
+        body.insert("resolution".into(), serde_json::json!(resolution));
+    }
+    body.insert(
+        "result".into(),
+        Value::Object(from_chrono_fields(&sum.result, &fields)),
+    );
+    Ok(output.respond(&body))
+}
+
+/// Query parameters accepted by `/parse`
+#[derive(Debug, Default, Deserialize)]
+pub struct ParseQuery {
+    /// Zone times without an offset are read in, and results rendered in
+    pub tz: Option<String>,
+    /// Comma-separated list of timestamp fields to return (default: all)
+    pub fields: Option<String>,
+    /// Output encoding, overriding the `Accept` header
+    pub format: Option<String>,
+}
+
+/// Free-form timestamp parser - `POST /parse`
+///
+/// The request body is the raw string to parse (`03/04/24 5pm`,
+/// `Fri, 15 Mar 2024 12:30:45 +0000`, `1710504045123`, `2024-W11-5`), or a
+/// JSON object `{"input": "..."}` when sent as `application/json`. Every
+/// known format is tried; readings that agree on the instant are merged, so
+/// more than one candidate means the input is ambiguous, as with month/day
+/// against day/month or seconds against milliseconds. Each date field that
+/// `/` returns parses back. See [`parse`] for the recognised forms.
+///
+/// # Returns
+///
+/// Response with `status` `unique` or `ambiguous` and one candidate per
+/// distinct reading, each with the matching formats, an interpretation, the
+/// `Timestamp` fields whose rendering equals the input, and the full
+/// timestamp; or a 400 with error code `unrecognized_timestamp`,
+/// `unknown_time_zone` or `unknown_fields`.
+///
+/// # Example Response
+///
+/// ```json
+/// {
+///   "input": "03/04/24 5pm",
+///   "zone": "UTC",
+///   "status": "ambiguous",
+///   "candidates": [
+///     {
+///       "formats": ["%m/%d/%y %-I%P"],
+///       "interpretation": "month/day/two-digit year, 12-hour clock, read in UTC",
+///       "fields": [],
+///       "timestamp": { "yyyy_mm_dd": "2024_03_04", ... }
+///     },
+///     {
+///       "formats": ["%d/%m/%y %-I%P"],
+///       "interpretation": "day/month/two-digit year, 12-hour clock, read in UTC",
+///       "fields": [],
+///       "timestamp": { "yyyy_mm_dd": "2024_04_03", ... }
+///     }
+///   ]
+/// }
+/// ```
+pub async fn parse_timestamp(
+    req: HttpRequest,
+    query: web::Query<ParseQuery>,
+    body: String,
+) -> Result<HttpResponse, ApiError> {
+    let output = OutputFormat::negotiate(&req, query.format.as_deref())?;
+    let zone = query
+        .tz
+        .as_deref()
+        .map_or(Ok(Zone::default()), Zone::parse)?;
+    let fields = query
+        .fields
+        .as_deref()
+        .map_or(Ok(Field::ALL.to_vec()), parse_fields)?;
+    let is_json = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    let input = if is_json {
+        serde_json::from_str::<Value>(&body)
+            .ok()
+            .and_then(|value| value.get("input")?.as_str().map(String::from))
+            .ok_or_else(|| ApiError::UnrecognizedTimestamp {
+                input: body.chars().take(64).collect(),
+                reason: "expected a JSON object with a string 'input'".to_string(),
+            })?
+    } else {
+        body
+    };
+
+    let readings = parse::detect(&input, zone)?;
+    let status = if readings.len() > 1 {
+        Status::Ambiguous
+    } else {
+        Status::Unique
+    };
+    let candidates: Vec<Value> = readings
+        .into_iter()
+        .map(|reading| {
+            serde_json::json!({
+                "formats": reading.formats,
+                "interpretation": reading.interpretation,
+                "fields": reading.fields,
+                "timestamp": from_chrono_fields(&zone.at(reading.instant), &fields),
+            })
+        })
+        .collect();
+    let mut body = Map::new();
+    body.insert("input".into(), Value::String(input.trim().to_string()));
+    body.insert("zone".into(), Value::String(zone.to_string()));
+    body.insert("status".into(), serde_json::json!(status));
+    body.insert("candidates".into(), Value::Array(candidates));
+    Ok(output.respond(&body))
+}
+
+/// Query parameters accepted by `/resolve`
+#[derive(Debug, Default, Deserialize)]
+pub struct ResolveQuery {
+    /// English expression such as `next friday at 9am`
+    pub q: Option<String>,
+    /// Zone the expression is resolved in (default: UTC)
+    pub tz: Option<String>,
+    /// Reference time instead of the server clock, in any form `/add`
+    /// accepts for `instant`
+    pub now: Option<String>,
+    /// Comma-separated list of result fields to return (default: all)
+    pub fields: Option<String>,
+    /// Output encoding, overriding the `Accept` header
+    pub format: Option<String>,
+}
+
+/// Resolve an English relative expression - `GET /resolve?q=...`
+///
+/// Resolves expressions such as `tomorrow`, `next friday at 9am`,
+/// `2 weeks ago`, `in 3 business days`, `last day of month` or
+/// `end of quarter` against the server clock, or against `now` when given,
+/// in zone `tz`. See [`natural`] for the full grammar.
+///
+/// # Returns
+///
+/// The expression, the reference time and the `result` as a full timestamp
+/// (projected by `fields`, encoded per `format` or `Accept`), or a 400 with
+/// error code `missing_parameter`, `invalid_expression` (with the offending
+/// `word` and its byte `position`), `invalid_instant`, `unknown_time_zone`
+/// or `out_of_range`.
+///
+/// # Example Response
+///
+/// ```json
+/// {
+///   "query": "next friday at 9am",
+///   "now": "2024-03-13T15:00:00+00:00",
+///   "result": {
+///     "yyyy_mm_dd": "2024_03_15",
+///     ...
+///   }
+/// }
+/// ```
+pub async fn resolve_expression(
+    req: HttpRequest,
+    query: web::Query<ResolveQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let q = query.q.as_deref().ok_or(ApiError::MissingParameter("q"))?;
+    let output = OutputFormat::negotiate(&req, query.format.as_deref())?;
+    let zone = query
+        .tz
+        .as_deref()
+        .map_or(Ok(Zone::default()), Zone::parse)?;
+    let now = match query.now.as_deref() {
+        Some(now) => arithmetic::parse_start(now, zone)?,
+        None => zone.at(Utc::now()),
+    };
+    let fields = query
+        .fields
+        .as_deref()
+        .map_or(Ok(Field::ALL.to_vec()), parse_fields)?;
+
+    let result = natural::resolve_expression(q, &now)?;
+    let mut body = Map::new();
+    body.insert("query".into(), Value::String(q.to_string()));
+    body.insert(
+        "now".into(),
+        Value::String(now.to_rfc3339_opts(SecondsFormat::AutoSi, false)),
+    );
+    body.insert(
+        "result".into(),
+        Value::Object(from_chrono_fields(&result, &fields)),
+    );
+    Ok(output.respond(&body))
 }
 
 /// Health check endpoint for monitoring and orchestration
 ///
 /// # Returns
 ///
-/// JSON response with status "healthy" and HTTP 200.
+/// JSON response with status "healthy" and HTTP 200, and the leap second
+/// table in use. Once that table has expired, a `warnings` list says so;
+/// the service still answers, but TAI, TT and GPS time may be a second out.
 ///
 /// # Example Response
 ///
 /// ```json
 /// {
-///   "status": "healthy"
+///   "status": "healthy",
+///   "leap_seconds": {
+///     "source": "embedded",
+///     "updated": "2026-01-06T11:14:18Z",
+///     "expires": "2026-12-28T00:00:00Z",
+///     "stale": false
+///   }
 /// }
 /// ```
 pub async fn health_check() -> impl Responder {
-    HttpResponse::Ok().json(serde_json::json!({"status": "healthy"}))
+    let table = leap_seconds::table();
+    let stale = table.is_stale(Utc::now());
+    let instant = |dt: DateTime<Utc>| dt.to_rfc3339_opts(SecondsFormat::Secs, true);
+    let mut body = serde_json::json!({
+        "status": "healthy",
+        "leap_seconds": {
+            "source": table.source,
+            "updated": table.updated.map(instant),
+            "expires": instant(table.expires),
+            "stale": stale,
+        },
+    });
+    if stale {
+        body["warnings"] = serde_json::json!([format!(
+            "leap second table expired on {}; set LEAP_SECONDS_FILE to a current leap-seconds.list",
+            instant(table.expires)
+        )]);
+    }
+    HttpResponse::Ok().json(body)
 }
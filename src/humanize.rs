@@ -0,0 +1,320 @@
+//! Humanized relative time such as "3 hours ago" or "in 2 days".
+//!
+//! The distance between two instants is expressed in a single unit, chosen
+//! by comparing the distance, rounded to each unit in turn, against a
+//! threshold for that unit. With the defaults:
+//!
+//! | Rounded distance | Unit | Example |
+//! |------------------|------|---------|
+//! | under 45 seconds | seconds | `44 seconds ago` |
+//! | under 45 minutes | minutes | `in 44 minutes` |
+//! | under 22 hours | hours | `21 hours ago` |
+//! | under 26 days | days | `in 25 days` |
+//! | under 11 months | months | `10 months ago` |
+//! | otherwise | years | `in 2 years` |
+//!
+//! Each unit rounds half up from the exact distance (so 90 seconds is
+//! 2 minutes); months are average Gregorian months of 30.436875 days and
+//! years are 365.2425 days. A distance that rounds to zero seconds reads
+//! `just now`. The defaults match moment.js, so strings agree with UIs that
+//! already use it.
+
+use std::fmt;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+
+use crate::duration::plural;
+use crate::error::ApiError;
+
+const SECONDS_PER_MONTH: f64 = 30.436875 * 86_400.0;
+const SECONDS_PER_YEAR: f64 = 365.2425 * 86_400.0;
+
+/// Largest rounded value, exclusive, shown in each unit before moving to
+/// the next larger one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Thresholds {
+    pub seconds: u32,
+    pub minutes: u32,
+    pub hours: u32,
+    pub days: u32,
+    pub months: u32,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Thresholds {
+            seconds: 45,
+            minutes: 45,
+            hours: 22,
+            days: 26,
+            months: 11,
+        }
+    }
+}
+
+impl Thresholds {
+    /// Override defaults from a list such as `hours:24,days:30`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use thedate::humanize::Thresholds;
+    ///
+    /// let t = Thresholds::parse("hours:24, days:30").unwrap();
+    /// assert_eq!((t.hours, t.days, t.minutes), (24, 30, 45));
+    /// assert!(Thresholds::parse("weeks:2").is_err());
+    /// ```
+    pub fn parse(spec: &str) -> Result<Self, ApiError> {
+        let mut thresholds = Thresholds::default();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let invalid = |reason: &str| ApiError::InvalidThresholds {
+                entry: entry.to_string(),
+                reason: reason.to_string(),
+            };
+            let (unit, value) = entry
+                .split_once(':')
+                .ok_or_else(|| invalid("expected unit:value"))?;
+            let value: u32 = value
+                .trim()
+                .parse()
+                .ok()
+                .filter(|v| *v > 0)
+                .ok_or_else(|| invalid("value must be a positive integer"))?;
+            let slot = match unit.trim() {
+                "seconds" => &mut thresholds.seconds,
+                "minutes" => &mut thresholds.minutes,
+                "hours" => &mut thresholds.hours,
+                "days" => &mut thresholds.days,
+                "months" => &mut thresholds.months,
+                _ => {
+                    return Err(invalid(
+                        "unit must be seconds, minutes, hours, days or months",
+                    ))
+                }
+            };
+            *slot = value;
+        }
+        Ok(thresholds)
+    }
+}
+
+/// Unit a relative time is expressed in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelativeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Month,
+    Year,
+}
+
+impl RelativeUnit {
+    fn short(self) -> &'static str {
+        match self {
+            RelativeUnit::Second => "s",
+            RelativeUnit::Minute => "m",
+            RelativeUnit::Hour => "h",
+            RelativeUnit::Day => "d",
+            RelativeUnit::Month => "mo",
+            RelativeUnit::Year => "y",
+        }
+    }
+}
+
+impl fmt::Display for RelativeUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RelativeUnit::Second => "second",
+            RelativeUnit::Minute => "minute",
+            RelativeUnit::Hour => "hour",
+            RelativeUnit::Day => "day",
+            RelativeUnit::Month => "month",
+            RelativeUnit::Year => "year",
+        })
+    }
+}
+
+/// An instant described relative to a reference instant
+#[derive(Debug, Serialize)]
+pub struct Relative {
+    pub relative_to: String,
+    /// English phrase: `3 hours ago`, `in 2 days`, `just now`
+    pub phrase: String,
+    /// Compact form: `3h ago`, `in 2d`, `now`
+    pub short: String,
+    /// Rounded distance in `unit`; negative for the past
+    pub value: i64,
+    pub unit: RelativeUnit,
+}
+
+/// Round half up, away from zero
+fn round(value: f64) -> i64 {
+    (value + 0.5).floor() as i64
+}
+
+/// Describe `instant` relative to `reference`
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use thedate::humanize::{humanize, Thresholds};
+///
+/// let reference = Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap();
+/// let relative = humanize(reference - Duration::hours(3), reference, &Thresholds::default());
+/// assert_eq!(relative.phrase, "3 hours ago");
+/// assert_eq!(relative.short, "3h ago");
+/// assert_eq!(relative.value, -3);
+/// ```
+pub fn humanize(
+    instant: DateTime<Utc>,
+    reference: DateTime<Utc>,
+    thresholds: &Thresholds,
+) -> Relative {
+    let delta = instant - reference;
+    let past = delta < chrono::Duration::zero();
+    let exact = delta.abs().num_milliseconds() as f64 / 1000.0;
+
+    let below = |value: i64, threshold: u32| value < i64::from(threshold);
+    let seconds = round(exact);
+    let minutes = round(exact / 60.0);
+    let hours = round(exact / 3_600.0);
+    let days = round(exact / 86_400.0);
+    let months = round(exact / SECONDS_PER_MONTH);
+    let (magnitude, unit) = if below(seconds, thresholds.seconds) {
+        (seconds, RelativeUnit::Second)
+    } else if below(minutes, thresholds.minutes) {
+        (minutes, RelativeUnit::Minute)
+    } else if below(hours, thresholds.hours) {
+        (hours, RelativeUnit::Hour)
+    } else if below(days, thresholds.days) {
+        (days, RelativeUnit::Day)
+    } else if below(months, thresholds.months) {
+        (months, RelativeUnit::Month)
+    } else {
+        (round(exact / SECONDS_PER_YEAR).max(1), RelativeUnit::Year)
+    };
+    // A larger unit never reads as zero: 30 seconds past a 20-second
+    // threshold is 1 minute, not 0 minutes
+    let magnitude = if unit == RelativeUnit::Second {
+        magnitude
+    } else {
+        magnitude.max(1)
+    };
+
+    let (phrase, short) = if magnitude == 0 {
+        ("just now".to_string(), "now".to_string())
+    } else {
+        let amount = plural(magnitude as u32, &unit.to_string());
+        let compact = format!("{}{}", magnitude, unit.short());
+        if past {
+            (format!("{} ago", amount), format!("{} ago", compact))
+        } else {
+            (format!("in {}", amount), format!("in {}", compact))
+        }
+    };
+    Relative {
+        relative_to: reference.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        phrase,
+        short,
+        value: if past { -magnitude } else { magnitude },
+        unit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn reference() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap()
+    }
+
+    fn phrase(delta: Duration) -> String {
+        humanize(reference() + delta, reference(), &Thresholds::default()).phrase
+    }
+
+    #[test]
+    fn test_default_thresholds() {
+        assert_eq!(phrase(Duration::zero()), "just now");
+        assert_eq!(phrase(Duration::seconds(-44)), "44 seconds ago");
+        assert_eq!(phrase(Duration::seconds(45)), "in 1 minute");
+        assert_eq!(phrase(Duration::seconds(90)), "in 2 minutes");
+        assert_eq!(phrase(Duration::minutes(-44)), "44 minutes ago");
+        assert_eq!(phrase(Duration::minutes(45)), "in 1 hour");
+        assert_eq!(phrase(Duration::hours(21)), "in 21 hours");
+        assert_eq!(phrase(Duration::hours(-22)), "1 day ago");
+        assert_eq!(phrase(Duration::days(25)), "in 25 days");
+        assert_eq!(phrase(Duration::days(26)), "in 1 month");
+        assert_eq!(phrase(Duration::days(-300)), "10 months ago");
+        assert_eq!(phrase(Duration::days(320)), "in 1 year");
+        assert_eq!(phrase(Duration::days(-800)), "2 years ago");
+    }
+
+    #[test]
+    fn test_short_form_and_value() {
+        let r = humanize(
+            reference() + Duration::days(2),
+            reference(),
+            &Thresholds::default(),
+        );
+        assert_eq!(r.short, "in 2d");
+        assert_eq!((r.value, r.unit), (2, RelativeUnit::Day));
+        let r = humanize(
+            reference() - Duration::days(400),
+            reference(),
+            &Thresholds::default(),
+        );
+        assert_eq!(r.short, "1y ago");
+        assert_eq!(r.value, -1);
+    }
+
+    #[test]
+    fn test_subsecond_is_just_now() {
+        let r = humanize(
+            reference() + Duration::milliseconds(400),
+            reference(),
+            &Thresholds::default(),
+        );
+        assert_eq!((r.phrase.as_str(), r.short.as_str()), ("just now", "now"));
+        assert_eq!(r.value, 0);
+    }
+
+    #[test]
+    fn test_custom_thresholds() {
+        let thresholds = Thresholds::parse("hours:24,days:7").unwrap();
+        let r = humanize(reference() - Duration::hours(23), reference(), &thresholds);
+        assert_eq!(r.phrase, "23 hours ago");
+        let r = humanize(reference() + Duration::days(8), reference(), &thresholds);
+        assert_eq!(r.phrase, "in 1 month");
+    }
+
+    #[test]
+    fn test_small_threshold_never_shows_zero() {
+        let thresholds = Thresholds::parse("seconds:10").unwrap();
+        let r = humanize(
+            reference() + Duration::seconds(20),
+            reference(),
+            &thresholds,
+        );
+        assert_eq!(r.phrase, "in 1 minute");
+    }
+
+    #[test]
+    fn test_invalid_thresholds() {
+        for spec in ["hours", "hours:0", "hours:-1", "fortnights:2"] {
+            assert!(
+                matches!(
+                    Thresholds::parse(spec),
+                    Err(ApiError::InvalidThresholds { .. })
+                ),
+                "{} should be rejected",
+                spec
+            );
+        }
+    }
+}
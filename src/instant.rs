@@ -0,0 +1,365 @@
+//! Parsing of caller-supplied instants.
+//!
+//! Accepted forms:
+//! - Unix seconds: `1710504045` (a bare integer is always seconds, never
+//!   guessed from its magnitude)
+//! - Unix time with an explicit unit: `@1710504045123ms`, `@1710504045s`,
+//!   `@1710504045123456us`, `@1710504045123456789ns`
+//! - RFC 3339: `2024-03-15T12:30:45Z`, `2024-03-15T08:30:45.5-04:00`
+//! - An ISO 8601 expanded year, signed and of at least four digits, at
+//!   midnight UTC or with an RFC 3339 time: `+12345-01-01`, `-0044-03-15`
+//!   (year 0 is 1 BCE, so this is 45 BCE), `+2024-03-15T12:30:45Z`
+//! - A date in another calendar, prefixed by its name, at midnight UTC or
+//!   with an RFC 3339 time: `persian:1403-01-01`,
+//!   `ethiopic:2017-01-01T09:00:00+03:00` (see [`crate::calendar`])
+//! - A Japanese era or ROC date, likewise: `R6.03.15`, `令和6年3月15日`,
+//!   `民國113年3月15日`, `R6.03.15T09:00:00+09:00` (see [`crate::era`])
+//!
+//! Any form may be written with native digits (`١٧١٠٥٠٥٨٤٥`); see
+//! [`crate::numerals`].
+
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
+
+use crate::calendar::Calendar;
+use crate::era;
+use crate::error::ApiError;
+use crate::numerals::to_latin;
+use crate::zone::{local_datetime, Zone};
+
+/// Resolution of a numeric Unix time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unit {
+    Seconds,
+    Millis,
+    Micros,
+    Nanos,
+}
+
+impl Unit {
+    fn from_suffix(suffix: &str) -> Option<Self> {
+        match suffix {
+            "" | "s" => Some(Unit::Seconds),
+            "ms" => Some(Unit::Millis),
+            "us" | "µs" => Some(Unit::Micros),
+            "ns" => Some(Unit::Nanos),
+            _ => None,
+        }
+    }
+
+    /// Convert a count of this unit since the Unix epoch into an instant
+    pub fn to_instant(self, value: i64) -> Option<DateTime<Utc>> {
+        match self {
+            Unit::Seconds => DateTime::from_timestamp(value, 0),
+            Unit::Millis => DateTime::from_timestamp_millis(value),
+            Unit::Micros => DateTime::from_timestamp_micros(value),
+            Unit::Nanos => Some(DateTime::from_timestamp_nanos(value)),
+        }
+    }
+}
+
+/// Parse an instant from one of the accepted forms
+///
+/// # Examples
+///
+/// ```
+/// use thedate::instant::parse_instant;
+///
+/// let a = parse_instant("1710505845").unwrap();
+/// let b = parse_instant("@1710505845000ms").unwrap();
+/// let c = parse_instant("2024-03-15T12:30:45Z").unwrap();
+/// let d = parse_instant("persian:1402-12-25T16:00:45+03:30").unwrap();
+/// assert_eq!(a, b);
+/// assert_eq!(b, c);
+/// assert_eq!(c, d);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`ApiError::InvalidInstant`] if the input matches none of the
+/// accepted forms or lies outside the representable range.
+pub fn parse_instant(input: &str) -> Result<DateTime<Utc>, ApiError> {
+    let invalid = |reason: &str| ApiError::InvalidInstant {
+        input: input.to_string(),
+        reason: reason.to_string(),
+    };
+
+    let latin = to_latin(input.trim());
+    let trimmed = latin.as_ref();
+    if trimmed.is_empty() {
+        return Err(invalid("instant is empty"));
+    }
+
+    if let Some((name, date)) = trimmed.split_once(':').filter(|(name, _)| {
+        !name.is_empty() && name.bytes().all(|b| b.is_ascii_alphabetic() || b == b'-')
+    }) {
+        let calendar = Calendar::parse(name).map_err(|_| {
+            invalid(&format!(
+                "unknown calendar; expected one of {}",
+                Calendar::NAMES.join(", ")
+            ))
+        })?;
+        let (date, time) = split_time(date);
+        let gregorian = calendar
+            .parse_date(date)
+            .ok_or_else(|| invalid(&calendar.date_error(date)))?;
+        return at_time(gregorian, time).map_err(|reason| invalid(&reason));
+    }
+    let (date, time) = split_time(trimmed);
+    if let Some(gregorian) = era::parse(date) {
+        return gregorian
+            .and_then(|gregorian| at_time(gregorian, time))
+            .map_err(|reason| invalid(&reason));
+    }
+
+    if let Some(expanded) = expanded_date(trimmed) {
+        return expanded.map_err(|reason| invalid(&reason));
+    }
+
+    let explicit = trimmed.strip_prefix('@');
+    let numeric = explicit.unwrap_or(trimmed);
+    let digits_end = numeric
+        .char_indices()
+        .find(|&(i, c)| !(c.is_ascii_digit() || (i == 0 && c == '-')))
+        .map_or(numeric.len(), |(i, _)| i);
+    let (number, suffix) = numeric.split_at(digits_end);
+
+    if explicit.is_some() || (suffix.is_empty() && !number.is_empty()) {
+        let unit = Unit::from_suffix(suffix)
+            .ok_or_else(|| invalid("unit must be one of s, ms, us, ns"))?;
+        let value: i64 = number
+            .parse()
+            .map_err(|_| invalid("expected an integer Unix time"))?;
+        return unit
+            .to_instant(value)
+            .ok_or_else(|| invalid("Unix time is out of range"));
+    }
+
+    DateTime::parse_from_rfc3339(trimmed)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| invalid(&format!("not a Unix time or RFC 3339 timestamp ({})", e)))
+}
+
+/// Parse `input` as [`parse_instant`] does, and express it in `zone`
+///
+/// # Errors
+///
+/// As [`parse_instant`], and [`ApiError::InvalidInstant`] when the
+/// wall-clock time in `zone` falls outside the supported range, which
+/// happens within a day of either end of it.
+pub fn parse_instant_in(input: &str, zone: Zone) -> Result<DateTime<Zone>, ApiError> {
+    let dt = zone.at(parse_instant(input)?);
+    if local_datetime(&dt).is_none() {
+        return Err(ApiError::InvalidInstant {
+            input: input.to_string(),
+            reason: format!("its local time in {} is outside the supported range", zone),
+        });
+    }
+    Ok(dt)
+}
+
+/// Split a date from an optional `T`-prefixed time of day; a leading `T`
+/// is the Taishō era initial, not a separator
+fn split_time(input: &str) -> (&str, Option<&str>) {
+    match input.char_indices().skip(1).find(|&(_, c)| c == 'T') {
+        Some((at, _)) => (&input[..at], Some(&input[at + 1..])),
+        None => (input, None),
+    }
+}
+
+/// A signed ISO 8601 expanded-year date, such as `-0044-03-15`, with an
+/// optional time; `None` when `input` does not have that shape
+fn expanded_date(input: &str) -> Option<Result<DateTime<Utc>, String>> {
+    let negative = input.starts_with('-');
+    let rest = input.strip_prefix(['+', '-'])?;
+    let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+    if digits < 4 || rest.as_bytes().get(digits) != Some(&b'-') {
+        return None;
+    }
+    let (date, time) = split_time(rest);
+    let mut parts = date.splitn(3, '-');
+    let mut part = || parts.next().and_then(|part| part.parse::<u32>().ok());
+    let date = match (part(), part(), part()) {
+        (Some(year), Some(month), Some(day)) => i32::try_from(year).ok().and_then(|year| {
+            NaiveDate::from_ymd_opt(if negative { -year } else { year }, month, day)
+        }),
+        _ => None,
+    };
+    Some(
+        date.ok_or_else(|| {
+            format!(
+                "expected an existing expanded-year date between {} and {}",
+                NaiveDate::MIN,
+                NaiveDate::MAX
+            )
+        })
+        .and_then(|date| at_time(date, time)),
+    )
+}
+
+/// `date` at an RFC 3339 time of day with offset, or at midnight UTC
+fn at_time(date: NaiveDate, time: Option<&str>) -> Result<DateTime<Utc>, String> {
+    let Some(time) = time else {
+        return Ok(date.and_time(NaiveTime::MIN).and_utc());
+    };
+    // Parse the time on a stand-in date, as RFC 3339 only has 4-digit years
+    let parsed = DateTime::parse_from_rfc3339(&format!("2000-01-01T{}", time))
+        .map_err(|e| format!("invalid time of day ({})", e))?;
+    parsed
+        .offset()
+        .from_local_datetime(&date.and_time(parsed.time()))
+        .single()
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok_or_else(|| "instant is out of range".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn expected() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 15, 12, 30, 45).unwrap()
+    }
+
+    #[test]
+    fn test_parse_instant_in_refuses_local_times_out_of_range() {
+        let west = Zone::parse("America/New_York").unwrap();
+        let east = Zone::parse("+14:00").unwrap();
+        assert!(parse_instant_in("-262143-01-01T00:00:00Z", east).is_ok());
+        let error = parse_instant_in("-262143-01-01T00:00:00Z", west).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid instant '-262143-01-01T00:00:00Z': its local time in America/New_York \
+             is outside the supported range"
+        );
+        assert!(parse_instant_in("+262142-12-31T23:59:59Z", east).is_err());
+    }
+
+    #[test]
+    fn test_bare_integer_is_seconds() {
+        assert_eq!(parse_instant("1710505845").unwrap(), expected());
+    }
+
+    #[test]
+    fn test_explicit_units() {
+        assert_eq!(parse_instant("@1710505845").unwrap(), expected());
+        assert_eq!(parse_instant("@1710505845s").unwrap(), expected());
+        assert_eq!(parse_instant("@1710505845000ms").unwrap(), expected());
+        assert_eq!(parse_instant("@1710505845000000us").unwrap(), expected());
+        assert_eq!(parse_instant("@1710505845000000000ns").unwrap(), expected());
+    }
+
+    #[test]
+    fn test_sub_second_precision_is_kept() {
+        let dt = parse_instant("@1710505845123ms").unwrap();
+        assert_eq!(dt.timestamp_subsec_millis(), 123);
+    }
+
+    #[test]
+    fn test_negative_unix_time() {
+        let dt = parse_instant("-86400").unwrap();
+        assert_eq!(dt, Utc.with_ymd_and_hms(1969, 12, 31, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_rfc3339() {
+        assert_eq!(parse_instant("2024-03-15T12:30:45Z").unwrap(), expected());
+        assert_eq!(
+            parse_instant("2024-03-15T08:30:45-04:00").unwrap(),
+            expected()
+        );
+    }
+
+    #[test]
+    fn test_expanded_years() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let midnight = |y, m, d| date(y, m, d).and_time(NaiveTime::MIN).and_utc();
+        assert_eq!(
+            parse_instant("+12345-01-01").unwrap(),
+            midnight(12345, 1, 1)
+        );
+        assert_eq!(parse_instant("-0044-03-15").unwrap(), midnight(-44, 3, 15));
+        assert_eq!(parse_instant("+2024-03-15T12:30:45Z").unwrap(), expected());
+        assert_eq!(
+            parse_instant("-0044-03-15T12:00:00+02:00").unwrap(),
+            date(-44, 3, 15).and_hms_opt(10, 0, 0).unwrap().and_utc()
+        );
+        assert!(matches!(
+            parse_instant("+999999-01-01"),
+            Err(ApiError::InvalidInstant { .. })
+        ));
+        assert!(matches!(
+            parse_instant("-0044-02-30"),
+            Err(ApiError::InvalidInstant { .. })
+        ));
+        // Still a negative Unix time, not a year
+        assert_eq!(parse_instant("-86400").unwrap().timestamp(), -86_400);
+    }
+
+    #[test]
+    fn test_other_calendars() {
+        let nowruz = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        assert_eq!(parse_instant("persian:1403-01-01").unwrap(), nowruz);
+        assert_eq!(
+            parse_instant("Buddhist:2567-03-15T12:30:45Z").unwrap(),
+            expected()
+        );
+        assert!(matches!(
+            parse_instant("persian:1403-02-32"),
+            Err(ApiError::InvalidInstant { .. })
+        ));
+        assert!(matches!(
+            parse_instant("persian:1403-01-01T25:00:00Z"),
+            Err(ApiError::InvalidInstant { .. })
+        ));
+        assert!(matches!(
+            parse_instant("mayan:13-0-0"),
+            Err(ApiError::InvalidInstant { .. })
+        ));
+    }
+
+    #[test]
+    fn test_era_dates() {
+        assert_eq!(
+            parse_instant("R6.03.15T21:30:45+09:00").unwrap(),
+            expected()
+        );
+        assert_eq!(
+            parse_instant("T15.12.24T12:00:00Z").unwrap(),
+            Utc.with_ymd_and_hms(1926, 12, 24, 12, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_instant("令和6年3月15日").unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 15, 0, 0, 0).unwrap()
+        );
+        assert!(matches!(
+            parse_instant("H31.05.01"),
+            Err(ApiError::InvalidInstant { reason, .. }) if reason == "Heisei ended on 2019-04-30"
+        ));
+    }
+
+    #[test]
+    fn test_native_digits() {
+        assert_eq!(parse_instant("١٧١٠٥٠٥٨٤٥").unwrap(), expected());
+        assert_eq!(parse_instant("@๑๗๑๐๕๐๕๘๔๕s").unwrap(), expected());
+        assert_eq!(parse_instant("२०२४-०३-१५T१२:३०:४५Z").unwrap(), expected());
+    }
+
+    #[test]
+    fn test_rejects_unknown_unit() {
+        let err = parse_instant("@1710504045h").unwrap_err();
+        assert_eq!(err.code(), "invalid_instant");
+    }
+
+    #[test]
+    fn test_rejects_out_of_range() {
+        assert!(parse_instant("@99999999999999999s").is_err());
+    }
+
+    #[test]
+    fn test_rejects_garbage() {
+        assert!(parse_instant("yesterday").is_err());
+        assert!(parse_instant("").is_err());
+        assert!(parse_instant("@").is_err());
+    }
+}
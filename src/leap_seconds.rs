@@ -0,0 +1,425 @@
+//! Leap seconds, and the atomic time scales that count them.
+//!
+//! TAI − UTC comes from a table in the IERS `leap-seconds.list` format. A
+//! copy is built in, and [`install`] replaces it at startup with a newer
+//! file (see `LEAP_SECONDS_FILE` in [`crate::config`]). The `#h` line, a
+//! SHA-1 over the dates and entries, is checked whenever it is present, and
+//! a file read at startup must have one: the hash is the last line, so a
+//! truncated download is refused along with a corrupted one.
+//!
+//! From the offset:
+//! - TAI is UTC plus TAI − UTC
+//! - GPS time is TAI − 19 s, counted in weeks from 1980-01-06T00:00:00Z
+//! - Terrestrial Time is TAI + 32.184 s
+//!
+//! UTC only counted whole leap seconds from 1972; earlier instants have no
+//! offset, and their TAI, TT and GPS fields are null. A table past its
+//! expiry still gives the last known offset, which may miss a newly
+//! announced leap second; `/health` warns when that is the case.
+
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use sha1::{Digest, Sha1};
+
+/// The built-in table
+const EMBEDDED: &str = include_str!("leap-seconds.list");
+/// Seconds from the NTP epoch, 1900-01-01, to the Unix epoch
+const NTP_UNIX_OFFSET: i64 = 2_208_988_800;
+/// Unix time of the GPS epoch, 1980-01-06T00:00:00Z
+const GPS_EPOCH: i64 = 315_964_800;
+/// TAI − GPS time, in seconds
+const TAI_GPS_OFFSET: i64 = 19;
+/// TT − TAI, in milliseconds
+const TT_TAI_OFFSET_MILLIS: i64 = 32_184;
+const SECONDS_PER_WEEK: i64 = 604_800;
+
+/// A table of TAI − UTC offsets
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeapSeconds {
+    /// Unix time from which each offset applies, and the offset in seconds
+    entries: Vec<(i64, i64)>,
+    /// When the list was last updated, if it says
+    pub updated: Option<DateTime<Utc>>,
+    /// When the list stops being authoritative
+    pub expires: DateTime<Utc>,
+    /// `embedded`, or the path the list was read from
+    pub source: String,
+}
+
+impl LeapSeconds {
+    /// Parse a list in the IERS `leap-seconds.list` format
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use thedate::leap_seconds::LeapSeconds;
+    ///
+    /// let list = "#@\t3991593600\n2272060800\t10\t# 1 Jan 1972\n";
+    /// let table = LeapSeconds::parse(list, "example").unwrap();
+    /// assert_eq!(table.expires.to_string(), "2026-06-28 00:00:00 UTC");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a reason when a line is malformed, offsets do not increase,
+    /// the `#@` expiry line is missing, or a `#h` hash does not match.
+    pub fn parse(text: &str, source: &str) -> Result<Self, String> {
+        Self::parse_hashed(text, source).map(|(table, _)| table)
+    }
+
+    /// Read and parse the list at `path`, which must carry a `#h` hash
+    ///
+    /// # Errors
+    ///
+    /// Returns a reason when the file cannot be read or parsed, or has no
+    /// hash.
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        match Self::parse_hashed(&text, path)? {
+            (table, true) => Ok(table),
+            (_, false) => Err("missing the '#h' hash line; the file may be truncated".to_string()),
+        }
+    }
+
+    /// Parse a list, and say whether it had a `#h` hash to check
+    fn parse_hashed(text: &str, source: &str) -> Result<(Self, bool), String> {
+        let mut entries: Vec<(i64, i64)> = Vec::new();
+        let mut updated = None;
+        let mut expires = None;
+        let mut hash = None;
+        // What the `#h` line is a hash of, in the order it appears
+        let mut hashed = Sha1::new();
+        for (number, line) in text.lines().enumerate() {
+            let malformed = || format!("line {}: malformed '{}'", number + 1, line.trim());
+            if let Some(value) = line.strip_prefix("#$") {
+                updated = Some(ntp_instant(value).ok_or_else(malformed)?);
+                hashed.update(value.trim());
+                continue;
+            }
+            if let Some(value) = line.strip_prefix("#@") {
+                expires = Some(ntp_instant(value).ok_or_else(malformed)?);
+                hashed.update(value.trim());
+                continue;
+            }
+            if let Some(value) = line.strip_prefix("#h") {
+                hash = Some(digest(value).ok_or_else(malformed)?);
+                continue;
+            }
+            let data = line.split('#').next().unwrap_or_default();
+            let mut parts = data.split_whitespace();
+            let (Some(ntp_text), offset_text) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            let ntp: i64 = ntp_text.parse().map_err(|_| malformed())?;
+            let offset: i64 = offset_text
+                .and_then(|offset| offset.parse().ok())
+                .ok_or_else(malformed)?;
+            hashed.update(ntp_text);
+            hashed.update(offset_text.unwrap_or_default());
+            let start = ntp - NTP_UNIX_OFFSET;
+            if entries.last().is_some_and(|&(last, _)| last >= start) {
+                return Err(format!("line {}: entries are out of order", number + 1));
+            }
+            entries.push((start, offset));
+        }
+        if hash.is_some_and(|hash| hash != <[u8; 20]>::from(hashed.finalize())) {
+            return Err("the '#h' hash does not match; the file may be corrupted".to_string());
+        }
+        if entries.is_empty() {
+            return Err("no leap second entries".to_string());
+        }
+        let table = LeapSeconds {
+            entries,
+            updated,
+            expires: expires.ok_or("missing the '#@' expiry line")?,
+            source: source.to_string(),
+        };
+        Ok((table, hash.is_some()))
+    }
+
+    /// The built-in table
+    pub fn embedded() -> Self {
+        Self::parse(EMBEDDED, "embedded").expect("embedded leap second list is valid")
+    }
+
+    /// TAI − UTC in seconds at `instant`; `None` before 1972
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use thedate::leap_seconds::LeapSeconds;
+    ///
+    /// let table = LeapSeconds::embedded();
+    /// let instant = Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap();
+    /// assert_eq!(table.offset(instant), Some(37));
+    /// ```
+    pub fn offset(&self, instant: DateTime<Utc>) -> Option<i64> {
+        let seconds = instant.timestamp();
+        self.entries
+            .iter()
+            .rev()
+            .find(|&&(start, _)| start <= seconds)
+            .map(|&(_, offset)| offset)
+    }
+
+    /// Whether the table has expired at `now`
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires
+    }
+
+    /// The TAI reading at `instant`, without a zone; `None` before 1972 or
+    /// where it falls past the end of chrono's range
+    pub fn tai(&self, instant: DateTime<Utc>) -> Option<NaiveDateTime> {
+        let offset = self.offset(instant)?;
+        // Whole UTC seconds plus any leap second in the nanoseconds, so a
+        // reading of 23:59:60 lands on its own TAI second
+        let seconds = DateTime::from_timestamp(instant.timestamp().checked_add(offset)?, 0)?;
+        let nanos = Duration::nanoseconds(i64::from(instant.timestamp_subsec_nanos()));
+        seconds.naive_utc().checked_add_signed(nanos)
+    }
+
+    /// The Terrestrial Time reading at `instant`, without a zone; `None`
+    /// where [`LeapSeconds::tai`] is, or 32.184 s before the end of chrono's
+    /// range
+    pub fn tt(&self, instant: DateTime<Utc>) -> Option<NaiveDateTime> {
+        self.tai(instant)?
+            .checked_add_signed(Duration::milliseconds(TT_TAI_OFFSET_MILLIS))
+    }
+
+    /// GPS week number, and seconds into that week, at `instant`; `None`
+    /// before the GPS epoch
+    ///
+    /// The week number is the full count, not the 10- or 13-bit one
+    /// broadcast by satellites.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use thedate::leap_seconds::LeapSeconds;
+    ///
+    /// let table = LeapSeconds::embedded();
+    /// let instant = Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap();
+    /// assert_eq!(table.gps(instant), Some((2305, 475_218.0)));
+    /// ```
+    pub fn gps(&self, instant: DateTime<Utc>) -> Option<(i64, f64)> {
+        let offset = self.offset(instant)?;
+        let seconds = instant.timestamp() - GPS_EPOCH + offset - TAI_GPS_OFFSET;
+        if seconds < 0 {
+            return None;
+        }
+        let nanos = f64::from(instant.timestamp_subsec_nanos()) / 1e9;
+        Some((
+            seconds / SECONDS_PER_WEEK,
+            (seconds % SECONDS_PER_WEEK) as f64 + nanos,
+        ))
+    }
+}
+
+/// The SHA-1 digest on a `#h` line: five hexadecimal words, which IERS
+/// writes without leading zeros
+fn digest(value: &str) -> Option<[u8; 20]> {
+    let words: Vec<u32> = value
+        .split_whitespace()
+        .map(|word| u32::from_str_radix(word, 16).ok())
+        .collect::<Option<_>>()?;
+    let words: [u32; 5] = words.try_into().ok()?;
+    let mut digest = [0; 20];
+    for (bytes, word) in digest.chunks_exact_mut(4).zip(words) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    Some(digest)
+}
+
+/// An instant from a count of NTP seconds
+fn ntp_instant(value: &str) -> Option<DateTime<Utc>> {
+    let ntp: i64 = value.trim().parse().ok()?;
+    DateTime::from_timestamp(ntp - NTP_UNIX_OFFSET, 0)
+}
+
+static TABLE: OnceLock<LeapSeconds> = OnceLock::new();
+
+/// The table in use: the one [`install`]ed, or else the built-in one
+pub fn table() -> &'static LeapSeconds {
+    TABLE.get_or_init(LeapSeconds::embedded)
+}
+
+/// Use `table` from now on, unless the built-in one expires later
+///
+/// Call once at startup, before the table is first used.
+///
+/// # Errors
+///
+/// Returns `table` back when it is older than the built-in one, or when a
+/// table is already in use.
+pub fn install(table: LeapSeconds) -> Result<(), LeapSeconds> {
+    if table.expires < LeapSeconds::embedded().expires {
+        return Err(table);
+    }
+    TABLE.set(table)
+}
+
+/// [`install`] the list at `path`, as `LEAP_SECONDS_FILE` asks
+///
+/// # Errors
+///
+/// Returns a warning naming the file and why it was ignored in favour of
+/// the built-in table.
+pub fn install_file(path: &str) -> Result<(), String> {
+    let table = LeapSeconds::load(path).map_err(|reason| {
+        format!(
+            "Could not load leap seconds from {}: {}; using the built-in table",
+            path, reason
+        )
+    })?;
+    install(table).map_err(|table| {
+        format!(
+            "Leap seconds in {} expire on {}, before the built-in table; \
+             ignoring it and using the built-in table",
+            path, table.expires
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32, s: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, s).unwrap()
+    }
+
+    #[test]
+    fn test_embedded_table() {
+        let table = LeapSeconds::embedded();
+        assert_eq!(table.entries.len(), 28);
+        assert_eq!(table.offset(utc(1971, 12, 31, 23, 59, 59)), None);
+        assert_eq!(table.offset(utc(1972, 1, 1, 0, 0, 0)), Some(10));
+        assert_eq!(table.offset(utc(2016, 12, 31, 23, 59, 59)), Some(36));
+        assert_eq!(table.offset(utc(2017, 1, 1, 0, 0, 0)), Some(37));
+        assert_eq!(table.updated, Some(utc(2026, 1, 6, 11, 14, 18)));
+        assert_eq!(table.expires, utc(2026, 12, 28, 0, 0, 0));
+        assert!(table.is_stale(utc(2026, 12, 28, 0, 0, 0)));
+        assert!(!table.is_stale(utc(2026, 12, 27, 23, 59, 59)));
+    }
+
+    #[test]
+    fn test_tai_and_tt_across_a_leap_second() {
+        let table = LeapSeconds::embedded();
+        let tai = |instant| table.tai(instant).unwrap().to_string();
+        assert_eq!(tai(utc(2016, 12, 31, 23, 59, 59)), "2017-01-01 00:00:35");
+        let leap = NaiveDate::from_ymd_opt(2016, 12, 31)
+            .unwrap()
+            .and_hms_nano_opt(23, 59, 59, 1_500_000_000)
+            .unwrap()
+            .and_utc();
+        assert_eq!(tai(leap), "2017-01-01 00:00:36.500");
+        assert_eq!(tai(utc(2017, 1, 1, 0, 0, 0)), "2017-01-01 00:00:37");
+        let tt = table.tt(utc(2017, 1, 1, 0, 0, 0)).unwrap().to_string();
+        assert_eq!(tt, "2017-01-01 00:01:09.184");
+        assert_eq!(table.tai(utc(1970, 1, 1, 0, 0, 0)), None);
+    }
+
+    #[test]
+    fn test_gps_weeks() {
+        let table = LeapSeconds::embedded();
+        assert_eq!(table.gps(utc(1980, 1, 6, 0, 0, 0)), Some((0, 0.0)));
+        assert_eq!(table.gps(utc(1980, 1, 5, 23, 59, 59)), None);
+        // The first week number rollover, with GPS 13 s ahead of UTC
+        assert_eq!(table.gps(utc(1999, 8, 21, 23, 59, 47)), Some((1024, 0.0)));
+        // And the second, 18 s ahead
+        assert_eq!(table.gps(utc(2019, 4, 6, 23, 59, 42)), Some((2048, 0.0)));
+    }
+
+    #[test]
+    fn test_embedded_table_matches_its_hash() {
+        assert!(matches!(
+            LeapSeconds::parse_hashed(EMBEDDED, "embedded"),
+            Ok((_, true))
+        ));
+        let corrupted = EMBEDDED.replace("3692217600      37", "3692217600      38");
+        assert_eq!(
+            LeapSeconds::parse(&corrupted, "test"),
+            Err("the '#h' hash does not match; the file may be corrupted".to_string())
+        );
+    }
+
+    #[test]
+    fn test_load_refuses_a_truncated_file() {
+        let path = std::env::temp_dir().join(format!("thedate-truncated-{}", std::process::id()));
+        let truncated: String = EMBEDDED
+            .lines()
+            .take(100)
+            .map(|line| line.to_string() + "\n")
+            .collect();
+        std::fs::write(&path, truncated).unwrap();
+        let loaded = LeapSeconds::load(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            loaded,
+            Err("missing the '#h' hash line; the file may be truncated".to_string())
+        );
+    }
+
+    /// A list of one entry expiring at `expires`, with its `#h` hash
+    fn signed_list(expires: i64) -> String {
+        let hash = Sha1::digest(format!("{}227206080010", expires));
+        let words: Vec<String> = hash
+            .chunks_exact(4)
+            .map(|word| format!("{:x}", u32::from_be_bytes(word.try_into().unwrap())))
+            .collect();
+        format!("#@\t{}\n2272060800\t10\n#h\t{}\n", expires, words.join(" "))
+    }
+
+    #[test]
+    fn test_install_file_keeps_the_newer_built_in_table() {
+        let path = std::env::temp_dir().join(format!("thedate-older-{}", std::process::id()));
+        let path = path.to_str().unwrap();
+        // Expiring 2026-06-28, half a year before the built-in list
+        std::fs::write(path, signed_list(3_991_593_600)).unwrap();
+        assert!(LeapSeconds::load(path).is_ok());
+        let installed = install_file(path);
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(
+            installed,
+            Err(format!(
+                "Leap seconds in {} expire on 2026-06-28 00:00:00 UTC, before the built-in \
+                 table; ignoring it and using the built-in table",
+                path
+            ))
+        );
+        assert_eq!(table().source, "embedded");
+    }
+
+    #[test]
+    fn test_install_file_reports_unreadable_files() {
+        let installed = install_file("/nonexistent/leap-seconds.list");
+        assert!(installed
+            .unwrap_err()
+            .ends_with("; using the built-in table"));
+    }
+
+    #[test]
+    fn test_digest_words_may_drop_leading_zeros() {
+        let bytes = digest("1e27c8a 0 ffffffff 10 a").unwrap();
+        assert_eq!(bytes[..4], [0x01, 0xe2, 0x7c, 0x8a]);
+        assert_eq!(bytes[16..], [0, 0, 0, 0x0a]);
+        assert_eq!(digest("1 2 3 4"), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_lists() {
+        assert!(LeapSeconds::parse("2272060800\t10\n", "test").is_err());
+        assert!(LeapSeconds::parse("#@\t3991593600\n", "test").is_err());
+        let out_of_order = "#@\t3991593600\n2287785600\t11\n2272060800\t10\n";
+        assert!(LeapSeconds::parse(out_of_order, "test").is_err());
+        let malformed = "#@\t3991593600\n2272060800\tten\n";
+        assert_eq!(
+            LeapSeconds::parse(malformed, "test"),
+            Err("line 2: malformed '2272060800\tten'".to_string())
+        );
+    }
+}
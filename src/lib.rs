@@ -16,6 +16,75 @@
 //! # Get current timestamp in all formats
 //! curl http://localhost:8080/
 //!
+//! # Render in an IANA time zone or a fixed offset
+//! curl 'http://localhost:8080/?tz=America/New_York'
+//! curl 'http://localhost:8080/?tz=%2B05:30'
+//!
+//! # Render any instant (Unix seconds, @<n><unit>, or RFC 3339)
+//! curl http://localhost:8080/at/1710504045
+//! curl http://localhost:8080/at/@1710504045123ms
+//! curl http://localhost:8080/at/2024-03-15T12:30:45Z
+//!
+//! # Describe an instant relative to another ("3 hours ago")
+//! curl 'http://localhost:8080/at/1710493200?relative_to=2024-03-15T12:00:00Z'
+//!
+//! # Localize month and weekday names (or send Accept-Language)
+//! curl 'http://localhost:8080/?locale=de-DE'
+//!
+//! # Week numbers under a region's rule (first day of week, minimal days)
+//! curl 'http://localhost:8080/?week_rule=AE'
+//!
+//! # Native digits in string fields (integers stay numeric)
+//! curl 'http://localhost:8080/?numerals=arabext'
+//! curl http://localhost:8080/at/۱۷۱۰۵۰۴۰۴۵
+//!
+//! # Speakable phrases ("just after half past twelve in the afternoon")
+//! curl 'http://localhost:8080/?spoken=true&fields=military_time'
+//!
+//! # Dates in other calendars alongside the Gregorian fields, and back again
+//! curl 'http://localhost:8080/?calendars=hebrew,islamic'
+//! curl 'http://localhost:8080/at/persian:1403-01-01?calendars=persian,ethiopic'
+//! curl 'http://localhost:8080/at/chinese:2023-02L-15?calendars=chinese&locale=zh'
+//! curl 'http://localhost:8080/at/historical-gb:1752-09-02?calendars=julian,historical-gb'
+//!
+//! # Years before 1 CE or after 9999, with reasons for formats that are null
+//! curl 'http://localhost:8080/at/-0043-03-15?fields=full_iso,era,year_of_era,yyyymmdd'
+//!
+//! # Julian Date, sidereal time, and local sidereal time at a longitude
+//! curl 'http://localhost:8080/?fields=julian_date,greenwich_mean_sidereal_time&longitude=-77.07'
+//!
+//! # TAI, Terrestrial Time and GPS week, from the leap second table
+//! curl 'http://localhost:8080/?fields=tai,terrestrial_time,tai_utc_offset,gps_week,gps_time_of_week'
+//!
+//! # Japanese era and ROC years, and era dates read back
+//! curl 'http://localhost:8080/at/R6.03.15?fields=japanese_era_kanji,roc_year_chinese'
+//!
+//! # Choose an encoding: JSON (default), CSV, XML, YAML or key=value text
+//! curl -H 'Accept: text/csv' http://localhost:8080/
+//! curl 'http://localhost:8080/?format=yaml'
+//!
+//! # Print a single field as plain text
+//! curl http://localhost:8080/f/yyyymmdd
+//!
+//! # Render a custom strftime pattern
+//! curl 'http://localhost:8080/format?pattern=%25Y-%25m-%25dT%25H'
+//!
+//! # Convert a wall-clock time between zones (DST gaps and overlaps reported)
+//! curl 'http://localhost:8080/convert?from=Europe/Berlin&to=Asia/Tokyo&time=2024-03-31T02:30'
+//!
+//! # Difference between two instants, with an ISO 8601 duration
+//! curl 'http://localhost:8080/diff?start=2023-01-01T00:00:00Z&end=2024-03-04T04:00:00Z'
+//!
+//! # Calendar-aware arithmetic (month ends clamp by default)
+//! curl 'http://localhost:8080/add?instant=2024-01-31&months=1&days=-3'
+//! curl 'http://localhost:8080/add?instant=2024-03-30T12:00&tz=Europe/Berlin&duration=P1D&clock=absolute'
+//!
+//! # Detect the format of a messy timestamp, listing every reading
+//! curl -X POST --data '03/04/24 5pm' http://localhost:8080/parse
+//!
+//! # Resolve an English relative expression
+//! curl 'http://localhost:8080/resolve?q=next+friday+at+9am&tz=Europe/London'
+//!
 //! # Check service health
 //! curl http://localhost:8080/health
 //! ```
@@ -26,9 +95,32 @@
 //! - `HOST`: Bind address (default: 0.0.0.0)
 //! - `PORT`: Bind port (default: 8080)
 //! - `RUST_LOG`: Log level (default: info)
+//! - `LEAP_SECONDS_FILE`: Newer IERS `leap-seconds.list` (default: built in)
 
+pub mod arithmetic;
+pub mod astronomy;
+pub mod calendar;
 pub mod config;
+pub mod convert;
+pub mod duration;
+pub mod era;
+pub mod error;
 pub mod handlers;
+pub mod humanize;
+pub mod instant;
+pub mod leap_seconds;
+pub mod locale;
+pub mod natural;
+pub mod numerals;
+pub mod output;
+pub mod parse;
+pub mod pattern;
+pub mod spoken;
 pub mod timestamp;
+pub mod week;
+pub mod zone;
 
-pub use handlers::{health_check, home};
+pub use handlers::{
+    add, at_instant, convert_time, diff, field_value, format_pattern, health_check, home,
+    parse_timestamp, query_config, resolve_expression,
+};
@@ -0,0 +1,1042 @@
+//! Localized month and weekday names and date orders.
+//!
+//! The tables follow CLDR: `long_month` and `short_month` use the standalone
+//! forms, while `locale_date` and `verbose_date` use the format forms (which
+//! differ in Polish and Russian, where a month inside a date is genitive).
+//! `en-US` is the default and reproduces the unlocalized output exactly.
+
+use std::fmt::Display;
+
+use actix_web::{http::header, HttpRequest};
+use chrono::{DateTime, Datelike, TimeZone};
+use serde_json::{Map, Value};
+
+use crate::error::ApiError;
+
+/// Names and date patterns for one language
+///
+/// Weekdays start on Monday. Date patterns are chrono strftime patterns in
+/// which `%B`, `%b`, `%A` and `%a` are replaced by the localized names.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Locale {
+    /// BCP 47 tag reported in `Content-Language`
+    pub tag: &'static str,
+    months: [&'static str; 12],
+    date_months: [&'static str; 12],
+    short_months: [&'static str; 12],
+    weekdays: [&'static str; 7],
+    short_weekdays: [&'static str; 7],
+    short_date: &'static str,
+    long_date: &'static str,
+}
+
+const EN_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const EN_SHORT_MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+const EN_WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+const EN_SHORT_WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+const DE_MONTHS: [&str; 12] = [
+    "Januar",
+    "Februar",
+    "März",
+    "April",
+    "Mai",
+    "Juni",
+    "Juli",
+    "August",
+    "September",
+    "Oktober",
+    "November",
+    "Dezember",
+];
+const FR_MONTHS: [&str; 12] = [
+    "janvier",
+    "février",
+    "mars",
+    "avril",
+    "mai",
+    "juin",
+    "juillet",
+    "août",
+    "septembre",
+    "octobre",
+    "novembre",
+    "décembre",
+];
+const ES_MONTHS: [&str; 12] = [
+    "enero",
+    "febrero",
+    "marzo",
+    "abril",
+    "mayo",
+    "junio",
+    "julio",
+    "agosto",
+    "septiembre",
+    "octubre",
+    "noviembre",
+    "diciembre",
+];
+const IT_MONTHS: [&str; 12] = [
+    "gennaio",
+    "febbraio",
+    "marzo",
+    "aprile",
+    "maggio",
+    "giugno",
+    "luglio",
+    "agosto",
+    "settembre",
+    "ottobre",
+    "novembre",
+    "dicembre",
+];
+const PT_MONTHS: [&str; 12] = [
+    "janeiro",
+    "fevereiro",
+    "março",
+    "abril",
+    "maio",
+    "junho",
+    "julho",
+    "agosto",
+    "setembro",
+    "outubro",
+    "novembro",
+    "dezembro",
+];
+const NL_MONTHS: [&str; 12] = [
+    "januari",
+    "februari",
+    "maart",
+    "april",
+    "mei",
+    "juni",
+    "juli",
+    "augustus",
+    "september",
+    "oktober",
+    "november",
+    "december",
+];
+const SV_MONTHS: [&str; 12] = [
+    "januari",
+    "februari",
+    "mars",
+    "april",
+    "maj",
+    "juni",
+    "juli",
+    "augusti",
+    "september",
+    "oktober",
+    "november",
+    "december",
+];
+const TR_MONTHS: [&str; 12] = [
+    "Ocak", "Şubat", "Mart", "Nisan", "Mayıs", "Haziran", "Temmuz", "Ağustos", "Eylül", "Ekim",
+    "Kasım", "Aralık",
+];
+const ID_MONTHS: [&str; 12] = [
+    "Januari",
+    "Februari",
+    "Maret",
+    "April",
+    "Mei",
+    "Juni",
+    "Juli",
+    "Agustus",
+    "September",
+    "Oktober",
+    "November",
+    "Desember",
+];
+const HI_MONTHS: [&str; 12] = [
+    "जनवरी",
+    "फ़रवरी",
+    "मार्च",
+    "अप्रैल",
+    "मई",
+    "जून",
+    "जुलाई",
+    "अगस्त",
+    "सितंबर",
+    "अक्तूबर",
+    "नवंबर",
+    "दिसंबर",
+];
+const JA_MONTHS: [&str; 12] = [
+    "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+];
+const KO_MONTHS: [&str; 12] = [
+    "1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월",
+];
+const VI_MONTHS: [&str; 12] = [
+    "tháng 1",
+    "tháng 2",
+    "tháng 3",
+    "tháng 4",
+    "tháng 5",
+    "tháng 6",
+    "tháng 7",
+    "tháng 8",
+    "tháng 9",
+    "tháng 10",
+    "tháng 11",
+    "tháng 12",
+];
+
+const FA_MONTHS: [&str; 12] = [
+    "ژانویه",
+    "فوریه",
+    "مارس",
+    "آوریل",
+    "مه",
+    "ژوئن",
+    "ژوئیه",
+    "اوت",
+    "سپتامبر",
+    "اکتبر",
+    "نوامبر",
+    "دسامبر",
+];
+const FA_WEEKDAYS: [&str; 7] = [
+    "دوشنبه",
+    "سه\u{200c}شنبه",
+    "چهارشنبه",
+    "پنجشنبه",
+    "جمعه",
+    "شنبه",
+    "یکشنبه",
+];
+const TH_MONTHS: [&str; 12] = [
+    "มกราคม",
+    "กุมภาพันธ์",
+    "มีนาคม",
+    "เมษายน",
+    "พฤษภาคม",
+    "มิถุนายน",
+    "กรกฎาคม",
+    "สิงหาคม",
+    "กันยายน",
+    "ตุลาคม",
+    "พฤศจิกายน",
+    "ธันวาคม",
+];
+const AM_MONTHS: [&str; 12] = [
+    "ጃንዩወሪ",
+    "ፌብሩወሪ",
+    "ማርች",
+    "ኤፕሪል",
+    "ሜይ",
+    "ጁን",
+    "ጁላይ",
+    "ኦገስት",
+    "ሴፕቴምበር",
+    "ኦክቶበር",
+    "ኖቬምበር",
+    "ዲሴምበር",
+];
+
+/// Every supported locale; the first is the default
+pub static LOCALES: &[Locale] = &[
+    Locale {
+        tag: "en-US",
+        months: EN_MONTHS,
+        date_months: EN_MONTHS,
+        short_months: EN_SHORT_MONTHS,
+        weekdays: EN_WEEKDAYS,
+        short_weekdays: EN_SHORT_WEEKDAYS,
+        short_date: "%m/%d/%y",
+        long_date: "%e-%b-%Y",
+    },
+    Locale {
+        tag: "en-GB",
+        months: EN_MONTHS,
+        date_months: EN_MONTHS,
+        short_months: EN_SHORT_MONTHS,
+        weekdays: EN_WEEKDAYS,
+        short_weekdays: EN_SHORT_WEEKDAYS,
+        short_date: "%d/%m/%Y",
+        long_date: "%-d %B %Y",
+    },
+    Locale {
+        tag: "de",
+        months: DE_MONTHS,
+        date_months: DE_MONTHS,
+        short_months: [
+            "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+        ],
+        weekdays: [
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+            "Sonntag",
+        ],
+        short_weekdays: ["Mo.", "Di.", "Mi.", "Do.", "Fr.", "Sa.", "So."],
+        short_date: "%d.%m.%y",
+        long_date: "%-d. %B %Y",
+    },
+    Locale {
+        tag: "fr",
+        months: FR_MONTHS,
+        date_months: FR_MONTHS,
+        short_months: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        weekdays: [
+            "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+        ],
+        short_weekdays: ["lun.", "mar.", "mer.", "jeu.", "ven.", "sam.", "dim."],
+        short_date: "%d/%m/%Y",
+        long_date: "%-d %B %Y",
+    },
+    Locale {
+        tag: "es",
+        months: ES_MONTHS,
+        date_months: ES_MONTHS,
+        short_months: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+        ],
+        weekdays: [
+            "lunes",
+            "martes",
+            "miércoles",
+            "jueves",
+            "viernes",
+            "sábado",
+            "domingo",
+        ],
+        short_weekdays: ["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+        short_date: "%-d/%-m/%y",
+        long_date: "%-d de %B de %Y",
+    },
+    Locale {
+        tag: "it",
+        months: IT_MONTHS,
+        date_months: IT_MONTHS,
+        short_months: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        weekdays: [
+            "lunedì",
+            "martedì",
+            "mercoledì",
+            "giovedì",
+            "venerdì",
+            "sabato",
+            "domenica",
+        ],
+        short_weekdays: ["lun", "mar", "mer", "gio", "ven", "sab", "dom"],
+        short_date: "%d/%m/%y",
+        long_date: "%-d %B %Y",
+    },
+    Locale {
+        tag: "pt",
+        months: PT_MONTHS,
+        date_months: PT_MONTHS,
+        short_months: [
+            "jan.", "fev.", "mar.", "abr.", "mai.", "jun.", "jul.", "ago.", "set.", "out.", "nov.",
+            "dez.",
+        ],
+        weekdays: [
+            "segunda-feira",
+            "terça-feira",
+            "quarta-feira",
+            "quinta-feira",
+            "sexta-feira",
+            "sábado",
+            "domingo",
+        ],
+        short_weekdays: ["seg.", "ter.", "qua.", "qui.", "sex.", "sáb.", "dom."],
+        short_date: "%d/%m/%Y",
+        long_date: "%-d de %B de %Y",
+    },
+    Locale {
+        tag: "nl",
+        months: NL_MONTHS,
+        date_months: NL_MONTHS,
+        short_months: [
+            "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+        ],
+        weekdays: [
+            "maandag",
+            "dinsdag",
+            "woensdag",
+            "donderdag",
+            "vrijdag",
+            "zaterdag",
+            "zondag",
+        ],
+        short_weekdays: ["ma", "di", "wo", "do", "vr", "za", "zo"],
+        short_date: "%d-%m-%Y",
+        long_date: "%-d %B %Y",
+    },
+    Locale {
+        tag: "pl",
+        months: [
+            "styczeń",
+            "luty",
+            "marzec",
+            "kwiecień",
+            "maj",
+            "czerwiec",
+            "lipiec",
+            "sierpień",
+            "wrzesień",
+            "październik",
+            "listopad",
+            "grudzień",
+        ],
+        date_months: [
+            "stycznia",
+            "lutego",
+            "marca",
+            "kwietnia",
+            "maja",
+            "czerwca",
+            "lipca",
+            "sierpnia",
+            "września",
+            "października",
+            "listopada",
+            "grudnia",
+        ],
+        short_months: [
+            "sty", "lut", "mar", "kwi", "maj", "cze", "lip", "sie", "wrz", "paź", "lis", "gru",
+        ],
+        weekdays: [
+            "poniedziałek",
+            "wtorek",
+            "środa",
+            "czwartek",
+            "piątek",
+            "sobota",
+            "niedziela",
+        ],
+        short_weekdays: ["pon.", "wt.", "śr.", "czw.", "pt.", "sob.", "niedz."],
+        short_date: "%d.%m.%Y",
+        long_date: "%-d %B %Y",
+    },
+    Locale {
+        tag: "sv",
+        months: SV_MONTHS,
+        date_months: SV_MONTHS,
+        short_months: [
+            "jan.", "feb.", "mars", "apr.", "maj", "juni", "juli", "aug.", "sep.", "okt.", "nov.",
+            "dec.",
+        ],
+        weekdays: [
+            "måndag", "tisdag", "onsdag", "torsdag", "fredag", "lördag", "söndag",
+        ],
+        short_weekdays: ["mån", "tis", "ons", "tors", "fre", "lör", "sön"],
+        short_date: "%Y-%m-%d",
+        long_date: "%-d %B %Y",
+    },
+    Locale {
+        tag: "ru",
+        months: [
+            "январь",
+            "февраль",
+            "март",
+            "апрель",
+            "май",
+            "июнь",
+            "июль",
+            "август",
+            "сентябрь",
+            "октябрь",
+            "ноябрь",
+            "декабрь",
+        ],
+        date_months: [
+            "января",
+            "февраля",
+            "марта",
+            "апреля",
+            "мая",
+            "июня",
+            "июля",
+            "августа",
+            "сентября",
+            "октября",
+            "ноября",
+            "декабря",
+        ],
+        short_months: [
+            "янв.",
+            "февр.",
+            "март",
+            "апр.",
+            "май",
+            "июнь",
+            "июль",
+            "авг.",
+            "сент.",
+            "окт.",
+            "нояб.",
+            "дек.",
+        ],
+        weekdays: [
+            "понедельник",
+            "вторник",
+            "среда",
+            "четверг",
+            "пятница",
+            "суббота",
+            "воскресенье",
+        ],
+        short_weekdays: ["пн", "вт", "ср", "чт", "пт", "сб", "вс"],
+        short_date: "%d.%m.%Y",
+        long_date: "%-d %B %Y г.",
+    },
+    Locale {
+        tag: "tr",
+        months: TR_MONTHS,
+        date_months: TR_MONTHS,
+        short_months: [
+            "Oca", "Şub", "Mar", "Nis", "May", "Haz", "Tem", "Ağu", "Eyl", "Eki", "Kas", "Ara",
+        ],
+        weekdays: [
+            "Pazartesi",
+            "Salı",
+            "Çarşamba",
+            "Perşembe",
+            "Cuma",
+            "Cumartesi",
+            "Pazar",
+        ],
+        short_weekdays: ["Pzt", "Sal", "Çar", "Per", "Cum", "Cmt", "Paz"],
+        short_date: "%d.%m.%Y",
+        long_date: "%-d %B %Y",
+    },
+    Locale {
+        tag: "ja",
+        months: JA_MONTHS,
+        date_months: JA_MONTHS,
+        short_months: JA_MONTHS,
+        weekdays: [
+            "月曜日",
+            "火曜日",
+            "水曜日",
+            "木曜日",
+            "金曜日",
+            "土曜日",
+            "日曜日",
+        ],
+        short_weekdays: ["月", "火", "水", "木", "金", "土", "日"],
+        short_date: "%Y/%m/%d",
+        long_date: "%Y年%-m月%-d日",
+    },
+    Locale {
+        tag: "zh",
+        months: [
+            "一月",
+            "二月",
+            "三月",
+            "四月",
+            "五月",
+            "六月",
+            "七月",
+            "八月",
+            "九月",
+            "十月",
+            "十一月",
+            "十二月",
+        ],
+        date_months: JA_MONTHS,
+        short_months: JA_MONTHS,
+        weekdays: [
+            "星期一",
+            "星期二",
+            "星期三",
+            "星期四",
+            "星期五",
+            "星期六",
+            "星期日",
+        ],
+        short_weekdays: ["周一", "周二", "周三", "周四", "周五", "周六", "周日"],
+        short_date: "%Y/%-m/%-d",
+        long_date: "%Y年%-m月%-d日",
+    },
+    Locale {
+        tag: "ko",
+        months: KO_MONTHS,
+        date_months: KO_MONTHS,
+        short_months: KO_MONTHS,
+        weekdays: [
+            "월요일",
+            "화요일",
+            "수요일",
+            "목요일",
+            "금요일",
+            "토요일",
+            "일요일",
+        ],
+        short_weekdays: ["월", "화", "수", "목", "금", "토", "일"],
+        short_date: "%y. %-m. %-d.",
+        long_date: "%Y년 %-m월 %-d일",
+    },
+    Locale {
+        tag: "hi",
+        months: HI_MONTHS,
+        date_months: HI_MONTHS,
+        short_months: [
+            "जन॰",
+            "फ़र॰",
+            "मार्च",
+            "अप्रैल",
+            "मई",
+            "जून",
+            "जुल॰",
+            "अग॰",
+            "सित॰",
+            "अक्तू॰",
+            "नव॰",
+            "दिस॰",
+        ],
+        weekdays: [
+            "सोमवार",
+            "मंगलवार",
+            "बुधवार",
+            "गुरुवार",
+            "शुक्रवार",
+            "शनिवार",
+            "रविवार",
+        ],
+        short_weekdays: ["सोम", "मंगल", "बुध", "गुरु", "शुक्र", "शनि", "रवि"],
+        short_date: "%-d/%-m/%y",
+        long_date: "%-d %B %Y",
+    },
+    Locale {
+        tag: "vi",
+        months: VI_MONTHS,
+        date_months: VI_MONTHS,
+        short_months: [
+            "thg 1", "thg 2", "thg 3", "thg 4", "thg 5", "thg 6", "thg 7", "thg 8", "thg 9",
+            "thg 10", "thg 11", "thg 12",
+        ],
+        weekdays: [
+            "Thứ Hai",
+            "Thứ Ba",
+            "Thứ Tư",
+            "Thứ Năm",
+            "Thứ Sáu",
+            "Thứ Bảy",
+            "Chủ Nhật",
+        ],
+        short_weekdays: ["Th 2", "Th 3", "Th 4", "Th 5", "Th 6", "Th 7", "CN"],
+        short_date: "%d/%m/%Y",
+        long_date: "%-d %B, %Y",
+    },
+    Locale {
+        tag: "id",
+        months: ID_MONTHS,
+        date_months: ID_MONTHS,
+        short_months: [
+            "Jan", "Feb", "Mar", "Apr", "Mei", "Jun", "Jul", "Agu", "Sep", "Okt", "Nov", "Des",
+        ],
+        weekdays: [
+            "Senin", "Selasa", "Rabu", "Kamis", "Jumat", "Sabtu", "Minggu",
+        ],
+        short_weekdays: ["Sen", "Sel", "Rab", "Kam", "Jum", "Sab", "Min"],
+        short_date: "%d/%m/%y",
+        long_date: "%-d %B %Y",
+    },
+    Locale {
+        tag: "fa",
+        months: FA_MONTHS,
+        date_months: FA_MONTHS,
+        short_months: FA_MONTHS,
+        weekdays: FA_WEEKDAYS,
+        short_weekdays: FA_WEEKDAYS,
+        short_date: "%Y/%-m/%-d",
+        long_date: "%-d %B %Y",
+    },
+    Locale {
+        tag: "th",
+        months: TH_MONTHS,
+        date_months: TH_MONTHS,
+        short_months: [
+            "ม.ค.",
+            "ก.พ.",
+            "มี.ค.",
+            "เม.ย.",
+            "พ.ค.",
+            "มิ.ย.",
+            "ก.ค.",
+            "ส.ค.",
+            "ก.ย.",
+            "ต.ค.",
+            "พ.ย.",
+            "ธ.ค.",
+        ],
+        weekdays: [
+            "วันจันทร์",
+            "วันอังคาร",
+            "วันพุธ",
+            "วันพฤหัสบดี",
+            "วันศุกร์",
+            "วันเสาร์",
+            "วันอาทิตย์",
+        ],
+        short_weekdays: ["จ.", "อ.", "พ.", "พฤ.", "ศ.", "ส.", "อา."],
+        short_date: "%-d/%-m/%y",
+        long_date: "%-d %B %Y",
+    },
+    Locale {
+        tag: "am",
+        months: AM_MONTHS,
+        date_months: AM_MONTHS,
+        short_months: [
+            "ጃንዩ",
+            "ፌብሩ",
+            "ማርች",
+            "ኤፕሪ",
+            "ሜይ",
+            "ጁን",
+            "ጁላይ",
+            "ኦገስ",
+            "ሴፕቴ",
+            "ኦክቶ",
+            "ኖቬም",
+            "ዲሴም",
+        ],
+        weekdays: ["ሰኞ", "ማክሰኞ", "ረቡዕ", "ሐሙስ", "ዓርብ", "ቅዳሜ", "እሑድ"],
+        short_weekdays: ["ሰኞ", "ማክሰ", "ረቡዕ", "ሐሙስ", "ዓርብ", "ቅዳሜ", "እሑድ"],
+        short_date: "%d/%m/%Y",
+        long_date: "%-d %B %Y",
+    },
+];
+
+impl Default for &'static Locale {
+    fn default() -> Self {
+        &LOCALES[0]
+    }
+}
+
+impl Locale {
+    /// Language subtag of this locale's tag, such as `en` for `en-US`
+    pub fn language(&self) -> &'static str {
+        self.tag.split('-').next().unwrap_or_default()
+    }
+
+    /// Standalone name of `month` (1-12) in this locale
+    pub fn month_name(&self, month: u32) -> &'static str {
+        self.months[month as usize - 1]
+    }
+
+    /// Tags of every supported locale
+    pub fn tags() -> Vec<&'static str> {
+        LOCALES.iter().map(|locale| locale.tag).collect()
+    }
+
+    /// Look up a locale by BCP 47 tag, falling back to its language
+    ///
+    /// Matching ignores case and accepts `_` as a separator, so `de-DE`,
+    /// `de_at` and `DE` all select German, and `en` selects `en-US`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use thedate::locale::Locale;
+    ///
+    /// assert_eq!(Locale::find("de-DE").unwrap().tag, "de");
+    /// assert_eq!(Locale::find("en_gb").unwrap().tag, "en-GB");
+    /// assert!(Locale::find("tlh").is_none());
+    /// ```
+    pub fn find(tag: &str) -> Option<&'static Locale> {
+        let tag = tag.trim().replace('_', "-");
+        let language = tag.split('-').next().unwrap_or_default();
+        LOCALES
+            .iter()
+            .find(|locale| locale.tag.eq_ignore_ascii_case(&tag))
+            .or_else(|| {
+                LOCALES.iter().find(|locale| {
+                    let own = locale.tag.split('-').next().unwrap_or_default();
+                    own.eq_ignore_ascii_case(language)
+                })
+            })
+    }
+
+    /// Parse an explicit `locale` parameter
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ApiError::UnsupportedLocale`] when no locale matches.
+    pub fn parse(tag: &str) -> Result<&'static Locale, ApiError> {
+        Self::find(tag).ok_or_else(|| ApiError::UnsupportedLocale(tag.to_string()))
+    }
+
+    /// Choose a locale from `?locale=`, then `Accept-Language`, then the default
+    ///
+    /// Accept-Language ranges are tried by descending quality; unsupported
+    /// ranges are skipped rather than rejected, since the header is only a
+    /// preference.
+    pub fn negotiate(
+        req: &HttpRequest,
+        requested: Option<&str>,
+    ) -> Result<&'static Locale, ApiError> {
+        if let Some(tag) = requested {
+            return Self::parse(tag);
+        }
+        let Some(accept) = req
+            .headers()
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+        else {
+            return Ok(Default::default());
+        };
+        let mut ranges: Vec<(&str, f32)> = accept
+            .split(',')
+            .filter_map(|range| {
+                let mut parts = range.split(';');
+                let tag = parts.next()?.trim();
+                let quality = parts
+                    .find_map(|p| p.trim().strip_prefix("q="))
+                    .map_or(Some(1.0), |q| q.trim().parse().ok())?;
+                (quality > 0.0 && tag != "*").then_some((tag, quality))
+            })
+            .collect();
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Ok(ranges
+            .into_iter()
+            .find_map(|(tag, _)| Self::find(tag))
+            .unwrap_or_default())
+    }
+
+    /// Render a date pattern, substituting localized names first
+    fn format<Tz: TimeZone>(&self, dt: &DateTime<Tz>, pattern: &str) -> String
+    where
+        Tz::Offset: Display,
+    {
+        let month = dt.month0() as usize;
+        let weekday = dt.weekday().num_days_from_monday() as usize;
+        let pattern = pattern
+            .replace("%B", self.date_months[month])
+            .replace("%b", self.short_months[month])
+            .replace("%A", self.weekdays[weekday])
+            .replace("%a", self.short_weekdays[weekday]);
+        dt.format(&pattern).to_string()
+    }
+
+    /// Replace the language-dependent fields present in `map`
+    ///
+    /// Only `short_month`, `long_month`, `weekday`, `abbrev_weekday`,
+    /// `locale_date` and `verbose_date` are touched, and only when selected
+    /// and not null.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use thedate::locale::Locale;
+    /// use thedate::timestamp::{from_chrono_fields, Field};
+    ///
+    /// let dt = Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap();
+    /// let mut map = from_chrono_fields(&dt, &[Field::long_month, Field::verbose_date]);
+    /// Locale::find("de").unwrap().localize(&dt, &mut map);
+    /// assert_eq!(map["long_month"], "März");
+    /// assert_eq!(map["verbose_date"], "15. März 2024");
+    /// ```
+    pub fn localize<Tz: TimeZone>(&self, dt: &DateTime<Tz>, map: &mut Map<String, Value>)
+    where
+        Tz::Offset: Display,
+    {
+        let month = dt.month0() as usize;
+        let weekday = dt.weekday().num_days_from_monday() as usize;
+        for (key, value) in map.iter_mut() {
+            if value.is_null() {
+                continue;
+            }
+            let localized = match key.as_str() {
+                "short_month" => self.short_months[month].to_string(),
+                "long_month" => self.months[month].to_string(),
+                "weekday" => self.weekdays[weekday].to_string(),
+                "abbrev_weekday" => self.short_weekdays[weekday].to_string(),
+                "locale_date" => self.format(dt, self.short_date),
+                "verbose_date" => self.format(dt, self.long_date),
+                _ => continue,
+            };
+            *value = Value::String(localized);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timestamp::{from_chrono_fields, Field};
+    use actix_web::test::TestRequest;
+    use chrono::Utc;
+
+    const LOCALIZED: [Field; 6] = [
+        Field::short_month,
+        Field::long_month,
+        Field::weekday,
+        Field::abbrev_weekday,
+        Field::locale_date,
+        Field::verbose_date,
+    ];
+
+    fn localized(tag: &str) -> Vec<String> {
+        let dt = Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap();
+        let mut map = from_chrono_fields(&dt, &LOCALIZED);
+        Locale::find(tag).unwrap().localize(&dt, &mut map);
+        map.values()
+            .map(|v| v.as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_default_matches_unlocalized_output() {
+        let dt = Utc.with_ymd_and_hms(2024, 3, 5, 12, 0, 0).unwrap();
+        let plain = from_chrono_fields(&dt, Field::ALL);
+        let mut map = plain.clone();
+        <&Locale>::default().localize(&dt, &mut map);
+        assert_eq!(map, plain);
+    }
+
+    #[test]
+    fn test_european_locales() {
+        assert_eq!(
+            localized("de-DE"),
+            ["Mär", "März", "Freitag", "Fr.", "15.03.24", "15. März 2024"]
+        );
+        assert_eq!(
+            localized("fr-FR"),
+            [
+                "mars",
+                "mars",
+                "vendredi",
+                "ven.",
+                "15/03/2024",
+                "15 mars 2024"
+            ]
+        );
+        assert_eq!(
+            localized("es"),
+            [
+                "mar",
+                "marzo",
+                "viernes",
+                "vie",
+                "15/3/24",
+                "15 de marzo de 2024"
+            ]
+        );
+        assert_eq!(
+            localized("en-GB"),
+            [
+                "Mar",
+                "March",
+                "Friday",
+                "Fri",
+                "15/03/2024",
+                "15 March 2024"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_genitive_months_in_dates() {
+        assert_eq!(localized("pl")[1], "marzec");
+        assert_eq!(localized("pl")[5], "15 marca 2024");
+        assert_eq!(localized("ru")[1], "март");
+        assert_eq!(localized("ru")[5], "15 марта 2024 г.");
+    }
+
+    #[test]
+    fn test_asian_locales() {
+        assert_eq!(
+            localized("ja-JP"),
+            ["3月", "3月", "金曜日", "金", "2024/03/15", "2024年3月15日"]
+        );
+        assert_eq!(
+            localized("zh-CN"),
+            [
+                "3月",
+                "三月",
+                "星期五",
+                "周五",
+                "2024/3/15",
+                "2024年3月15日"
+            ]
+        );
+        assert_eq!(
+            localized("ko"),
+            [
+                "3월",
+                "3월",
+                "금요일",
+                "금",
+                "24. 3. 15.",
+                "2024년 3월 15일"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_every_locale_has_distinct_names() {
+        for locale in LOCALES {
+            for names in [&locale.months[..], &locale.weekdays[..]] {
+                let mut unique = names.to_vec();
+                unique.sort();
+                unique.dedup();
+                assert_eq!(unique.len(), names.len(), "{}", locale.tag);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unsupported_locale() {
+        assert!(matches!(
+            Locale::parse("tlh-QO"),
+            Err(ApiError::UnsupportedLocale(tag)) if tag == "tlh-QO"
+        ));
+    }
+
+    #[test]
+    fn test_accept_language_by_quality() {
+        let req = TestRequest::default()
+            .insert_header((header::ACCEPT_LANGUAGE, "tlh, fr;q=0.5, ja;q=0.8, *;q=0.1"))
+            .to_http_request();
+        assert_eq!(Locale::negotiate(&req, None).unwrap().tag, "ja");
+        assert_eq!(Locale::negotiate(&req, Some("de")).unwrap().tag, "de");
+    }
+
+    #[test]
+    fn test_accept_language_unsupported_falls_back() {
+        let req = TestRequest::default()
+            .insert_header((header::ACCEPT_LANGUAGE, "tlh, en;q=0"))
+            .to_http_request();
+        assert_eq!(Locale::negotiate(&req, None).unwrap().tag, "en-US");
+    }
+}
@@ -1,5 +1,9 @@
 use actix_web::{middleware::Logger, web, App, HttpServer};
-use thedate::{config::Config, health_check, home};
+use thedate::leap_seconds;
+use thedate::{
+    add, at_instant, config::Config, convert_time, diff, field_value, format_pattern, health_check,
+    home, parse_timestamp, query_config, resolve_expression,
+};
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -9,12 +13,36 @@ async fn main() -> std::io::Result<()> {
     let config = Config::from_env();
     let bind_addr = config.bind_address();
 
+    if let Some(path) = &config.leap_seconds_file {
+        match leap_seconds::install_file(path) {
+            Ok(()) => log::info!("Loaded leap seconds from {}", path),
+            Err(warning) => log::warn!("{}", warning),
+        }
+    }
+    let table = leap_seconds::table();
+    if table.is_stale(chrono::Utc::now()) {
+        log::warn!(
+            "Leap second table from {} expired on {}",
+            table.source,
+            table.expires
+        );
+    }
+
     log::info!("Starting thedate server on {}", bind_addr);
 
     HttpServer::new(|| {
         App::new()
             .wrap(Logger::default())
+            .app_data(query_config())
             .route("/", web::get().to(home))
+            .route("/at/{instant}", web::get().to(at_instant))
+            .route("/f/{field}", web::get().to(field_value))
+            .route("/format", web::get().to(format_pattern))
+            .route("/convert", web::get().to(convert_time))
+            .route("/diff", web::get().to(diff))
+            .route("/add", web::get().to(add))
+            .route("/parse", web::post().to(parse_timestamp))
+            .route("/resolve", web::get().to(resolve_expression))
             .route("/health", web::get().to(health_check))
     })
     .bind(&bind_addr)?
@@ -0,0 +1,783 @@
+//! Resolution of English relative date expressions.
+//!
+//! An expression is at most one date clause and one time clause, in either
+//! order:
+//!
+//! | Date clause | Meaning |
+//! |-------------|---------|
+//! | `now`, `today`, `tomorrow`, `yesterday` | Relative days |
+//! | `the day after tomorrow`, `the day before yesterday` | ±2 days |
+//! | `friday`, `on friday` | Today if a Friday, else the next Friday |
+//! | `next friday`, `last friday` | First Friday after / before today |
+//! | `this friday` | Friday of the current week (Monday to Sunday) |
+//! | `in 3 days`, `3 days from now`, `2 weeks ago` | Calendar offsets |
+//! | `in 3 business days`, `2 working days ago` | Monday to Friday steps |
+//! | `next week`, `last month`, `this year` | One unit on / back / none |
+//! | `start of month`, `end of next quarter` | Period boundaries |
+//! | `first day of month`, `last day of last year` | Days in a period |
+//!
+//! Periods are `day`, `week`, `month`, `quarter` and `year`, optionally
+//! preceded by `the` and `this`, `next` or `last`. Times are `9am`,
+//! `9:30 pm`, `17:45`, `noon`, `midnight`, or a bare hour after `at`.
+//!
+//! Offsets and `now` keep the current time of day; named days resolve to
+//! midnight; `end of` is the last second of the period. A time clause
+//! replaces the time of day in every case.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Weekday};
+
+use crate::arithmetic::{self, resolve, Clock, MonthEnd};
+use crate::duration::CalendarOffset;
+use crate::error::ApiError;
+use crate::zone::{local_date, local_datetime, Zone};
+
+/// Longest expression accepted, in bytes
+pub const MAX_EXPRESSION_LEN: usize = 256;
+
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("monday", Weekday::Mon),
+    ("tuesday", Weekday::Tue),
+    ("wednesday", Weekday::Wed),
+    ("thursday", Weekday::Thu),
+    ("friday", Weekday::Fri),
+    ("saturday", Weekday::Sat),
+    ("sunday", Weekday::Sun),
+];
+
+const NUMBERS: [&str; 13] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve",
+];
+
+/// A span of time a clause can count in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Unit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    BusinessDay,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+/// A calendar period with a start and an end
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Period {
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Edge {
+    Start,
+    End,
+    FirstDay,
+    LastDay,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Relation {
+    Upcoming,
+    Next,
+    Last,
+    This,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DateClause {
+    Now,
+    Days(i64),
+    Weekday(Relation, Weekday),
+    Offset(i64, Unit),
+    Boundary(Edge, i64, Period),
+}
+
+/// A word of the expression and its byte offset
+#[derive(Debug)]
+struct Word<'a> {
+    text: String,
+    original: &'a str,
+    position: usize,
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    words: Vec<Word<'a>>,
+    index: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        let mut words = Vec::new();
+        let mut start = None;
+        for (i, c) in input.char_indices().chain([(input.len(), ' ')]) {
+            let separator = c.is_whitespace() || c == ',';
+            match (start, separator) {
+                (None, false) => start = Some(i),
+                (Some(s), true) => {
+                    words.push(Word {
+                        text: input[s..i].to_lowercase(),
+                        original: &input[s..i],
+                        position: s,
+                    });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        Parser {
+            input,
+            words,
+            index: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.words.get(self.index).map(|w| w.text.as_str())
+    }
+
+    fn peek_at(&self, ahead: usize) -> Option<&str> {
+        self.words.get(self.index + ahead).map(|w| w.text.as_str())
+    }
+
+    fn eat(&mut self, word: &str) -> bool {
+        if self.peek() == Some(word) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Error pointing at the current word, or at the end of the input
+    fn error(&self, expected: &'static str) -> ApiError {
+        match self.words.get(self.index) {
+            Some(word) => ApiError::InvalidExpression {
+                word: word.original.to_string(),
+                position: word.position,
+                expected,
+            },
+            None => ApiError::InvalidExpression {
+                word: String::new(),
+                position: self.input.len(),
+                expected,
+            },
+        }
+    }
+
+    fn expect(&mut self, word: &str, expected: &'static str) -> Result<(), ApiError> {
+        if self.eat(word) {
+            Ok(())
+        } else {
+            Err(self.error(expected))
+        }
+    }
+
+    fn number(&mut self) -> Option<i64> {
+        let text = self.peek()?;
+        let value = match text {
+            "a" | "an" => Some(1),
+            _ => NUMBERS
+                .iter()
+                .position(|n| *n == text)
+                .map(|n| n as i64)
+                .or_else(|| text.parse().ok().filter(|n: &i64| *n >= 0)),
+        }?;
+        self.index += 1;
+        Some(value)
+    }
+
+    fn unit(&mut self) -> Option<Unit> {
+        let text = self.peek()?;
+        if matches!(text, "business" | "working") {
+            if !matches!(self.peek_at(1), Some("day" | "days")) {
+                return None;
+            }
+            self.index += 2;
+            return Some(Unit::BusinessDay);
+        }
+        let unit = match text.strip_suffix('s').unwrap_or(text) {
+            "second" | "sec" => Unit::Second,
+            "minute" | "min" => Unit::Minute,
+            "hour" | "hr" => Unit::Hour,
+            "day" => Unit::Day,
+            "weekday" => Unit::BusinessDay,
+            "week" => Unit::Week,
+            "month" => Unit::Month,
+            "quarter" => Unit::Quarter,
+            "year" => Unit::Year,
+            _ => return None,
+        };
+        self.index += 1;
+        Some(unit)
+    }
+
+    fn period(&mut self) -> Result<(i64, Period), ApiError> {
+        self.eat("the");
+        let shift = if self.eat("next") {
+            1
+        } else if self.eat("last") || self.eat("previous") {
+            -1
+        } else {
+            self.eat("this");
+            0
+        };
+        let period = match self.peek() {
+            Some("day") => Period::Day,
+            Some("week") => Period::Week,
+            Some("month") => Period::Month,
+            Some("quarter") => Period::Quarter,
+            Some("year") => Period::Year,
+            _ => return Err(self.error("day, week, month, quarter or year")),
+        };
+        self.index += 1;
+        Ok((shift, period))
+    }
+
+    /// A weekday by full name or by an abbreviation of three letters or more
+    fn weekday(&mut self) -> Option<Weekday> {
+        let text = self.peek()?;
+        let (_, weekday) = WEEKDAYS
+            .iter()
+            .find(|(name, _)| text.len() >= 3 && name.starts_with(text))?;
+        self.index += 1;
+        Some(*weekday)
+    }
+
+    /// `9am`, `9:30 pm`, `17:45`, `noon`, `midnight`; a bare hour only when
+    /// `after_at`, since `2 weeks` would otherwise read as 2 o'clock
+    fn time(&mut self, after_at: bool) -> Result<Option<NaiveTime>, ApiError> {
+        let Some(text) = self.peek() else {
+            return Ok(None);
+        };
+        match text {
+            "noon" | "midday" => {
+                self.index += 1;
+                return Ok(NaiveTime::from_hms_opt(12, 0, 0));
+            }
+            "midnight" => {
+                self.index += 1;
+                return Ok(Some(NaiveTime::MIN));
+            }
+            _ => {}
+        }
+
+        let (clock, mut meridiem) = match text.strip_suffix("am") {
+            Some(clock) => (clock.to_string(), Some(false)),
+            None => match text.strip_suffix("pm") {
+                Some(clock) => (clock.to_string(), Some(true)),
+                None => (text.to_string(), None),
+            },
+        };
+        let (hour, minute) = match clock.split_once(':') {
+            Some((h, m)) if m.len() == 2 => (h, Some(m)),
+            Some(_) => return Err(self.error("a time such as 9am, 9:30pm or 17:45")),
+            None => (clock.as_str(), None),
+        };
+        let valid =
+            |s: &str| !s.is_empty() && s.len() <= 2 && s.bytes().all(|b| b.is_ascii_digit());
+        if !valid(hour) || minute.is_some_and(|m| !valid(m)) {
+            return if after_at {
+                Err(self.error("a time such as 9am, 9:30pm or 17:45"))
+            } else {
+                Ok(None)
+            };
+        }
+        let has_minutes = minute.is_some();
+        let (hour, minute): (u32, u32) = (
+            hour.parse().unwrap_or(99),
+            minute.map_or(0, |m| m.parse().unwrap_or(99)),
+        );
+        // A separate `am`/`pm` word after the number
+        let mut words = 1;
+        if meridiem.is_none() {
+            meridiem = match self.peek_at(1) {
+                Some("am" | "a.m.") => Some(false),
+                Some("pm" | "p.m.") => Some(true),
+                _ => None,
+            };
+            if meridiem.is_some() {
+                words = 2;
+            }
+        }
+        if meridiem.is_none() && !has_minutes && !after_at {
+            return Ok(None);
+        }
+        let hour = match meridiem {
+            Some(pm) if (1..=12).contains(&hour) => hour % 12 + if pm { 12 } else { 0 },
+            Some(_) => return Err(self.error("an hour from 1 to 12 before am or pm")),
+            None => hour,
+        };
+        let time = NaiveTime::from_hms_opt(hour, minute, 0)
+            .ok_or_else(|| self.error("a time such as 9am, 9:30pm or 17:45"))?;
+        self.index += words;
+        Ok(Some(time))
+    }
+
+    fn date(&mut self) -> Result<Option<DateClause>, ApiError> {
+        let Some(text) = self.peek().map(str::to_string) else {
+            return Ok(None);
+        };
+        let clause = match text.as_str() {
+            "now" => {
+                self.index += 1;
+                DateClause::Now
+            }
+            "right" if self.peek_at(1) == Some("now") => {
+                self.index += 2;
+                DateClause::Now
+            }
+            "today" => {
+                self.index += 1;
+                DateClause::Days(0)
+            }
+            "tomorrow" => {
+                self.index += 1;
+                DateClause::Days(1)
+            }
+            "yesterday" => {
+                self.index += 1;
+                DateClause::Days(-1)
+            }
+            "the" | "day" if matches!(self.peek_at(1), Some("day" | "after" | "before")) => {
+                self.eat("the");
+                self.expect("day", "day")?;
+                let days = if self.eat("after") {
+                    self.expect("tomorrow", "tomorrow")?;
+                    2
+                } else {
+                    self.expect("before", "after or before")?;
+                    self.expect("yesterday", "yesterday")?;
+                    -2
+                };
+                DateClause::Days(days)
+            }
+            "in" => {
+                self.index += 1;
+                let amount = self.number().ok_or_else(|| self.error("a number"))?;
+                let unit = self
+                    .unit()
+                    .ok_or_else(|| self.error("a unit such as days"))?;
+                DateClause::Offset(amount, unit)
+            }
+            "start" | "beginning" | "end" => {
+                self.index += 1;
+                self.expect("of", "of")?;
+                let (shift, period) = self.period()?;
+                let edge = if text == "end" {
+                    Edge::End
+                } else {
+                    Edge::Start
+                };
+                DateClause::Boundary(edge, shift, period)
+            }
+            "first" | "last" if self.peek_at(1) == Some("day") && self.peek_at(2) == Some("of") => {
+                let edge = if text == "first" {
+                    Edge::FirstDay
+                } else {
+                    Edge::LastDay
+                };
+                self.index += 3;
+                let (shift, period) = self.period()?;
+                DateClause::Boundary(edge, shift, period)
+            }
+            "next" | "last" | "previous" | "this" | "on" => {
+                self.index += 1;
+                let relation = match text.as_str() {
+                    "next" => Relation::Next,
+                    "last" | "previous" => Relation::Last,
+                    "this" => Relation::This,
+                    _ => Relation::Upcoming,
+                };
+                if let Some(weekday) = self.weekday() {
+                    DateClause::Weekday(relation, weekday)
+                } else if relation == Relation::Upcoming {
+                    return Err(self.error("a weekday"));
+                } else if let Some(unit) = self.unit() {
+                    let amount = match relation {
+                        Relation::Next => 1,
+                        Relation::Last => -1,
+                        _ => 0,
+                    };
+                    DateClause::Offset(amount, unit)
+                } else {
+                    return Err(self.error("a weekday or a unit such as week"));
+                }
+            }
+            _ => {
+                if let Some(weekday) = self.weekday() {
+                    DateClause::Weekday(Relation::Upcoming, weekday)
+                } else if let Some(amount) = self.number() {
+                    let unit = self
+                        .unit()
+                        .ok_or_else(|| self.error("a unit such as days"))?;
+                    let sign = if self.eat("ago") {
+                        -1
+                    } else if self.eat("from") {
+                        self.expect("now", "now")?;
+                        1
+                    } else if self.eat("later") || self.eat("hence") {
+                        1
+                    } else {
+                        return Err(self.error("ago or from now"));
+                    };
+                    DateClause::Offset(sign * amount, unit)
+                } else {
+                    return Ok(None);
+                }
+            }
+        };
+        Ok(Some(clause))
+    }
+
+    /// Parse the whole expression into an optional date and time clause
+    fn parse(mut self) -> Result<(Option<DateClause>, Option<NaiveTime>), ApiError> {
+        if self.words.is_empty() {
+            return Err(self.error("an expression such as 'tomorrow at 9am'"));
+        }
+        let mut date = None;
+        let mut time = None;
+        while self.index < self.words.len() {
+            let start = self.index;
+            if time.is_none() {
+                let after_at = self.eat("at");
+                if let Some(t) = self.time(after_at)? {
+                    time = Some(t);
+                    continue;
+                }
+                if after_at {
+                    return Err(self.error("a time such as 9am, 9:30pm or 17:45"));
+                }
+            }
+            if date.is_none() {
+                if let Some(clause) = self.date()? {
+                    date = Some(clause);
+                    continue;
+                }
+            }
+            self.index = start;
+            return Err(self.error(match (date, time) {
+                (None, None) => "a date or time",
+                (None, Some(_)) => "a date",
+                (Some(_), None) => "a time",
+                (Some(_), Some(_)) => "the end of the expression",
+            }));
+        }
+        Ok((date, time))
+    }
+}
+
+fn add_calendar(now: &DateTime<Zone>, amount: i64, unit: Unit) -> Result<DateTime<Zone>, ApiError> {
+    let mut offset = CalendarOffset::default();
+    match unit {
+        Unit::Second => offset.seconds = amount,
+        Unit::Minute => offset.minutes = amount,
+        Unit::Hour => offset.hours = amount,
+        Unit::Day => offset.days = amount,
+        Unit::Week => offset.weeks = amount,
+        Unit::Month => offset.months = amount,
+        Unit::Quarter => offset.months = amount.checked_mul(3).ok_or(ApiError::OutOfRange)?,
+        Unit::Year => offset.years = amount,
+        Unit::BusinessDay => {
+            let today = local_date(now).ok_or(ApiError::OutOfRange)?;
+            let step = if amount < 0 { -1 } else { 1 };
+            let mut remaining = amount.unsigned_abs();
+            // Any seven days in a row hold five business days, so whole
+            // weeks are skipped at once, leaving one to five to step over
+            let weeks = remaining.saturating_sub(1) / 5;
+            remaining -= weeks * 5;
+            let mut date = i64::try_from(weeks)
+                .ok()
+                .and_then(|weeks| weeks.checked_mul(7 * step))
+                .and_then(Duration::try_days)
+                .and_then(|days| today.checked_add_signed(days))
+                .ok_or(ApiError::OutOfRange)?;
+            while remaining > 0 {
+                date = date
+                    .checked_add_signed(Duration::days(step))
+                    .ok_or(ApiError::OutOfRange)?;
+                if !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+                    remaining -= 1;
+                }
+            }
+            offset.days = (date - today).num_days();
+        }
+    }
+    Ok(arithmetic::add(now, &offset, MonthEnd::Clamp, Clock::Wall)?.result)
+}
+
+fn weekday_date(today: NaiveDate, relation: Relation, weekday: Weekday) -> Option<NaiveDate> {
+    let target = i64::from(weekday.num_days_from_monday());
+    let current = i64::from(today.weekday().num_days_from_monday());
+    let days = match relation {
+        Relation::Upcoming => (target - current).rem_euclid(7),
+        Relation::Next => (target - current - 1).rem_euclid(7) + 1,
+        Relation::Last => -((current - target - 1).rem_euclid(7) + 1),
+        Relation::This => target - current,
+    };
+    today.checked_add_signed(Duration::days(days))
+}
+
+/// First and last day of the period containing `today`, shifted by `shift`
+fn period_bounds(today: NaiveDate, shift: i64, period: Period) -> Option<(NaiveDate, NaiveDate)> {
+    let month_span = |months: u32| -> Option<(NaiveDate, NaiveDate)> {
+        let index = i64::from(today.year()) * 12 + i64::from(today.month0());
+        let first = (index - index.rem_euclid(i64::from(months)))
+            .checked_add(shift.checked_mul(i64::from(months))?)?;
+        let start = NaiveDate::from_ymd_opt(
+            i32::try_from(first.div_euclid(12)).ok()?,
+            first.rem_euclid(12) as u32 + 1,
+            1,
+        )?;
+        let next = start.checked_add_months(chrono::Months::new(months))?;
+        Some((start, next.pred_opt()?))
+    };
+    match period {
+        Period::Day => {
+            let day = today.checked_add_signed(Duration::try_days(shift)?)?;
+            Some((day, day))
+        }
+        Period::Week => {
+            let monday = today.checked_sub_signed(Duration::days(i64::from(
+                today.weekday().num_days_from_monday(),
+            )))?;
+            let start = monday.checked_add_signed(Duration::try_weeks(shift)?)?;
+            Some((start, start.checked_add_signed(Duration::days(6))?))
+        }
+        Period::Month => month_span(1),
+        Period::Quarter => month_span(3),
+        Period::Year => month_span(12),
+    }
+}
+
+/// Resolve expression `q` against `now` in `now`'s zone
+///
+/// # Errors
+///
+/// Returns [`ApiError::InvalidExpression`] naming the first word that does
+/// not fit the grammar and its byte offset, or [`ApiError::OutOfRange`] if
+/// the result cannot be represented.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use thedate::natural::resolve_expression;
+/// use thedate::zone::Zone;
+///
+/// // Wednesday 2024-03-13, 15:00
+/// let now = Zone::default().at(Utc.with_ymd_and_hms(2024, 3, 13, 15, 0, 0).unwrap());
+/// let dt = resolve_expression("next friday at 9am", &now).unwrap();
+/// assert_eq!(dt.to_rfc3339(), "2024-03-15T09:00:00+00:00");
+/// ```
+pub fn resolve_expression(q: &str, now: &DateTime<Zone>) -> Result<DateTime<Zone>, ApiError> {
+    if q.len() > MAX_EXPRESSION_LEN {
+        return Err(ApiError::InvalidExpression {
+            word: String::new(),
+            position: MAX_EXPRESSION_LEN,
+            expected: "a shorter expression",
+        });
+    }
+    let (date, time) = Parser::new(q).parse()?;
+    let zone = now.timezone();
+    let today = local_date(now).ok_or(ApiError::OutOfRange)?;
+
+    // The date and the time of day it carries when no time clause is given
+    let (day, default_time) = match date.unwrap_or(DateClause::Now) {
+        DateClause::Now => (today, now.time()),
+        DateClause::Days(days) => (
+            today
+                .checked_add_signed(Duration::days(days))
+                .ok_or(ApiError::OutOfRange)?,
+            NaiveTime::MIN,
+        ),
+        DateClause::Weekday(relation, weekday) => (
+            weekday_date(today, relation, weekday).ok_or(ApiError::OutOfRange)?,
+            NaiveTime::MIN,
+        ),
+        DateClause::Offset(amount, unit) => {
+            let moved = add_calendar(now, amount, unit)?;
+            if time.is_none() {
+                return Ok(moved);
+            }
+            let local = local_datetime(&moved).ok_or(ApiError::OutOfRange)?;
+            (local.date(), local.time())
+        }
+        DateClause::Boundary(edge, shift, period) => {
+            let (first, last) = period_bounds(today, shift, period).ok_or(ApiError::OutOfRange)?;
+            match edge {
+                Edge::Start | Edge::FirstDay => (first, NaiveTime::MIN),
+                Edge::LastDay => (last, NaiveTime::MIN),
+                Edge::End => (
+                    last,
+                    NaiveTime::from_hms_opt(23, 59, 59).unwrap_or(NaiveTime::MIN),
+                ),
+            }
+        }
+    };
+    let local = day.and_time(time.unwrap_or(default_time));
+    Ok(resolve(zone, local)?.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{SecondsFormat, TimeZone, Utc};
+
+    /// Wednesday 2024-03-13 15:04:05 UTC
+    fn now() -> DateTime<Zone> {
+        Zone::default().at(Utc.with_ymd_and_hms(2024, 3, 13, 15, 4, 5).unwrap())
+    }
+
+    fn resolved(q: &str) -> String {
+        resolve_expression(q, &now())
+            .unwrap_or_else(|e| panic!("{}: {}", q, e))
+            .to_rfc3339_opts(SecondsFormat::Secs, true)
+    }
+
+    fn offending(q: &str) -> (String, usize) {
+        match resolve_expression(q, &now()) {
+            Err(ApiError::InvalidExpression { word, position, .. }) => (word, position),
+            other => panic!("expected InvalidExpression for {}, got {:?}", q, other),
+        }
+    }
+
+    #[test]
+    fn test_relative_days() {
+        assert_eq!(resolved("now"), "2024-03-13T15:04:05Z");
+        assert_eq!(resolved("today"), "2024-03-13T00:00:00Z");
+        assert_eq!(resolved("Tomorrow"), "2024-03-14T00:00:00Z");
+        assert_eq!(resolved("yesterday at noon"), "2024-03-12T12:00:00Z");
+        assert_eq!(resolved("the day after tomorrow"), "2024-03-15T00:00:00Z");
+        assert_eq!(resolved("day before yesterday"), "2024-03-11T00:00:00Z");
+    }
+
+    #[test]
+    fn test_weekdays() {
+        assert_eq!(resolved("friday"), "2024-03-15T00:00:00Z");
+        assert_eq!(resolved("wednesday"), "2024-03-13T00:00:00Z");
+        assert_eq!(resolved("next wednesday"), "2024-03-20T00:00:00Z");
+        assert_eq!(resolved("last wednesday"), "2024-03-06T00:00:00Z");
+        assert_eq!(resolved("this monday"), "2024-03-11T00:00:00Z");
+        assert_eq!(resolved("on tue at 5pm"), "2024-03-19T17:00:00Z");
+        assert_eq!(resolved("next friday at 9am"), "2024-03-15T09:00:00Z");
+    }
+
+    #[test]
+    fn test_offsets_keep_time_of_day() {
+        assert_eq!(resolved("2 weeks ago"), "2024-02-28T15:04:05Z");
+        assert_eq!(resolved("in 3 hours"), "2024-03-13T18:04:05Z");
+        assert_eq!(resolved("in a month"), "2024-04-13T15:04:05Z");
+        assert_eq!(resolved("five days from now"), "2024-03-18T15:04:05Z");
+        assert_eq!(resolved("next week"), "2024-03-20T15:04:05Z");
+        assert_eq!(resolved("in 2 days at 08:30"), "2024-03-15T08:30:00Z");
+    }
+
+    #[test]
+    fn test_business_days_skip_weekends() {
+        assert_eq!(resolved("in 3 business days"), "2024-03-18T15:04:05Z");
+        assert_eq!(resolved("in 2 working days"), "2024-03-15T15:04:05Z");
+        assert_eq!(resolved("4 business days ago"), "2024-03-07T15:04:05Z");
+    }
+
+    #[test]
+    fn test_business_days_match_stepping_day_by_day() {
+        for start in 9..=17 {
+            let now = Zone::default().at(Utc.with_ymd_and_hms(2024, 3, start, 12, 0, 0).unwrap());
+            for amount in -23_i64..=23 {
+                let mut date = now.date_naive();
+                let mut remaining = amount;
+                while remaining != 0 {
+                    date += Duration::days(remaining.signum());
+                    if !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+                        remaining -= remaining.signum();
+                    }
+                }
+                let moved = add_calendar(&now, amount, Unit::BusinessDay).unwrap();
+                assert_eq!(moved.date_naive(), date, "{} from {}", amount, now);
+            }
+        }
+    }
+
+    #[test]
+    fn test_huge_business_day_counts_fail_fast() {
+        for q in [
+            "in 999999999999 business days",
+            "999999999999 working days ago",
+        ] {
+            assert!(matches!(
+                resolve_expression(q, &now()),
+                Err(ApiError::OutOfRange)
+            ));
+        }
+        assert!(matches!(
+            add_calendar(&now(), i64::MIN, Unit::BusinessDay),
+            Err(ApiError::OutOfRange)
+        ));
+    }
+
+    #[test]
+    fn test_range_edges_are_out_of_range() {
+        let last = Zone::default().at(Utc.with_ymd_and_hms(262_142, 12, 31, 12, 0, 0).unwrap());
+        let first = Zone::default().at(Utc.with_ymd_and_hms(-262_143, 1, 1, 12, 0, 0).unwrap());
+        for (q, now) in [
+            ("next monday", last),
+            ("end of week", last),
+            ("last monday", first),
+            ("start of week", first),
+            ("in 2 business days", last),
+        ] {
+            assert!(
+                matches!(resolve_expression(q, &now), Err(ApiError::OutOfRange)),
+                "{}",
+                q
+            );
+        }
+    }
+
+    #[test]
+    fn test_period_boundaries() {
+        assert_eq!(resolved("last day of month"), "2024-03-31T00:00:00Z");
+        assert_eq!(resolved("first day of next month"), "2024-04-01T00:00:00Z");
+        assert_eq!(resolved("end of quarter"), "2024-03-31T23:59:59Z");
+        assert_eq!(resolved("start of next quarter"), "2024-04-01T00:00:00Z");
+        assert_eq!(resolved("end of the week"), "2024-03-17T23:59:59Z");
+        assert_eq!(resolved("beginning of last year"), "2023-01-01T00:00:00Z");
+        assert_eq!(resolved("last day of last month"), "2024-02-29T00:00:00Z");
+        assert_eq!(resolved("end of day"), "2024-03-13T23:59:59Z");
+    }
+
+    #[test]
+    fn test_time_only_is_today() {
+        assert_eq!(resolved("9am"), "2024-03-13T09:00:00Z");
+        assert_eq!(resolved("at 9"), "2024-03-13T09:00:00Z");
+        assert_eq!(resolved("9:30 pm"), "2024-03-13T21:30:00Z");
+        assert_eq!(resolved("midnight tomorrow"), "2024-03-14T00:00:00Z");
+    }
+
+    #[test]
+    fn test_resolves_in_zone() {
+        let zone = Zone::parse("Europe/London").unwrap();
+        let now = zone.at(Utc.with_ymd_and_hms(2024, 3, 29, 12, 0, 0).unwrap());
+        // London springs forward on Sunday 2024-03-31
+        let dt = resolve_expression("next monday at 9am", &now).unwrap();
+        assert_eq!(dt.to_rfc3339(), "2024-04-01T09:00:00+01:00");
+    }
+
+    #[test]
+    fn test_errors_point_at_offending_word() {
+        assert_eq!(offending("next fryday"), ("fryday".to_string(), 5));
+        assert_eq!(offending("in 3 fortnights"), ("fortnights".to_string(), 5));
+        assert_eq!(offending("tomorrow at 25:00"), ("25:00".to_string(), 12));
+        assert_eq!(offending("tomorrow banana"), ("banana".to_string(), 9));
+        assert_eq!(offending("3 days"), (String::new(), 6));
+        assert_eq!(offending("tomorrow today"), ("today".to_string(), 9));
+        assert_eq!(offending("  "), (String::new(), 2));
+    }
+}
@@ -0,0 +1,195 @@
+//! Native-digit numbering systems.
+//!
+//! Names follow the CLDR numbering system identifiers. Only the ten decimal
+//! digits are substituted; separators, signs and letters are kept, so a
+//! string rendered in any system converts back with [`to_latin`].
+
+use std::borrow::Cow;
+
+use serde_json::{Map, Value};
+
+use crate::error::ApiError;
+
+/// Decimal digit set used in rendered strings
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Numerals {
+    /// ASCII digits `0`-`9`
+    #[default]
+    Latn,
+    /// Arabic-Indic digits `٠`-`٩` (U+0660)
+    Arab,
+    /// Extended Arabic-Indic digits `۰`-`۹` (U+06F0), used for Persian and Urdu
+    Arabext,
+    /// Devanagari digits `०`-`९` (U+0966)
+    Deva,
+    /// Thai digits `๐`-`๙` (U+0E50)
+    Thai,
+}
+
+/// Every non-Latin digit zero; each system's digits are contiguous from it
+const ZEROS: [char; 4] = ['\u{0660}', '\u{06F0}', '\u{0966}', '\u{0E50}'];
+
+impl Numerals {
+    /// Accepted values of the `numerals` parameter
+    pub const NAMES: &'static [&'static str] = &["latn", "arab", "arabext", "deva", "thai"];
+
+    /// Parse a `numerals` parameter value
+    pub fn parse(value: &str) -> Result<Self, ApiError> {
+        match value.to_ascii_lowercase().as_str() {
+            "latn" => Ok(Numerals::Latn),
+            "arab" => Ok(Numerals::Arab),
+            "arabext" => Ok(Numerals::Arabext),
+            "deva" => Ok(Numerals::Deva),
+            "thai" => Ok(Numerals::Thai),
+            _ => Err(ApiError::InvalidChoice {
+                parameter: "numerals",
+                value: value.to_string(),
+                valid: Self::NAMES,
+            }),
+        }
+    }
+
+    fn zero(self) -> char {
+        match self {
+            Numerals::Latn => '0',
+            Numerals::Arab => ZEROS[0],
+            Numerals::Arabext => ZEROS[1],
+            Numerals::Deva => ZEROS[2],
+            Numerals::Thai => ZEROS[3],
+        }
+    }
+
+    /// Replace ASCII digits in `text` with this system's digits
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use thedate::numerals::Numerals;
+    ///
+    /// assert_eq!(Numerals::Arab.render("2024-03-15"), "٢٠٢٤-٠٣-١٥");
+    /// assert_eq!(Numerals::Thai.render("12:30 pm"), "๑๒:๓๐ pm");
+    /// ```
+    pub fn render<'a>(self, text: &'a str) -> Cow<'a, str> {
+        if self == Numerals::Latn || !text.bytes().any(|b| b.is_ascii_digit()) {
+            return Cow::Borrowed(text);
+        }
+        let zero = self.zero() as u32;
+        Cow::Owned(
+            text.chars()
+                .map(|c| match c.to_digit(10) {
+                    Some(d) if c.is_ascii_digit() => char::from_u32(zero + d).unwrap_or(c),
+                    _ => c,
+                })
+                .collect(),
+        )
+    }
+
+    /// Render every string value in `map`; numbers and nested values are kept
+    pub fn apply(self, map: &mut Map<String, Value>) {
+        if self == Numerals::Latn {
+            return;
+        }
+        for value in map.values_mut() {
+            if let Value::String(text) = value {
+                if let Cow::Owned(rendered) = self.render(text) {
+                    *text = rendered;
+                }
+            }
+        }
+    }
+}
+
+/// Value of `c` if it is a digit in any supported system
+fn digit_value(c: char) -> Option<u32> {
+    if c.is_ascii_digit() {
+        return c.to_digit(10);
+    }
+    ZEROS
+        .iter()
+        .map(|&zero| (c as u32).wrapping_sub(zero as u32))
+        .find(|&offset| offset < 10)
+}
+
+/// Replace digits from any supported system with ASCII digits
+///
+/// Systems may be mixed within one string. Input without native digits is
+/// returned unchanged and unallocated.
+///
+/// # Examples
+///
+/// ```
+/// use thedate::numerals::to_latin;
+///
+/// assert_eq!(to_latin("۱۷۱۰۵۰۴۰۴۵"), "1710504045");
+/// assert_eq!(to_latin("२०२४-03-१५"), "2024-03-15");
+/// ```
+pub fn to_latin(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(
+        text.chars()
+            .map(|c| match digit_value(c) {
+                Some(d) if !c.is_ascii_digit() => char::from_digit(d, 10).unwrap_or(c),
+                _ => c,
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SYSTEMS: [Numerals; 5] = [
+        Numerals::Latn,
+        Numerals::Arab,
+        Numerals::Arabext,
+        Numerals::Deva,
+        Numerals::Thai,
+    ];
+
+    #[test]
+    fn test_every_system_round_trips() {
+        let text = "2024-03-15T12:30:45.123+05:30 0123456789";
+        for numerals in SYSTEMS {
+            let rendered = numerals.render(text);
+            assert_eq!(to_latin(&rendered), text, "{:?}", numerals);
+        }
+    }
+
+    #[test]
+    fn test_digit_values() {
+        assert_eq!(Numerals::Arabext.render("0123456789"), "۰۱۲۳۴۵۶۷۸۹");
+        assert_eq!(Numerals::Deva.render("0123456789"), "०१२३४५६७८९");
+        assert_eq!(Numerals::Thai.render("0123456789"), "๐๑๒๓๔๕๖๗๘๙");
+        assert_eq!(Numerals::Arab.render("0123456789"), "٠١٢٣٤٥٦٧٨٩");
+    }
+
+    #[test]
+    fn test_apply_keeps_numbers_numeric() {
+        let mut map = Map::new();
+        map.insert("full_iso".into(), Value::from("2024-03-15"));
+        map.insert("unix_timestamp".into(), Value::from(1710504045));
+        Numerals::Deva.apply(&mut map);
+        assert_eq!(map["full_iso"], "२०२४-०३-१५");
+        assert_eq!(map["unix_timestamp"], 1710504045);
+    }
+
+    #[test]
+    fn test_to_latin_leaves_other_text() {
+        assert_eq!(to_latin("März ١٥"), "März 15");
+        assert!(matches!(to_latin("plain 15"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_unknown_system() {
+        assert!(matches!(
+            Numerals::parse("roman"),
+            Err(ApiError::InvalidChoice {
+                parameter: "numerals",
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,553 @@
+//! Response encodings for rendered timestamps.
+//!
+//! The encoding is chosen by the `?format=` query parameter when present,
+//! otherwise by the `Accept` header. `Accept: */*` and a missing header keep
+//! JSON, so existing clients see no change.
+//!
+//! Nested objects are flattened with dotted keys for CSV and plain text, and
+//! rendered as nested elements or mappings for XML and YAML. MessagePack and
+//! CBOR carry the same keys and value types as JSON, so integers such as
+//! `unix_timestamp` stay integers.
+
+use std::fmt::Write;
+
+use actix_web::{
+    http::header::{self, HeaderValue},
+    HttpRequest, HttpResponse,
+};
+use serde_json::{Map, Value};
+
+use crate::error::ApiError;
+
+/// A response encoding for a rendered timestamp
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    Csv,
+    Xml,
+    Yaml,
+    Text,
+    MsgPack,
+    Cbor,
+}
+
+impl OutputFormat {
+    /// Every supported encoding, JSON first
+    pub const ALL: &'static [OutputFormat] = &[
+        OutputFormat::Json,
+        OutputFormat::Csv,
+        OutputFormat::Xml,
+        OutputFormat::Yaml,
+        OutputFormat::Text,
+        OutputFormat::MsgPack,
+        OutputFormat::Cbor,
+    ];
+
+    /// Name accepted by `?format=`
+    pub fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Xml => "xml",
+            OutputFormat::Yaml => "yaml",
+            OutputFormat::Text => "text",
+            OutputFormat::MsgPack => "msgpack",
+            OutputFormat::Cbor => "cbor",
+        }
+    }
+
+    /// Media type sent in `Content-Type`
+    pub fn media_type(self) -> &'static str {
+        match self {
+            OutputFormat::Json => "application/json",
+            OutputFormat::Csv => "text/csv; charset=utf-8",
+            OutputFormat::Xml => "application/xml; charset=utf-8",
+            OutputFormat::Yaml => "application/yaml; charset=utf-8",
+            OutputFormat::Text => "text/plain; charset=utf-8",
+            OutputFormat::MsgPack => "application/msgpack",
+            OutputFormat::Cbor => "application/cbor",
+        }
+    }
+
+    /// Parse a `?format=` value
+    pub fn from_name(name: &str) -> Result<Self, ApiError> {
+        match name.to_ascii_lowercase().as_str() {
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            "xml" => Ok(OutputFormat::Xml),
+            "yaml" | "yml" => Ok(OutputFormat::Yaml),
+            "text" | "txt" | "plain" => Ok(OutputFormat::Text),
+            "msgpack" | "messagepack" => Ok(OutputFormat::MsgPack),
+            "cbor" => Ok(OutputFormat::Cbor),
+            _ => Err(ApiError::UnknownFormat(name.to_string())),
+        }
+    }
+
+    /// Match a single media range from an `Accept` header
+    fn from_media_range(range: &str) -> Option<Self> {
+        match range.to_ascii_lowercase().as_str() {
+            "*/*" | "application/*" | "application/json" => Some(OutputFormat::Json),
+            "text/csv" => Some(OutputFormat::Csv),
+            "application/xml" | "text/xml" => Some(OutputFormat::Xml),
+            "application/yaml" | "application/x-yaml" | "text/yaml" | "text/x-yaml" => {
+                Some(OutputFormat::Yaml)
+            }
+            "text/plain" | "text/*" => Some(OutputFormat::Text),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(OutputFormat::MsgPack)
+            }
+            "application/cbor" => Some(OutputFormat::Cbor),
+            _ => None,
+        }
+    }
+
+    /// Choose an encoding from an `Accept` header value
+    ///
+    /// The supported media range with the highest `q` wins; ties go to the
+    /// range listed first. Ranges with `q=0` are never chosen.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ApiError::NotAcceptable`] if no listed range is supported.
+    pub fn from_accept(accept: &str) -> Result<Self, ApiError> {
+        let mut best: Option<(f32, OutputFormat)> = None;
+        for entry in accept.split(',') {
+            let mut parts = entry.split(';').map(str::trim);
+            let range = parts.next().unwrap_or_default();
+            let q = parts
+                .filter_map(|p| p.strip_prefix("q="))
+                .find_map(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if q <= 0.0 {
+                continue;
+            }
+            if let Some(format) = Self::from_media_range(range) {
+                if best.is_none_or(|(best_q, _)| q > best_q) {
+                    best = Some((q, format));
+                }
+            }
+        }
+        best.map(|(_, format)| format)
+            .ok_or_else(|| ApiError::NotAcceptable(accept.to_string()))
+    }
+
+    /// Choose an encoding for a request, preferring `?format=` over `Accept`
+    pub fn negotiate(req: &HttpRequest, format: Option<&str>) -> Result<Self, ApiError> {
+        if let Some(name) = format {
+            return Self::from_name(name);
+        }
+        match req.headers().get(header::ACCEPT).map(HeaderValue::to_str) {
+            Some(Ok(accept)) if !accept.trim().is_empty() => Self::from_accept(accept),
+            _ => Ok(OutputFormat::Json),
+        }
+    }
+
+    /// Encode a rendered timestamp in this format
+    pub fn encode(self, map: &Map<String, Value>) -> Vec<u8> {
+        match self {
+            OutputFormat::Json => Value::Object(map.clone()).to_string().into_bytes(),
+            OutputFormat::Csv => to_csv(map).into_bytes(),
+            OutputFormat::Xml => to_xml(map).into_bytes(),
+            OutputFormat::Yaml => to_yaml(map).into_bytes(),
+            OutputFormat::Text => to_text(map).into_bytes(),
+            OutputFormat::MsgPack => to_msgpack(map),
+            OutputFormat::Cbor => to_cbor(map),
+        }
+    }
+
+    /// Build a 200 response carrying `map` in this format
+    pub fn respond(self, map: &Map<String, Value>) -> HttpResponse {
+        HttpResponse::Ok()
+            .content_type(self.media_type())
+            .insert_header((header::VARY, "Accept"))
+            .body(self.encode(map))
+    }
+}
+
+/// Flatten nested objects and arrays into `(dotted.key, scalar)` pairs
+fn flatten(prefix: &str, value: &Value, out: &mut Vec<(String, Value)>) {
+    let join = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        }
+    };
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                flatten(&join(key), value, out);
+            }
+        }
+        Value::Array(items) => {
+            for (i, value) in items.iter().enumerate() {
+                flatten(&join(&i.to_string()), value, out);
+            }
+        }
+        scalar => out.push((prefix.to_string(), scalar.clone())),
+    }
+}
+
+/// Render a scalar without JSON quoting; `null` becomes empty
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn csv_cell(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// RFC 4180 CSV: a header row of field names and one row of values
+fn to_csv(map: &Map<String, Value>) -> String {
+    let mut pairs = Vec::new();
+    flatten("", &Value::Object(map.clone()), &mut pairs);
+    let header: Vec<_> = pairs.iter().map(|(k, _)| csv_cell(k)).collect();
+    let values: Vec<_> = pairs
+        .iter()
+        .map(|(_, v)| csv_cell(&scalar_text(v)))
+        .collect();
+    format!("{}\r\n{}\r\n", header.join(","), values.join(","))
+}
+
+/// `key=value` lines, one per (flattened) field
+fn to_text(map: &Map<String, Value>) -> String {
+    let mut pairs = Vec::new();
+    flatten("", &Value::Object(map.clone()), &mut pairs);
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}\n", k, scalar_text(v).replace('\n', "\\n")))
+        .collect()
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn is_xml_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        && !name.to_ascii_lowercase().starts_with("xml")
+}
+
+fn xml_element(out: &mut String, name: &str, value: &Value, depth: usize) {
+    let indent = "  ".repeat(depth);
+    let (open, close) = if is_xml_name(name) {
+        (name.to_string(), name.to_string())
+    } else {
+        (
+            format!("field name=\"{}\"", xml_escape(name)),
+            "field".to_string(),
+        )
+    };
+    match value {
+        Value::Object(map) => {
+            let _ = writeln!(out, "{}<{}>", indent, open);
+            for (key, value) in map {
+                xml_element(out, key, value, depth + 1);
+            }
+            let _ = writeln!(out, "{}</{}>", indent, close);
+        }
+        Value::Array(items) => {
+            let _ = writeln!(out, "{}<{}>", indent, open);
+            for value in items {
+                xml_element(out, "item", value, depth + 1);
+            }
+            let _ = writeln!(out, "{}</{}>", indent, close);
+        }
+        Value::Null => {
+            let _ = writeln!(out, "{}<{}/>", indent, open);
+        }
+        scalar => {
+            let text = xml_escape(&scalar_text(scalar));
+            let _ = writeln!(out, "{}<{}>{}</{}>", indent, open, text, close);
+        }
+    }
+}
+
+/// XML document with a `<timestamp>` root and one element per field
+fn to_xml(map: &Map<String, Value>) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml_element(&mut out, "timestamp", &Value::Object(map.clone()), 0);
+    out
+}
+
+fn yaml_key(key: &str) -> String {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
+
+fn yaml_value(out: &mut String, value: &Value, depth: usize) {
+    let indent = "  ".repeat(depth);
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                match value {
+                    Value::Object(m) if !m.is_empty() => {
+                        let _ = writeln!(out, "{}{}:", indent, yaml_key(key));
+                        yaml_value(out, value, depth + 1);
+                    }
+                    Value::Array(a) if !a.is_empty() => {
+                        let _ = writeln!(out, "{}{}:", indent, yaml_key(key));
+                        yaml_value(out, value, depth);
+                    }
+                    scalar => {
+                        let _ =
+                            writeln!(out, "{}{}: {}", indent, yaml_key(key), yaml_scalar(scalar));
+                    }
+                }
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for value in items {
+                match value {
+                    Value::Object(m) if !m.is_empty() => {
+                        let _ = writeln!(out, "{}-", indent);
+                        yaml_value(out, value, depth + 1);
+                    }
+                    Value::Array(a) if !a.is_empty() => {
+                        let _ = writeln!(out, "{}-", indent);
+                        yaml_value(out, value, depth + 1);
+                    }
+                    scalar => {
+                        let _ = writeln!(out, "{}- {}", indent, yaml_scalar(scalar));
+                    }
+                }
+            }
+        }
+        scalar => {
+            let _ = writeln!(out, "{}{}", indent, yaml_scalar(scalar));
+        }
+    }
+}
+
+/// Strings are always double-quoted (JSON string syntax is valid YAML), so
+/// values such as `"20240315"` keep their string type
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::Object(_) => "{}".to_string(),
+        Value::Array(_) => "[]".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// YAML document with one mapping entry per field
+fn to_yaml(map: &Map<String, Value>) -> String {
+    let mut out = String::from("---\n");
+    yaml_value(&mut out, &Value::Object(map.clone()), 0);
+    out
+}
+
+/// MessagePack map with string keys, as `rmp_serde` writes structs
+fn to_msgpack(map: &Map<String, Value>) -> Vec<u8> {
+    // Writing a JSON value into a Vec cannot fail
+    rmp_serde::to_vec_named(map).expect("MessagePack encoding of a JSON value")
+}
+
+/// CBOR map with text-string keys
+fn to_cbor(map: &Map<String, Value>) -> Vec<u8> {
+    let mut out = Vec::new();
+    // Writing a JSON value into a Vec cannot fail
+    ciborium::into_writer(map, &mut out).expect("CBOR encoding of a JSON value");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn encode_str(format: OutputFormat, map: &Map<String, Value>) -> String {
+        String::from_utf8(format.encode(map)).unwrap()
+    }
+
+    fn sample() -> Map<String, Value> {
+        match json!({
+            "yyyymmdd": "20240315",
+            "unix_timestamp": 1710504045,
+            "verbose_date": "15-Mar-2024",
+            "note": "a, \"quoted\" <value>",
+        }) {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_accept_star_keeps_json() {
+        assert_eq!(
+            OutputFormat::from_accept("*/*").unwrap(),
+            OutputFormat::Json
+        );
+    }
+
+    #[test]
+    fn test_accept_specific_types() {
+        assert_eq!(
+            OutputFormat::from_accept("text/csv").unwrap(),
+            OutputFormat::Csv
+        );
+        assert_eq!(
+            OutputFormat::from_accept("text/xml").unwrap(),
+            OutputFormat::Xml
+        );
+        assert_eq!(
+            OutputFormat::from_accept("application/x-yaml").unwrap(),
+            OutputFormat::Yaml
+        );
+        assert_eq!(
+            OutputFormat::from_accept("text/plain").unwrap(),
+            OutputFormat::Text
+        );
+    }
+
+    #[test]
+    fn test_accept_quality_values() {
+        let accept = "application/json;q=0.5, text/csv;q=0.9, */*;q=0.1";
+        assert_eq!(
+            OutputFormat::from_accept(accept).unwrap(),
+            OutputFormat::Csv
+        );
+    }
+
+    #[test]
+    fn test_accept_browser_style_header() {
+        let accept = "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8";
+        assert_eq!(
+            OutputFormat::from_accept(accept).unwrap(),
+            OutputFormat::Xml
+        );
+    }
+
+    #[test]
+    fn test_accept_q_zero_is_refused() {
+        assert!(OutputFormat::from_accept("text/csv;q=0").is_err());
+    }
+
+    #[test]
+    fn test_accept_unsupported_type() {
+        assert!(matches!(
+            OutputFormat::from_accept("image/png"),
+            Err(ApiError::NotAcceptable(_))
+        ));
+    }
+
+    #[test]
+    fn test_format_names() {
+        for format in OutputFormat::ALL {
+            assert_eq!(OutputFormat::from_name(format.name()).unwrap(), *format);
+        }
+        assert!(OutputFormat::from_name("pdf").is_err());
+    }
+
+    #[test]
+    fn test_csv_has_header_and_value_rows() {
+        let csv = encode_str(OutputFormat::Csv, &sample());
+        let rows: Vec<_> = csv.split("\r\n").collect();
+        assert_eq!(rows[0], "yyyymmdd,unix_timestamp,verbose_date,note");
+        assert_eq!(
+            rows[1],
+            "20240315,1710504045,15-Mar-2024,\"a, \"\"quoted\"\" <value>\""
+        );
+        assert_eq!(rows[2], "");
+    }
+
+    #[test]
+    fn test_xml_escapes_values() {
+        let xml = encode_str(OutputFormat::Xml, &sample());
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains("<unix_timestamp>1710504045</unix_timestamp>"));
+        assert!(xml.contains("<note>a, &quot;quoted&quot; &lt;value&gt;</note>"));
+    }
+
+    #[test]
+    fn test_yaml_quotes_strings_keeps_numbers() {
+        let yaml = encode_str(OutputFormat::Yaml, &sample());
+        assert!(yaml.contains("yyyymmdd: \"20240315\"\n"));
+        assert!(yaml.contains("unix_timestamp: 1710504045\n"));
+    }
+
+    #[test]
+    fn test_text_is_key_value_lines() {
+        let text = encode_str(OutputFormat::Text, &sample());
+        assert!(text.starts_with("yyyymmdd=20240315\nunix_timestamp=1710504045\n"));
+    }
+
+    #[test]
+    fn test_accept_binary_types() {
+        assert_eq!(
+            OutputFormat::from_accept("application/x-msgpack").unwrap(),
+            OutputFormat::MsgPack
+        );
+        assert_eq!(
+            OutputFormat::from_accept("application/cbor").unwrap(),
+            OutputFormat::Cbor
+        );
+    }
+
+    #[test]
+    fn test_msgpack_round_trip() {
+        let bytes = OutputFormat::MsgPack.encode(&sample());
+        let decoded: Map<String, Value> = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(decoded, sample());
+        assert!(decoded["unix_timestamp"].is_i64());
+    }
+
+    #[test]
+    fn test_cbor_round_trip() {
+        let bytes = OutputFormat::Cbor.encode(&sample());
+        let decoded: Map<String, Value> = ciborium::from_reader(bytes.as_slice()).unwrap();
+        assert_eq!(decoded, sample());
+        assert!(decoded["unix_timestamp"].is_i64());
+    }
+
+    #[test]
+    fn test_cbor_integer_is_major_type_0() {
+        let map = match json!({"n": 1710504045}) {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        };
+        // a1 (map of 1), 61 6e ("n"), 1a (uint32) 65f4386d
+        assert_eq!(
+            OutputFormat::Cbor.encode(&map),
+            [0xa1, 0x61, 0x6e, 0x1a, 0x65, 0xf4, 0x38, 0x6d]
+        );
+    }
+
+    #[test]
+    fn test_nested_values_are_flattened() {
+        let map = match json!({"relative": {"value": 3, "unit": "hour"}}) {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            encode_str(OutputFormat::Text, &map),
+            "relative.value=3\nrelative.unit=hour\n"
+        );
+        assert_eq!(
+            encode_str(OutputFormat::Yaml, &map),
+            "---\nrelative:\n  value: 3\n  unit: \"hour\"\n"
+        );
+    }
+}
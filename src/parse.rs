@@ -0,0 +1,913 @@
+//! Detection of the format of free-form timestamp strings.
+//!
+//! The input is tried against every known format rather than the first that
+//! fits, so an input such as `03/04/24` yields both its month/day and
+//! day/month readings. Readings that land on the same instant are merged;
+//! any more than one left is an ambiguity for the caller to resolve.
+//!
+//! Recognised forms:
+//! - RFC 3339 and RFC 2822
+//! - Unix time as a bare integer: always read as seconds, and additionally as
+//!   milliseconds, microseconds or nanoseconds when that reading falls in
+//!   [`PLAUSIBLE_YEARS`]; readings past the year 9999 are dropped, as
+//!   several formats cannot represent them
+//! - Numeric dates in year-month-day, month/day/year and day/month/year
+//!   order with `-`, `/`, `.`, `_` or no separator, and two-digit years
+//!   (`00`–`68` are 2000–2068, `69`–`99` are 1969–1999, as in POSIX `%y`)
+//! - Dates with an English month name (`15 Mar 2024`, `March 15, 2024`,
+//!   `5-Mar-2024`), optionally after a weekday that must agree with the date
+//! - ISO week dates (`2024-W11-5`, `2024W115`, `2024-W11`) and ordinal dates
+//!   (`2024-075`, `2024075`)
+//! - Any date followed by a time (`T` or whitespace separated) on a 24-hour
+//!   (`17:30`, `17:30:45.123`) or 12-hour (`5pm`, `5:30 PM`) clock, with an
+//!   optional offset (`Z`, `UTC`, `GMT`, `+01:00`, `+0100`, `+01`)
+//!
+//! Times without an offset are read as wall-clock time in the requested
+//! zone; a time repeated when clocks fall back yields both readings. Digits
+//! may be Arabic-Indic, Extended Arabic-Indic, Devanagari or Thai.
+
+use std::ops::RangeInclusive;
+
+use chrono::{
+    DateTime, Datelike, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+    Utc, Weekday,
+};
+use serde_json::Value;
+
+use crate::arithmetic::{resolve, Resolution};
+use crate::error::ApiError;
+use crate::instant::{parse_instant, Unit};
+use crate::numerals::to_latin;
+use crate::timestamp::Field;
+use crate::zone::Zone;
+
+/// Longest input accepted, in bytes
+pub const MAX_INPUT_LEN: usize = 256;
+
+/// Years in which a Unix time read in a unit finer than seconds is offered
+/// as a candidate; outside them such a reading is almost never intended
+pub const PLAUSIBLE_YEARS: RangeInclusive<i32> = 1971..=2199;
+
+/// Years every `Timestamp` field can render; readings outside are dropped
+const RENDERABLE_YEARS: RangeInclusive<i32> = 0..=9999;
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("monday", Weekday::Mon),
+    ("tuesday", Weekday::Tue),
+    ("wednesday", Weekday::Wed),
+    ("thursday", Weekday::Thu),
+    ("friday", Weekday::Fri),
+    ("saturday", Weekday::Sat),
+    ("sunday", Weekday::Sun),
+];
+
+/// One way of reading the input
+#[derive(Debug)]
+pub struct Reading {
+    /// strftime patterns (or standard names) that produce the input
+    pub formats: Vec<String>,
+    /// How the input was understood, such as `day/month/year, 12-hour clock`
+    pub interpretation: String,
+    pub instant: DateTime<Utc>,
+    /// Names of `Timestamp` fields that render exactly as the input
+    pub fields: Vec<&'static str>,
+}
+
+/// Every distinct reading of `input`, with times lacking an offset read in
+/// `zone`
+///
+/// # Errors
+///
+/// Returns [`ApiError::UnrecognizedTimestamp`] if the input is empty, longer
+/// than [`MAX_INPUT_LEN`], or matches no known format.
+///
+/// # Examples
+///
+/// ```
+/// use thedate::parse::detect;
+/// use thedate::zone::Zone;
+///
+/// let readings = detect("03/04/24", Zone::default()).unwrap();
+/// assert_eq!(readings.len(), 2);
+/// assert_eq!(readings[0].interpretation, "month/day/two-digit year, read in UTC");
+/// assert_eq!(readings[1].interpretation, "day/month/two-digit year, read in UTC");
+/// ```
+pub fn detect(input: &str, zone: Zone) -> Result<Vec<Reading>, ApiError> {
+    let unrecognized = |reason: &str| ApiError::UnrecognizedTimestamp {
+        input: input.chars().take(64).collect(),
+        reason: reason.to_string(),
+    };
+    if input.len() > MAX_INPUT_LEN {
+        return Err(unrecognized(&format!(
+            "input is longer than {} bytes",
+            MAX_INPUT_LEN
+        )));
+    }
+    let latin = to_latin(input.trim());
+    let trimmed = latin.as_ref();
+    if trimmed.is_empty() {
+        return Err(unrecognized("input is empty"));
+    }
+
+    let mut found: Vec<(String, String, DateTime<Utc>)> = Vec::new();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(trimmed) {
+        found.push(("RFC 3339".into(), "RFC 3339".into(), dt.to_utc()));
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc2822(trimmed) {
+        found.push(("RFC 2822".into(), "RFC 2822".into(), dt.to_utc()));
+    }
+    if trimmed.starts_with('@') {
+        if let Ok(dt) = parse_instant(trimmed) {
+            found.push(("@<n><unit>".into(), "Unix time with unit".into(), dt));
+        }
+    }
+    found.extend(unix_readings(trimmed));
+    found.extend(local_readings(trimmed, zone));
+
+    let mut readings: Vec<Reading> = Vec::new();
+    for (format, interpretation, instant) in found {
+        if !RENDERABLE_YEARS.contains(&instant.year()) {
+            continue;
+        }
+        match readings.iter_mut().find(|r| r.instant == instant) {
+            Some(existing) if !existing.formats.contains(&format) => existing.formats.push(format),
+            Some(_) => {}
+            None => readings.push(Reading {
+                formats: vec![format],
+                interpretation,
+                instant,
+                fields: matching_fields(trimmed, &zone.at(instant)),
+            }),
+        }
+    }
+    if readings.is_empty() {
+        return Err(unrecognized("no known format matches"));
+    }
+    Ok(readings)
+}
+
+/// Names of the fields of `dt` whose rendering equals `input`
+fn matching_fields(input: &str, dt: &DateTime<Zone>) -> Vec<&'static str> {
+    Field::ALL
+        .iter()
+        .filter(|field| match field.render(dt) {
+            Value::String(s) => s.trim() == input,
+            Value::Number(n) => n.to_string() == input,
+            _ => false,
+        })
+        .map(|field| field.name())
+        .collect()
+}
+
+/// Readings of a bare integer as Unix time in each unit
+fn unix_readings(input: &str) -> Vec<(String, String, DateTime<Utc>)> {
+    let digits = input.strip_prefix('-').unwrap_or(input);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Vec::new();
+    }
+    let Ok(value) = input.parse::<i64>() else {
+        return Vec::new();
+    };
+    [
+        (Unit::Seconds, "%s", "Unix time in seconds"),
+        (Unit::Millis, "%s%3f", "Unix time in milliseconds"),
+        (Unit::Micros, "%s%6f", "Unix time in microseconds"),
+        (Unit::Nanos, "%s%9f", "Unix time in nanoseconds"),
+    ]
+    .into_iter()
+    .filter_map(|(unit, format, interpretation)| {
+        let dt = unit.to_instant(value)?;
+        let plausible = unit == Unit::Seconds || PLAUSIBLE_YEARS.contains(&dt.year());
+        plausible.then(|| (format.to_string(), interpretation.to_string(), dt))
+    })
+    .collect()
+}
+
+/// Readings of the input as a date, optionally followed by a time
+fn local_readings(input: &str, zone: Zone) -> Vec<(String, String, DateTime<Utc>)> {
+    let mut splits: Vec<(&str, &str, &str)> = vec![(input, "", "")];
+    let bytes = input.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        let between_digits = i > 0
+            && i + 1 < bytes.len()
+            && bytes[i - 1].is_ascii_digit()
+            && bytes[i + 1].is_ascii_digit();
+        if (b == b'T' || b == b't') && between_digits {
+            splits.push((&input[..i], &input[i..=i], &input[i + 1..]));
+        }
+        // Split before each run of whitespace, absorbing a trailing comma
+        if b.is_ascii_whitespace() && i > 0 && !bytes[i - 1].is_ascii_whitespace() {
+            let end = (i..bytes.len())
+                .find(|&j| !bytes[j].is_ascii_whitespace())
+                .unwrap_or(bytes.len());
+            let date_end = if bytes[i - 1] == b',' { i - 1 } else { i };
+            splits.push((&input[..date_end], &input[date_end..end], &input[end..]));
+        }
+    }
+
+    let mut out = Vec::new();
+    for (date_part, separator, time_part) in splits {
+        let time = if time_part.is_empty() {
+            None
+        } else {
+            match parse_time(time_part) {
+                Some(time) => Some(time),
+                None => continue,
+            }
+        };
+        for date in parse_date(date_part) {
+            let (naive, format, mut interpretation) = match &time {
+                Some(time) => (
+                    date.date.and_time(time.time),
+                    format!("{}{}{}", date.format, separator, time.format),
+                    format!("{}, {}", date.interpretation, time.interpretation),
+                ),
+                None => (
+                    date.date.and_time(NaiveTime::MIN),
+                    date.format.clone(),
+                    date.interpretation.to_string(),
+                ),
+            };
+            match time.as_ref().and_then(|t| t.offset) {
+                Some(offset) => {
+                    if let LocalResult::Single(dt) = offset.from_local_datetime(&naive) {
+                        out.push((format, interpretation, dt.to_utc()));
+                    }
+                }
+                None => {
+                    interpretation.push_str(&format!(", read in {}", zone));
+                    out.extend(in_zone(zone, naive, format, interpretation));
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Every instant a wall-clock time names in `zone`; none when the zone's
+/// offset takes it past either end of the supported range
+fn in_zone(
+    zone: Zone,
+    naive: NaiveDateTime,
+    format: String,
+    interpretation: String,
+) -> Vec<(String, String, DateTime<Utc>)> {
+    match zone.from_local_datetime(&naive) {
+        LocalResult::Ambiguous(earlier, later) => vec![
+            (
+                format.clone(),
+                format!(
+                    "{} (earlier of two, before clocks fell back)",
+                    interpretation
+                ),
+                earlier.to_utc(),
+            ),
+            (
+                format,
+                format!("{} (later of two, after clocks fell back)", interpretation),
+                later.to_utc(),
+            ),
+        ],
+        _ => {
+            let Ok((dt, resolution)) = resolve(zone, naive) else {
+                return Vec::new();
+            };
+            let interpretation = if resolution == Resolution::GapShifted {
+                format!(
+                    "{} (skipped by a clock change, shifted forward)",
+                    interpretation
+                )
+            } else {
+                interpretation
+            };
+            vec![(format, interpretation, dt.to_utc())]
+        }
+    }
+}
+
+/// A calendar date read from the date part of the input
+struct DateReading {
+    date: NaiveDate,
+    format: String,
+    interpretation: &'static str,
+}
+
+/// Alphanumeric runs of `s` and the separators between them
+///
+/// Returns `None` if `s` starts or ends with a separator.
+fn tokenize(s: &str) -> Option<(Vec<&str>, Vec<&str>)> {
+    let mut parts = Vec::new();
+    let mut separators = Vec::new();
+    let mut start = 0;
+    let mut in_part = true;
+    for (i, c) in s.char_indices() {
+        let alnum = c.is_ascii_alphanumeric();
+        if alnum != in_part {
+            if in_part {
+                parts.push(&s[start..i]);
+            } else {
+                separators.push(&s[start..i]);
+            }
+            start = i;
+            in_part = alnum;
+        }
+    }
+    if !in_part || s.is_empty() || parts.len() != separators.len() {
+        return None;
+    }
+    parts.push(&s[start..]);
+    parts.first().filter(|p| !p.is_empty())?;
+    Some((parts, separators))
+}
+
+fn is_digits(s: &str, lens: RangeInclusive<usize>) -> bool {
+    lens.contains(&s.len()) && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn month_name(s: &str) -> Option<(u32, &'static str)> {
+    let lower = s.to_ascii_lowercase();
+    MONTHS.iter().zip(1..).find_map(|(name, number)| {
+        if lower == *name {
+            Some((number, "%B"))
+        } else if lower.len() == 3 && name.starts_with(&lower) {
+            Some((number, "%b"))
+        } else {
+            None
+        }
+    })
+}
+
+fn weekday_name(s: &str) -> Option<(Weekday, &'static str)> {
+    let lower = s.to_ascii_lowercase();
+    WEEKDAYS.iter().find_map(|(name, weekday)| {
+        if lower == *name {
+            Some((*weekday, "%A"))
+        } else if lower.len() == 3 && name.starts_with(&lower) {
+            Some((*weekday, "%a"))
+        } else {
+            None
+        }
+    })
+}
+
+/// Two-digit years pivot as POSIX `%y` does
+fn expand_year(two_digits: i32) -> i32 {
+    if two_digits < 69 {
+        2000 + two_digits
+    } else {
+        1900 + two_digits
+    }
+}
+
+/// `%d` for a zero-padded number, `%-d` otherwise
+fn numeric_spec(part: &str, spec: char) -> String {
+    if part.len() == 2 {
+        format!("%{}", spec)
+    } else {
+        format!("%-{}", spec)
+    }
+}
+
+fn iso_week(year: &str, week: &str, day: Option<&str>) -> Option<NaiveDate> {
+    let weekday = match day {
+        Some(day) => Weekday::try_from(day.parse::<u8>().ok()?.checked_sub(1)?).ok()?,
+        None => Weekday::Mon,
+    };
+    NaiveDate::from_isoywd_opt(year.parse().ok()?, week.parse().ok()?, weekday)
+}
+
+fn parse_date(s: &str) -> Vec<DateReading> {
+    let Some((mut parts, mut separators)) = tokenize(s) else {
+        return Vec::new();
+    };
+
+    let mut prefix = String::new();
+    let mut weekday = None;
+    if parts.len() > 1 {
+        if let Some((wd, spec)) = weekday_name(parts[0]) {
+            weekday = Some(wd);
+            prefix = format!("{}{}", spec, separators[0]);
+            parts.remove(0);
+            separators.remove(0);
+        }
+    }
+
+    // Each candidate is the date, one spec per part, and an interpretation
+    let mut candidates: Vec<(Option<NaiveDate>, Vec<String>, &'static str)> = Vec::new();
+    let ymd =
+        |y: i32, m: &str, d: &str| NaiveDate::from_ymd_opt(y, m.parse().ok()?, d.parse().ok()?);
+    match parts.as_slice() {
+        [single] if is_digits(single, 8..=8) => {
+            let (a, b, c) = (&single[..4], &single[4..6], &single[6..]);
+            let (d, e, f) = (&single[..2], &single[2..4], &single[4..]);
+            let year = |y: &str| y.parse::<i32>().ok();
+            candidates.push((
+                year(a).and_then(|y| ymd(y, b, c)),
+                vec!["%Y%m%d".into()],
+                "year-month-day",
+            ));
+            candidates.push((
+                year(f).and_then(|y| ymd(y, d, e)),
+                vec!["%m%d%Y".into()],
+                "month/day/year",
+            ));
+            candidates.push((
+                year(f).and_then(|y| ymd(y, e, d)),
+                vec!["%d%m%Y".into()],
+                "day/month/year",
+            ));
+        }
+        [single] if is_digits(single, 7..=7) => {
+            let date = single[..4]
+                .parse()
+                .ok()
+                .and_then(|y| NaiveDate::from_yo_opt(y, single[4..].parse().ok()?));
+            candidates.push((date, vec!["%Y%j".into()], "year and day of year"));
+        }
+        [single] if single.len() >= 7 && single.as_bytes()[4].eq_ignore_ascii_case(&b'W') => {
+            let (year, rest) = (&single[..4], &single[5..]);
+            if is_digits(year, 4..=4) && is_digits(rest, 2..=3) {
+                let day = rest.get(2..).filter(|d| !d.is_empty());
+                let spec = if day.is_some() { "%GW%V%u" } else { "%GW%V" };
+                candidates.push((
+                    iso_week(year, &rest[..2], day),
+                    vec![spec.into()],
+                    "ISO week date",
+                ));
+            }
+        }
+        [year, ordinal] if is_digits(year, 4..=4) && is_digits(ordinal, 3..=3) => {
+            let date = year
+                .parse()
+                .ok()
+                .and_then(|y| NaiveDate::from_yo_opt(y, ordinal.parse().ok()?));
+            candidates.push((date, vec!["%Y".into(), "%j".into()], "year and day of year"));
+        }
+        [year, week, rest @ ..]
+            if is_digits(year, 4..=4)
+                && week.len() == 3
+                && week.as_bytes()[0].eq_ignore_ascii_case(&b'W')
+                && is_digits(&week[1..], 2..=2)
+                && (rest.is_empty() || is_digits(rest[0], 1..=1))
+                && rest.len() <= 1 =>
+        {
+            let mut specs = vec!["%G".into(), "W%V".into()];
+            if !rest.is_empty() {
+                specs.push("%u".into());
+            }
+            candidates.push((
+                iso_week(year, &week[1..], rest.first().copied()),
+                specs,
+                "ISO week date",
+            ));
+        }
+        [a, b, c] => {
+            let year4 = |y: &str| is_digits(y, 4..=4).then(|| y.parse::<i32>().ok()).flatten();
+            if let Some((month, spec)) = month_name(b) {
+                // 15 Mar 2024, 5-Mar-2024, 2024-Mar-15
+                if let (true, Some(y)) = (is_digits(a, 1..=2), year4(c)) {
+                    candidates.push((
+                        a.parse()
+                            .ok()
+                            .and_then(|d| NaiveDate::from_ymd_opt(y, month, d)),
+                        vec![numeric_spec(a, 'd'), spec.into(), "%Y".into()],
+                        "day, month name, year",
+                    ));
+                } else if let (Some(y), true) = (year4(a), is_digits(c, 1..=2)) {
+                    candidates.push((
+                        c.parse()
+                            .ok()
+                            .and_then(|d| NaiveDate::from_ymd_opt(y, month, d)),
+                        vec!["%Y".into(), spec.into(), numeric_spec(c, 'd')],
+                        "year, month name, day",
+                    ));
+                }
+            } else if let Some((month, spec)) = month_name(a) {
+                // March 15, 2024
+                if let (true, Some(y)) = (is_digits(b, 1..=2), year4(c)) {
+                    candidates.push((
+                        b.parse()
+                            .ok()
+                            .and_then(|d| NaiveDate::from_ymd_opt(y, month, d)),
+                        vec![spec.into(), numeric_spec(b, 'd'), "%Y".into()],
+                        "month name, day, year",
+                    ));
+                }
+            } else if let (Some(y), true, true) =
+                (year4(a), is_digits(b, 1..=2), is_digits(c, 1..=2))
+            {
+                candidates.push((
+                    ymd(y, b, c),
+                    vec!["%Y".into(), numeric_spec(b, 'm'), numeric_spec(c, 'd')],
+                    "year-month-day",
+                ));
+            } else if is_digits(a, 1..=2) && is_digits(b, 1..=2) {
+                let (year, year_spec, mdy, dmy) = if let Some(y) = year4(c) {
+                    (Some(y), "%Y", "month/day/year", "day/month/year")
+                } else if is_digits(c, 2..=2) {
+                    (
+                        c.parse().ok().map(expand_year),
+                        "%y",
+                        "month/day/two-digit year",
+                        "day/month/two-digit year",
+                    )
+                } else {
+                    (None, "", "", "")
+                };
+                if let Some(y) = year {
+                    candidates.push((
+                        ymd(y, a, b),
+                        vec![numeric_spec(a, 'm'), numeric_spec(b, 'd'), year_spec.into()],
+                        mdy,
+                    ));
+                    candidates.push((
+                        ymd(y, b, a),
+                        vec![numeric_spec(a, 'd'), numeric_spec(b, 'm'), year_spec.into()],
+                        dmy,
+                    ));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    candidates
+        .into_iter()
+        .filter_map(|(date, specs, interpretation)| {
+            let date = date?;
+            if weekday.is_some_and(|wd| wd != date.weekday()) {
+                return None;
+            }
+            let mut format = prefix.clone();
+            for (i, spec) in specs.iter().enumerate() {
+                if i > 0 {
+                    format.push_str(separators.get(i - 1).copied().unwrap_or(""));
+                }
+                format.push_str(spec);
+            }
+            Some(DateReading {
+                date,
+                format,
+                interpretation,
+            })
+        })
+        .collect()
+}
+
+/// A time of day read from the time part of the input
+struct TimeReading {
+    time: NaiveTime,
+    offset: Option<FixedOffset>,
+    format: String,
+    interpretation: &'static str,
+}
+
+/// Parse a trailing UTC offset such as `+01:00`, `+0100` or `+01`
+fn parse_offset(s: &str) -> Option<(FixedOffset, &'static str)> {
+    let sign = match s.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let digits = &s[1..];
+    let (hours, minutes, spec) = match digits.len() {
+        2 => (digits, "00", "%:::z"),
+        4 => (&digits[..2], &digits[2..], "%z"),
+        5 if digits.as_bytes()[2] == b':' => (&digits[..2], &digits[3..], "%:z"),
+        _ => return None,
+    };
+    if !is_digits(hours, 2..=2) || !is_digits(minutes, 2..=2) {
+        return None;
+    }
+    let seconds = hours.parse::<i32>().ok()? * 3600 + minutes.parse::<i32>().ok()? * 60;
+    Some((FixedOffset::east_opt(sign * seconds)?, spec))
+}
+
+fn parse_time(s: &str) -> Option<TimeReading> {
+    let mut rest = s.trim();
+    let mut suffix = String::new();
+
+    // Offset: Z, UTC/GMT, or a signed offset, attached or after a space
+    let mut offset = None;
+    if let Some(stripped) = rest.strip_suffix(['Z', 'z']) {
+        offset = Some(FixedOffset::east_opt(0)?);
+        suffix = "Z".into();
+        rest = stripped;
+    } else if let Some(i) = rest.rfind([' ', '+', '-']) {
+        let token = rest[i..].trim_start();
+        let head = rest[..i].trim_end();
+        let gap = &rest[head.len()..rest.len() - token.len()];
+        let spec = if token.eq_ignore_ascii_case("utc") || token.eq_ignore_ascii_case("gmt") {
+            offset = Some(FixedOffset::east_opt(0)?);
+            Some(token)
+        } else {
+            parse_offset(token).map(|(fixed, spec)| {
+                offset = Some(fixed);
+                spec
+            })
+        };
+        if let Some(spec) = spec {
+            suffix = format!("{}{}", gap, spec);
+            rest = head;
+        }
+    }
+
+    // Meridiem: am/pm in either case, attached or after a space
+    let lower = rest.to_ascii_lowercase();
+    let meridiem = ["am", "pm"].into_iter().find(|m| lower.ends_with(m));
+    if meridiem.is_some() {
+        let marker = &rest[rest.len() - 2..];
+        let spec = if marker == marker.to_ascii_uppercase() {
+            "%p"
+        } else {
+            "%P"
+        };
+        let head = rest[..rest.len() - 2].trim_end();
+        suffix = format!("{}{}{}", &rest[head.len()..rest.len() - 2], spec, suffix);
+        rest = head;
+    }
+
+    let (clock, fraction) = match rest.split_once(['.', ',']) {
+        Some((clock, fraction)) if is_digits(fraction, 1..=9) => (clock, Some(fraction)),
+        Some(_) => return None,
+        None => (rest, None),
+    };
+    let pieces: Vec<&str> = clock.split(':').collect();
+    let (hour, minute, second) = match pieces.as_slice() {
+        [h] if meridiem.is_some() && fraction.is_none() => (*h, "00", None),
+        [h, m] if fraction.is_none() => (*h, *m, None),
+        [h, m, s] => (*h, *m, Some(*s)),
+        _ => return None,
+    };
+    if !is_digits(hour, 1..=2) || !is_digits(minute, 2..=2) {
+        return None;
+    }
+    if second.is_some_and(|s| !is_digits(s, 2..=2)) {
+        return None;
+    }
+    let mut h: u32 = hour.parse().ok()?;
+    let twelve_hour = meridiem.is_some();
+    if twelve_hour {
+        if !(1..=12).contains(&h) {
+            return None;
+        }
+        h = h % 12 + if meridiem == Some("pm") { 12 } else { 0 };
+    }
+    let nanos = match fraction {
+        Some(f) => format!("{:0<9}", f).parse().ok()?,
+        None => 0,
+    };
+    let time = NaiveTime::from_hms_nano_opt(
+        h,
+        minute.parse().ok()?,
+        second.map_or(Some(0), |s| s.parse().ok())?,
+        nanos,
+    )?;
+
+    let mut format = numeric_spec(hour, if twelve_hour { 'I' } else { 'H' });
+    if pieces.len() > 1 {
+        format.push_str(":%M");
+    }
+    if second.is_some() {
+        format.push_str(":%S");
+    }
+    if fraction.is_some() {
+        format.push_str("%.f");
+    }
+    format.push_str(&suffix);
+    Some(TimeReading {
+        time,
+        offset,
+        format,
+        interpretation: if twelve_hour {
+            "12-hour clock"
+        } else {
+            "24-hour clock"
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timestamp::from_chrono_fields;
+    use chrono::SecondsFormat;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().to_utc()
+    }
+
+    fn instants(input: &str) -> Vec<String> {
+        detect(input, Zone::default())
+            .unwrap()
+            .iter()
+            .map(|r| r.instant.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+            .collect()
+    }
+
+    #[test]
+    fn test_mm_dd_vs_dd_mm_is_ambiguous() {
+        let readings = detect("03/04/24 5pm", Zone::default()).unwrap();
+        assert_eq!(readings.len(), 2);
+        assert_eq!(readings[0].instant, utc("2024-03-04T17:00:00Z"));
+        assert_eq!(readings[0].formats, ["%m/%d/%y %-I%P"]);
+        assert_eq!(
+            readings[0].interpretation,
+            "month/day/two-digit year, 12-hour clock, read in UTC"
+        );
+        assert_eq!(readings[1].instant, utc("2024-04-03T17:00:00Z"));
+    }
+
+    #[test]
+    fn test_same_day_and_month_is_unique() {
+        let readings = detect("03/03/2024", Zone::default()).unwrap();
+        assert_eq!(readings.len(), 1);
+        assert_eq!(readings[0].formats, ["%m/%d/%Y", "%d/%m/%Y"]);
+    }
+
+    #[test]
+    fn test_invalid_month_rules_out_reading() {
+        assert_eq!(instants("15/03/2024"), ["2024-03-15T00:00:00Z"]);
+    }
+
+    #[test]
+    fn test_rfc2822_merges_with_generic_reading() {
+        let readings = detect("Fri, 15 Mar 2024 12:30:45 +0000", Zone::default()).unwrap();
+        assert_eq!(readings.len(), 1);
+        assert_eq!(readings[0].formats[0], "RFC 2822");
+        assert!(readings[0]
+            .formats
+            .contains(&"%a, %d %b %Y %H:%M:%S %z".to_string()));
+        assert_eq!(readings[0].fields, ["rfc2822_date_format"]);
+    }
+
+    #[test]
+    fn test_wrong_weekday_is_rejected() {
+        assert!(detect("Thu, 15 Mar 2024", Zone::default()).is_err());
+    }
+
+    #[test]
+    fn test_seconds_vs_milliseconds() {
+        let readings = detect("171050404512", Zone::default()).unwrap();
+        assert_eq!(readings.len(), 2);
+        assert_eq!(readings[0].interpretation, "Unix time in seconds");
+        assert_eq!(readings[0].instant.year(), 7390);
+        assert_eq!(readings[1].interpretation, "Unix time in milliseconds");
+        assert_eq!(readings[1].instant, utc("1975-06-03T18:00:04.512Z"));
+    }
+
+    #[test]
+    fn test_thirteen_digits_are_milliseconds() {
+        // Read as seconds, this would fall in the year 56173
+        assert_eq!(instants("1710504045123"), ["2024-03-15T12:00:45.123Z"]);
+    }
+
+    #[test]
+    fn test_ten_digit_unix_time_is_seconds_only() {
+        assert_eq!(instants("1710504045"), ["2024-03-15T12:00:45Z"]);
+    }
+
+    #[test]
+    fn test_iso_week_and_ordinal_dates() {
+        assert_eq!(instants("2024-W11-5"), ["2024-03-15T00:00:00Z"]);
+        assert_eq!(instants("2024W115"), ["2024-03-15T00:00:00Z"]);
+        assert_eq!(instants("2024-W11"), ["2024-03-11T00:00:00Z"]);
+        assert_eq!(instants("2024-075"), ["2024-03-15T00:00:00Z"]);
+    }
+
+    #[test]
+    fn test_month_names() {
+        assert_eq!(instants("March 15, 2024"), ["2024-03-15T00:00:00Z"]);
+        assert_eq!(instants("15 mar 2024 17:30"), ["2024-03-15T17:30:00Z"]);
+        assert_eq!(instants("2024-Mar-15"), ["2024-03-15T00:00:00Z"]);
+    }
+
+    #[test]
+    fn test_time_forms() {
+        assert_eq!(instants("2024-03-15 5:30 PM"), ["2024-03-15T17:30:00Z"]);
+        assert_eq!(instants("2024-03-15 12am"), ["2024-03-15T00:00:00Z"]);
+        assert_eq!(
+            instants("2024-03-15 12:30:45.5 +01:00"),
+            ["2024-03-15T11:30:45.500Z"]
+        );
+        assert_eq!(instants("2024-03-15T12:30 GMT"), ["2024-03-15T12:30:00Z"]);
+    }
+
+    #[test]
+    fn test_native_digits() {
+        assert_eq!(instants("٢٠٢٤-٠٣-١٥ ١٧:٣٠"), ["2024-03-15T17:30:00Z"]);
+        assert_eq!(instants("۱۷۱۰۵۰۴۰۴۵"), ["2024-03-15T12:00:45Z"]);
+        assert_eq!(instants("१५ mar २०२४"), ["2024-03-15T00:00:00Z"]);
+    }
+
+    #[test]
+    fn test_local_time_read_in_zone_and_fold() {
+        let zone = Zone::parse("Europe/Berlin").unwrap();
+        let readings = detect("2024-10-27 02:30", zone).unwrap();
+        assert_eq!(readings.len(), 2);
+        assert_eq!(readings[0].instant, utc("2024-10-27T00:30:00Z"));
+        assert_eq!(readings[1].instant, utc("2024-10-27T01:30:00Z"));
+    }
+
+    #[test]
+    fn test_garbage_is_unrecognized() {
+        for input in [
+            "",
+            "   ",
+            "next tuesday",
+            "2024-13-45",
+            "12:30",
+            "2024-03-15 25:00",
+        ] {
+            assert!(
+                matches!(
+                    detect(input, Zone::default()),
+                    Err(ApiError::UnrecognizedTimestamp { .. })
+                ),
+                "{:?} should be unrecognized",
+                input
+            );
+        }
+        let long = "1".repeat(MAX_INPUT_LEN + 1);
+        assert!(detect(&long, Zone::default()).is_err());
+    }
+
+    /// Every field that identifies a date or instant parses back to it
+    #[test]
+    fn test_every_emitted_format_parses_back() {
+        const ROUND_TRIP: &[&str] = &[
+            "yyyy_mm_dd",
+            "mm_dd_yyyy",
+            "dd_mm_yyyy",
+            "yyyymmdd",
+            "mmddyyyy",
+            "ddmmyyyy",
+            "yyyymmdd_hyphenated",
+            "mmddyyyy_hyphenated",
+            "ddmmyyyy_hyphenated",
+            "unix_timestamp",
+            "rfc2822_date_format",
+            "rfc3339_date_format",
+            "rfc3339_date_format_millis",
+            "rfc3339_date_format_millis_z",
+            "rfc3339_date_format_secs",
+            "rfc3339_date_format_secs_z",
+            "rfc3339_date_format_micros",
+            "rfc3339_date_format_micros_z",
+            "rfc3339_date_format_nanos",
+            "rfc3339_date_format_nanos_z",
+            "rfc3339_date_format_autosi",
+            "rfc3339_date_format_autosi_z",
+            "iso_week_date_format",
+            "mdy_format",
+            "locale_date",
+            "full_iso",
+            "verbose_date",
+        ];
+        let fields: Vec<Field> = ROUND_TRIP.iter().map(|f| f.parse().unwrap()).collect();
+        for tz in ["UTC", "Asia/Kolkata", "America/New_York"] {
+            let zone = Zone::parse(tz).unwrap();
+            for instant in [utc("2024-03-05T09:07:03Z"), utc("1999-12-31T23:59:59Z")] {
+                let dt = zone.at(instant);
+                let midnight = resolve(zone, dt.date_naive().and_time(NaiveTime::MIN))
+                    .unwrap()
+                    .0;
+                for (name, value) in from_chrono_fields(&dt, &fields) {
+                    let text = match value {
+                        Value::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    let readings = detect(&text, zone).unwrap();
+                    let expected = if name.starts_with("rfc") || name == "unix_timestamp" {
+                        instant
+                    } else {
+                        midnight.to_utc()
+                    };
+                    let reading = readings.iter().find(|r| r.instant == expected);
+                    assert!(reading.is_some(), "{} = {:?} in {}", name, text, tz);
+                    assert!(
+                        reading.unwrap().fields.contains(&name.as_str()),
+                        "{} not listed for {:?}",
+                        name,
+                        text
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,293 @@
+//! Validation and rendering of caller-supplied strftime patterns.
+//!
+//! chrono reports an unknown specifier only when the pattern is displayed,
+//! as an `fmt::Error` halfway through rendering. Patterns are therefore
+//! tokenized up front against the specifiers chrono can format, so a bad
+//! token is reported by name before any output is produced. Specifiers that
+//! abbreviate the year are refused for years they cannot abbreviate, where
+//! chrono would write garbage.
+
+use std::fmt::{self, Display, Write};
+
+use chrono::{DateTime, Datelike, TimeZone};
+
+use crate::error::ApiError;
+
+/// Longest pattern accepted, in bytes
+pub const MAX_PATTERN_LEN: usize = 256;
+
+/// Largest rendered output, in bytes
+pub const MAX_OUTPUT_LEN: usize = 4096;
+
+/// Specifiers that take a single conversion letter
+const LETTERS: &str = "YCymbBhdeaAwuUWGgVjDxFvHkIlPpMSfRTXrZzcs+tn%";
+
+/// Specifiers that accept a `-`, `_` or `0` padding modifier
+const PADDABLE: &str = "YCymdewuUWGgVjHkIlMSs";
+
+/// Specifiers that write the century or a two-digit year, which only years
+/// 0000 to 9999 have; `%g` is the ISO week-based year
+const TWO_DIGIT_YEAR: &str = "CygDx";
+
+/// Check that every `%` specifier in `pattern` is one chrono can format
+///
+/// # Errors
+///
+/// Returns [`ApiError::PatternTooLong`] if the pattern exceeds
+/// [`MAX_PATTERN_LEN`], or [`ApiError::InvalidPattern`] naming the first
+/// unsupported token and its byte offset.
+///
+/// # Examples
+///
+/// ```
+/// use thedate::pattern::validate;
+///
+/// assert!(validate("%Y-%m-%dT%H").is_ok());
+/// assert!(validate("%-d %B, %:z").is_ok());
+/// assert!(validate("%Q").is_err());
+/// ```
+pub fn validate(pattern: &str) -> Result<(), ApiError> {
+    if pattern.len() > MAX_PATTERN_LEN {
+        return Err(ApiError::PatternTooLong {
+            max: MAX_PATTERN_LEN,
+        });
+    }
+    specifiers(pattern).map(|_| ())
+}
+
+/// Byte offset and length of every `%` specifier in `pattern`
+fn specifiers(pattern: &str) -> Result<Vec<(usize, usize)>, ApiError> {
+    let mut found = Vec::new();
+    let bytes = pattern.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'%' {
+            i += 1;
+            continue;
+        }
+        let len = specifier_len(&bytes[i + 1..]).ok_or_else(|| {
+            let end = (i + 1..=pattern.len())
+                .find(|&end| end > i + 1 && pattern.is_char_boundary(end))
+                .unwrap_or(pattern.len());
+            ApiError::InvalidPattern {
+                token: pattern[i..end].to_string(),
+                position: i,
+            }
+        })?;
+        found.push((i, 1 + len));
+        i += 1 + len;
+    }
+    Ok(found)
+}
+
+/// Refuse a century or two-digit year specifier where `dt`'s year, or its
+/// ISO week-based year for `%g`, is outside 0000 to 9999
+fn check_years<Tz: TimeZone>(dt: &DateTime<Tz>, pattern: &str) -> Result<(), ApiError> {
+    for (position, len) in specifiers(pattern)? {
+        let token = &pattern[position..position + len];
+        let spec = char::from(token.as_bytes()[len - 1]);
+        let year = if spec == 'g' {
+            dt.iso_week().year()
+        } else {
+            dt.year()
+        };
+        if TWO_DIGIT_YEAR.contains(spec) && !(0..=9999).contains(&year) {
+            return Err(ApiError::UnrepresentableYear {
+                token: token.to_string(),
+                position,
+                year,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Length of the valid specifier at the start of `rest` (just after `%`)
+fn specifier_len(rest: &[u8]) -> Option<usize> {
+    let is = |set: &str, b: &u8| b.is_ascii() && set.as_bytes().contains(b);
+    match rest {
+        [pad, spec, ..] if is("-_0", pad) && is(PADDABLE, spec) => Some(2),
+        [b'.', b'3' | b'6' | b'9', b'f', ..] => Some(3),
+        [b'.', b'f', ..] | [b'3' | b'6' | b'9', b'f', ..] => Some(2),
+        [b':', b':', b':', b'z', ..] => Some(4),
+        [b':', b':', b'z', ..] => Some(3),
+        [b':', b'z', ..] => Some(2),
+        [spec, ..] if is(LETTERS, spec) => Some(1),
+        _ => None,
+    }
+}
+
+/// A `fmt::Write` sink that refuses to grow past a byte limit
+struct Bounded {
+    buf: String,
+    limit: usize,
+}
+
+impl Write for Bounded {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.buf.len() + s.len() > self.limit {
+            return Err(fmt::Error);
+        }
+        self.buf.push_str(s);
+        Ok(())
+    }
+}
+
+/// Validate `pattern` and render `dt` with it
+///
+/// # Errors
+///
+/// Any error from [`validate`], [`ApiError::UnrepresentableYear`] naming a
+/// century or two-digit year specifier (`%C`, `%y`, `%g`, `%D`, `%x`) when
+/// the year is outside 0000 to 9999, or [`ApiError::OutputTooLarge`] if the
+/// rendered text would exceed [`MAX_OUTPUT_LEN`].
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use thedate::pattern::render;
+///
+/// let dt = Utc.with_ymd_and_hms(2024, 3, 15, 12, 30, 45).unwrap();
+/// assert_eq!(render(&dt, "%Y-%m-%dT%H").unwrap(), "2024-03-15T12");
+/// ```
+pub fn render<Tz: TimeZone>(dt: &DateTime<Tz>, pattern: &str) -> Result<String, ApiError>
+where
+    Tz::Offset: Display,
+{
+    validate(pattern)?;
+    check_years(dt, pattern)?;
+    let mut out = Bounded {
+        buf: String::new(),
+        limit: MAX_OUTPUT_LEN,
+    };
+    write!(out, "{}", dt.format(pattern)).map_err(|_| ApiError::OutputTooLarge {
+        max: MAX_OUTPUT_LEN,
+    })?;
+    Ok(out.buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn dt() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 5, 9, 7, 3).unwrap()
+    }
+
+    fn bad_token(pattern: &str) -> (String, usize) {
+        match validate(pattern) {
+            Err(ApiError::InvalidPattern { token, position }) => (token, position),
+            other => panic!("expected InvalidPattern, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_renders_basic_pattern() {
+        assert_eq!(render(&dt(), "%Y-%m-%dT%H").unwrap(), "2024-03-05T09");
+    }
+
+    #[test]
+    fn test_padding_modifiers() {
+        assert_eq!(render(&dt(), "%-d|%_m|%0e").unwrap(), "5| 3|05");
+    }
+
+    #[test]
+    fn test_fractional_and_offset_specifiers() {
+        assert_eq!(
+            render(&dt(), "%.3f %6f %:z %::z %:::z").unwrap(),
+            ".000 000000 +00:00 +00:00:00 +00"
+        );
+    }
+
+    #[test]
+    fn test_literal_text_and_escapes() {
+        assert_eq!(render(&dt(), "100%% at %H:%M").unwrap(), "100% at 09:07");
+        assert_eq!(render(&dt(), "héllo ✓").unwrap(), "héllo ✓");
+    }
+
+    #[test]
+    fn test_every_accepted_specifier_renders() {
+        let mut patterns: Vec<String> = LETTERS.chars().map(|c| format!("%{}", c)).collect();
+        for pad in ['-', '_', '0'] {
+            patterns.extend(PADDABLE.chars().map(|c| format!("%{}{}", pad, c)));
+        }
+        for pattern in patterns {
+            assert!(render(&dt(), &pattern).is_ok(), "{} failed", pattern);
+        }
+    }
+
+    #[test]
+    fn test_unknown_specifier_is_named() {
+        assert_eq!(bad_token("%Y-%Q"), ("%Q".to_string(), 3));
+    }
+
+    #[test]
+    fn test_trailing_percent_is_rejected() {
+        assert_eq!(bad_token("%Y%"), ("%".to_string(), 2));
+    }
+
+    #[test]
+    fn test_padding_on_text_specifier_is_rejected() {
+        assert_eq!(bad_token("%-B"), ("%-".to_string(), 0));
+    }
+
+    #[test]
+    fn test_parse_only_specifier_is_rejected() {
+        assert_eq!(bad_token("%#z"), ("%#".to_string(), 0));
+    }
+
+    #[test]
+    fn test_multibyte_token_is_reported_whole() {
+        assert_eq!(bad_token("%é"), ("%é".to_string(), 0));
+    }
+
+    #[test]
+    fn test_two_digit_years_outside_0000_to_9999_are_refused() {
+        let year = |y| Utc.with_ymd_and_hms(y, 3, 15, 12, 0, 0).unwrap();
+        for (y, pattern, token, position) in [
+            (12345, "%C%y %x", "%C", 0),
+            (-44, "%x %D %C", "%x", 0),
+            (-44, "%Y %-y", "%-y", 3),
+            (10000, "%d.%m.%g", "%g", 6),
+        ] {
+            match render(&year(y), pattern) {
+                Err(ApiError::UnrepresentableYear {
+                    token: t,
+                    position: p,
+                    year: ry,
+                }) => assert_eq!((t.as_str(), p, ry), (token, position, y)),
+                other => panic!("{} in {}: {:?}", pattern, y, other),
+            }
+        }
+        assert_eq!(
+            render(&year(12345), "%Y %G %F %%y").unwrap(),
+            "+12345 +12345 +12345-03-15 %y"
+        );
+        assert_eq!(render(&year(0), "%C%y %x").unwrap(), "0000 03/15/00");
+        // 0000-01-01, a Saturday, is in the last week of ISO year -1
+        let first = Utc.with_ymd_and_hms(0, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(render(&first, "%y").unwrap(), "00");
+        assert!(render(&first, "%g").is_err());
+    }
+
+    #[test]
+    fn test_pattern_length_cap() {
+        let pattern = "x".repeat(MAX_PATTERN_LEN + 1);
+        assert!(matches!(
+            validate(&pattern),
+            Err(ApiError::PatternTooLong { .. })
+        ));
+    }
+
+    #[test]
+    fn test_output_length_cap() {
+        let dt = Utc.timestamp_opt(1709629623, 123_456_789).unwrap();
+        let pattern = "%+".repeat(MAX_PATTERN_LEN / 2);
+        assert!(matches!(
+            render(&dt, &pattern),
+            Err(ApiError::OutputTooLarge { .. })
+        ));
+    }
+}
@@ -0,0 +1,441 @@
+//! Speakable time and date phrases for voice and accessibility tooling.
+//!
+//! Three phrases are produced for a wall-clock time:
+//! - `exact`: the time to the minute, `twelve thirty-five p.m.`
+//! - `fuzzy`: the nearest five minutes, qualified with `just after` or
+//!   `almost` when more than 30 seconds away, `just after half past twelve
+//!   in the afternoon`
+//! - `date`: `Friday, the fifteenth of March, twenty twenty-four`
+//!
+//! Rounding to the nearest five minutes is shared by every language in
+//! [`FuzzyTime`]; the wording lives in a [`Phrasebook`]. Adding a language
+//! means implementing that trait and listing it in [`PHRASEBOOKS`]. A
+//! language without a phrasebook falls back to English, and the response
+//! names the language actually used.
+
+use chrono::{Datelike, NaiveDate, NaiveTime, Timelike, Weekday};
+use serde::Serialize;
+
+/// A time rounded to the nearest five minutes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FuzzyTime {
+    /// Hour of the day the phrase refers to, 0-23: 14 for `quarter to
+    /// three` at 14:45
+    pub hour: u32,
+    /// Minutes past `hour`, a multiple of 5 from 0 to 55
+    pub minute: u32,
+    pub relation: Relation,
+    /// Hour of the actual time, for periods such as `in the afternoon`
+    pub actual_hour: u32,
+}
+
+/// How the actual time relates to the rounded mark
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relation {
+    /// Within 30 seconds of the mark
+    At,
+    /// Up to two and a half minutes after the mark
+    JustAfter,
+    /// Up to two and a half minutes before the mark
+    Almost,
+}
+
+impl FuzzyTime {
+    /// Round `time` to the nearest five minutes; halfway rounds up
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::NaiveTime;
+    /// use thedate::spoken::{FuzzyTime, Relation};
+    ///
+    /// let fuzzy = FuzzyTime::new(NaiveTime::from_hms_opt(23, 58, 0).unwrap());
+    /// assert_eq!((fuzzy.hour, fuzzy.minute, fuzzy.relation), (0, 0, Relation::Almost));
+    /// ```
+    pub fn new(time: NaiveTime) -> Self {
+        let seconds = time.num_seconds_from_midnight() as i64;
+        let mark = (seconds + 150).div_euclid(300) * 300;
+        let offset = seconds - mark;
+        let relation = match offset {
+            -29..=29 => Relation::At,
+            o if o > 0 => Relation::JustAfter,
+            _ => Relation::Almost,
+        };
+        let mark = mark.rem_euclid(86_400) as u32;
+        FuzzyTime {
+            hour: mark / 3600,
+            minute: mark % 3600 / 60,
+            relation,
+            actual_hour: time.hour(),
+        }
+    }
+}
+
+/// Wording rules for one language
+pub trait Phrasebook: Sync {
+    /// ISO 639-1 code, matched against the requested locale's language
+    fn language(&self) -> &'static str;
+
+    /// The time to the minute
+    fn exact(&self, time: NaiveTime) -> String;
+
+    /// The time to the nearest five minutes
+    fn fuzzy(&self, time: FuzzyTime) -> String;
+
+    /// The full date with its weekday
+    fn date(&self, date: NaiveDate) -> String;
+}
+
+/// Every available phrasebook; the first is the fallback
+pub static PHRASEBOOKS: &[&dyn Phrasebook] = &[&English];
+
+/// Phrasebook for `language`, falling back to English
+pub fn phrasebook(language: &str) -> &'static dyn Phrasebook {
+    PHRASEBOOKS
+        .iter()
+        .copied()
+        .find(|book| book.language().eq_ignore_ascii_case(language))
+        .unwrap_or(PHRASEBOOKS[0])
+}
+
+/// Speakable phrases for one instant
+#[derive(Debug, Serialize)]
+pub struct Spoken {
+    /// Language the phrases are in
+    pub language: &'static str,
+    pub exact: String,
+    pub fuzzy: String,
+    pub date: String,
+}
+
+/// Speak `date` and `time` in `language`, or English when unavailable
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use thedate::spoken::speak;
+///
+/// let dt = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap().and_hms_opt(12, 35, 0).unwrap();
+/// let spoken = speak(dt.date(), dt.time(), "en");
+/// assert_eq!(spoken.exact, "twelve thirty-five p.m.");
+/// assert_eq!(spoken.date, "Friday, the fifteenth of March, twenty twenty-four");
+/// ```
+pub fn speak(date: NaiveDate, time: NaiveTime, language: &str) -> Spoken {
+    let book = phrasebook(language);
+    Spoken {
+        language: book.language(),
+        exact: book.exact(time),
+        fuzzy: book.fuzzy(FuzzyTime::new(time)),
+        date: book.date(date),
+    }
+}
+
+/// English wording
+pub struct English;
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+impl English {
+    /// `twenty-four`, `one hundred and five`, `two million`
+    pub fn cardinal(n: u64) -> String {
+        match n {
+            0..=19 => ONES[n as usize].to_string(),
+            20..=99 if n.is_multiple_of(10) => TENS[n as usize / 10].to_string(),
+            20..=99 => format!("{}-{}", TENS[n as usize / 10], ONES[n as usize % 10]),
+            100..=999 => Self::scaled(n, 100, "hundred", " and "),
+            1_000..=999_999 => Self::scaled(n, 1_000, "thousand", Self::joiner(n % 1_000)),
+            1_000_000..=999_999_999 => {
+                Self::scaled(n, 1_000_000, "million", Self::joiner(n % 1_000_000))
+            }
+            _ => Self::scaled(n, 1_000_000_000, "billion", Self::joiner(n % 1_000_000_000)),
+        }
+    }
+
+    /// `and` before a final part below one hundred: `one thousand and five`
+    fn joiner(rest: u64) -> &'static str {
+        if rest < 100 {
+            " and "
+        } else {
+            " "
+        }
+    }
+
+    fn scaled(n: u64, unit: u64, name: &str, joiner: &str) -> String {
+        let head = format!("{} {}", Self::cardinal(n / unit), name);
+        match n % unit {
+            0 => head,
+            rest => format!("{}{}{}", head, joiner, Self::cardinal(rest)),
+        }
+    }
+
+    /// `first`, `twelfth`, `twenty-third`
+    pub fn ordinal(n: u64) -> String {
+        let cardinal = Self::cardinal(n);
+        let split = cardinal.rfind([' ', '-']).map_or(0, |i| i + 1);
+        let (head, last) = cardinal.split_at(split);
+        let last = match last {
+            "one" => "first".to_string(),
+            "two" => "second".to_string(),
+            "three" => "third".to_string(),
+            "five" => "fifth".to_string(),
+            "eight" => "eighth".to_string(),
+            "nine" => "ninth".to_string(),
+            "twelve" => "twelfth".to_string(),
+            word if word.ends_with('y') => format!("{}ieth", &word[..word.len() - 1]),
+            word => format!("{}th", word),
+        };
+        format!("{}{}", head, last)
+    }
+
+    /// A year read in pairs: `nineteen ninety-nine`, `twenty twenty-four`,
+    /// `nineteen oh five`, `two thousand`, `twenty-one hundred`
+    pub fn year(year: i32) -> String {
+        if year <= 0 {
+            return format!("{} BC", Self::cardinal(u64::from(year.unsigned_abs()) + 1));
+        }
+        let year = year as u64;
+        let (high, low) = (year / 100, year % 100);
+        match year {
+            _ if !(1000..=9999).contains(&year) => Self::cardinal(year),
+            _ if year % 1000 < 10 => Self::cardinal(year).replace(" and ", " "),
+            _ if low == 0 => format!("{} hundred", Self::cardinal(high)),
+            _ if low < 10 => format!("{} oh {}", Self::cardinal(high), ONES[low as usize]),
+            _ => format!("{} {}", Self::cardinal(high), Self::cardinal(low)),
+        }
+    }
+
+    /// Twelve-hour clock hour: 0 and 12 are `twelve`
+    fn hour(hour: u32) -> &'static str {
+        ONES[match hour % 12 {
+            0 => 12,
+            h => h as usize,
+        }]
+    }
+
+    fn period(hour: u32) -> &'static str {
+        match hour {
+            5..=11 => "in the morning",
+            12..=16 => "in the afternoon",
+            17..=20 => "in the evening",
+            _ => "at night",
+        }
+    }
+}
+
+impl Phrasebook for English {
+    fn language(&self) -> &'static str {
+        "en"
+    }
+
+    fn exact(&self, time: NaiveTime) -> String {
+        let (hour, minute) = (time.hour(), time.minute());
+        let suffix = if hour < 12 { "a.m." } else { "p.m." };
+        match (hour, minute) {
+            (0, 0) => "midnight".to_string(),
+            (12, 0) => "noon".to_string(),
+            (_, 0) => format!("{} {}", Self::hour(hour), suffix),
+            (_, 1..=9) => format!(
+                "{} oh {} {}",
+                Self::hour(hour),
+                ONES[minute as usize],
+                suffix
+            ),
+            _ => format!(
+                "{} {} {}",
+                Self::hour(hour),
+                Self::cardinal(u64::from(minute)),
+                suffix
+            ),
+        }
+    }
+
+    fn fuzzy(&self, time: FuzzyTime) -> String {
+        let next = (time.hour + 1) % 24;
+        let phrase = match (time.minute, time.hour) {
+            (0, 0) => "midnight".to_string(),
+            (0, 12) => "noon".to_string(),
+            (0, hour) => format!("{} o'clock", Self::hour(hour)),
+            (15, hour) => format!("quarter past {}", Self::hour(hour)),
+            (30, hour) => format!("half past {}", Self::hour(hour)),
+            (45, _) => format!("quarter to {}", Self::hour(next)),
+            (minute @ 5..=25, hour) => {
+                format!(
+                    "{} past {}",
+                    Self::cardinal(u64::from(minute)),
+                    Self::hour(hour)
+                )
+            }
+            (minute, _) => format!(
+                "{} to {}",
+                Self::cardinal(u64::from(60 - minute)),
+                Self::hour(next)
+            ),
+        };
+        let phrase = match time.relation {
+            Relation::At => phrase,
+            Relation::JustAfter => format!("just after {}", phrase),
+            Relation::Almost => format!("almost {}", phrase),
+        };
+        if phrase.ends_with("noon") || phrase.ends_with("midnight") {
+            phrase
+        } else {
+            format!("{} {}", phrase, Self::period(time.actual_hour))
+        }
+    }
+
+    fn date(&self, date: NaiveDate) -> String {
+        let weekday = match date.weekday() {
+            Weekday::Mon => "Monday",
+            Weekday::Tue => "Tuesday",
+            Weekday::Wed => "Wednesday",
+            Weekday::Thu => "Thursday",
+            Weekday::Fri => "Friday",
+            Weekday::Sat => "Saturday",
+            Weekday::Sun => "Sunday",
+        };
+        format!(
+            "{}, the {} of {}, {}",
+            weekday,
+            Self::ordinal(u64::from(date.day())),
+            MONTHS[date.month0() as usize],
+            Self::year(date.year())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(h: u32, m: u32, s: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, s).unwrap()
+    }
+
+    fn fuzzy(h: u32, m: u32, s: u32) -> String {
+        English.fuzzy(FuzzyTime::new(time(h, m, s)))
+    }
+
+    #[test]
+    fn test_exact() {
+        assert_eq!(English.exact(time(12, 35, 10)), "twelve thirty-five p.m.");
+        assert_eq!(English.exact(time(0, 0, 0)), "midnight");
+        assert_eq!(English.exact(time(12, 0, 59)), "noon");
+        assert_eq!(English.exact(time(15, 0, 0)), "three p.m.");
+        assert_eq!(English.exact(time(9, 5, 0)), "nine oh five a.m.");
+        assert_eq!(English.exact(time(0, 42, 0)), "twelve forty-two a.m.");
+    }
+
+    #[test]
+    fn test_fuzzy() {
+        assert_eq!(fuzzy(15, 15, 0), "quarter past three in the afternoon");
+        assert_eq!(
+            fuzzy(12, 32, 0),
+            "just after half past twelve in the afternoon"
+        );
+        assert_eq!(fuzzy(8, 48, 0), "almost ten to nine in the morning");
+        assert_eq!(fuzzy(18, 45, 20), "quarter to seven in the evening");
+        assert_eq!(fuzzy(21, 20, 0), "twenty past nine at night");
+        assert_eq!(fuzzy(11, 58, 0), "almost noon");
+        assert_eq!(fuzzy(0, 1, 0), "just after midnight");
+        assert_eq!(fuzzy(23, 57, 31), "almost midnight");
+        assert_eq!(fuzzy(7, 0, 0), "seven o'clock in the morning");
+        assert_eq!(fuzzy(10, 35, 0), "twenty-five to eleven in the morning");
+    }
+
+    #[test]
+    fn test_rounding_boundaries() {
+        // 2:30 past the mark rounds up to the next one
+        assert_eq!(
+            fuzzy(15, 2, 29),
+            "just after three o'clock in the afternoon"
+        );
+        assert_eq!(fuzzy(15, 2, 30), "almost five past three in the afternoon");
+        assert_eq!(fuzzy(15, 5, 29), "five past three in the afternoon");
+        assert_eq!(
+            fuzzy(15, 5, 30),
+            "just after five past three in the afternoon"
+        );
+    }
+
+    #[test]
+    fn test_cardinal_and_ordinal() {
+        assert_eq!(English::cardinal(0), "zero");
+        assert_eq!(English::cardinal(105), "one hundred and five");
+        assert_eq!(
+            English::cardinal(12_345),
+            "twelve thousand three hundred and forty-five"
+        );
+        assert_eq!(English::ordinal(1), "first");
+        assert_eq!(English::ordinal(12), "twelfth");
+        assert_eq!(English::ordinal(20), "twentieth");
+        assert_eq!(English::ordinal(23), "twenty-third");
+        assert_eq!(English::ordinal(31), "thirty-first");
+    }
+
+    #[test]
+    fn test_years() {
+        assert_eq!(English::year(2024), "twenty twenty-four");
+        assert_eq!(English::year(1999), "nineteen ninety-nine");
+        assert_eq!(English::year(1905), "nineteen oh five");
+        assert_eq!(English::year(1900), "nineteen hundred");
+        assert_eq!(English::year(2000), "two thousand");
+        assert_eq!(English::year(2007), "two thousand seven");
+        assert_eq!(English::year(2100), "twenty-one hundred");
+        assert_eq!(English::year(476), "four hundred and seventy-six");
+        assert_eq!(English::year(-43), "forty-four BC");
+    }
+
+    #[test]
+    fn test_date() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        assert_eq!(
+            English.date(date),
+            "Friday, the fifteenth of March, twenty twenty-four"
+        );
+    }
+
+    #[test]
+    fn test_unknown_language_falls_back_to_english() {
+        assert_eq!(phrasebook("de").language(), "en");
+        assert_eq!(phrasebook("EN").language(), "en");
+    }
+}
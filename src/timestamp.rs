@@ -1,84 +1,381 @@
 //! Timestamp data structure and conversion functions.
 //!
 //! Provides comprehensive date/time formatting across 61+ different formats.
+//!
+//! Every field is declared once in the `timestamp_fields!` table below, which
+//! generates both the [`Timestamp`] struct and the [`Field`] enum used to
+//! render a projection of just the fields a caller asked for.
+//!
+//! Years run over chrono's whole range, about 262,000 years either side of
+//! year 0, numbered astronomically: year 0 is 1 BCE and -44 is 45 BCE.
+//! Formats with four- or two-digit years, and the RFC formats, can only
+//! write years 0000 to 9999; outside that they are null, and
+//! [`Field::null_reason`] says why. `full_iso` and the ISO week formats use
+//! ISO 8601 expanded years instead, such as `+12345-01-01` and `-0044-03-15`.
 
-use chrono::{DateTime, Datelike, SecondsFormat, Timelike, Utc};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, NaiveDateTime, Offset, SecondsFormat, TimeZone, Timelike};
 use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::astronomy;
+use crate::era::{self, EraYear};
+use crate::error::ApiError;
+use crate::leap_seconds;
+use crate::zone::local_date;
+
+/// Why a format cannot write years outside 0000 to 9999
+const FOUR_DIGIT_YEAR: &str = "format has a four-digit year, so only years 0000 to 9999";
+pub(crate) const TWO_DIGIT_YEAR: &str = "two-digit years only abbreviate years 0000 to 9999";
+const RFC_YEAR: &str = "RFC 2822 and RFC 3339 years have four digits, so only 0000 to 9999";
+/// Why fields of the civil date are null where the zone's offset takes it
+/// out of range
+pub const LOCAL_DATE_RANGE: &str = "the date in this zone is outside the supported range";
+/// Why the era fields are null before the first era in the table
+const BEFORE_MEIJI: &str = "Japanese eras are counted here from Meiji, which began on 1868-10-23";
+/// Why the atomic time fields are null
+const BEFORE_LEAP_SECONDS: &str = "UTC has kept to TAI by leap seconds only since 1972";
+const BEFORE_GPS: &str = "GPS time began on 1980-01-06";
+const ATOMIC_RANGE: &str = "the reading on this time scale is past the end of the supported range";
+
+/// Rata Die of 1970-01-01
+const UNIX_EPOCH_RATA_DIE: i64 = 719_163;
+
+/// Whether formats limited to four-digit years can write `year`
+fn representable(year: i32) -> bool {
+    (0..=9999).contains(&year)
+}
+
+/// A TAI or TT reading in ISO 8601, without an offset since it is not UTC
+fn atomic_reading(reading: &NaiveDateTime) -> String {
+    reading.format("%Y-%m-%dT%H:%M:%S%.f").to_string()
+}
+
+/// Rata Die of the civil date of `dt`, from its wall-clock seconds, so it
+/// is defined even where the offset takes that date out of chrono's range
+fn rata_die<Tz: TimeZone>(dt: &DateTime<Tz>) -> i64 {
+    let seconds = dt.timestamp() + i64::from(dt.offset().fix().local_minus_utc());
+    seconds.div_euclid(86_400) + UNIX_EPOCH_RATA_DIE
+}
+
+/// Type of a field: optional when its format only covers some years
+macro_rules! field_type {
+    ($ty:ty) => { $ty };
+    ($ty:ty, $reason:expr) => { Option<$ty> };
+}
+
+/// Value of a field, or `None` when its format cannot write `dt`'s year
+macro_rules! field_value {
+    ($dt:ident, $value:expr) => {
+        $value
+    };
+    ($dt:ident, $value:expr, $reason:expr) => {
+        representable($dt.year()).then(|| $value)
+    };
+}
+
+macro_rules! timestamp_fields {
+    ($dt:ident => {
+        $( $name:ident : $ty:ty $([$reason:expr])? = $value:expr, )*
+    }) => {
+        /// Comprehensive timestamp representation with 61+ format variants
+        ///
+        /// Contains the same moment in time expressed in many different
+        /// date and time formats for maximum compatibility and utility.
+        #[derive(Serialize)]
+        pub struct Timestamp {
+            $( $name: field_type!($ty $(, $reason)?), )*
+        }
+
+        /// Name of a single [`Timestamp`] field
+        ///
+        /// Variants are named exactly like the JSON keys they select.
+        #[allow(non_camel_case_types)]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum Field {
+            $( $name, )*
+        }
 
-/// Comprehensive timestamp representation with 61+ format variants
+        impl Field {
+            /// Every field, in the order it appears in the full response
+            pub const ALL: &'static [Field] = &[ $( Field::$name, )* ];
+
+            /// The JSON key for this field
+            pub fn name(self) -> &'static str {
+                match self {
+                    $( Field::$name => stringify!($name), )*
+                }
+            }
+
+            /// Compute this field, and only this field, for `dt`
+            pub fn render<Tz: TimeZone>(self, $dt: &DateTime<Tz>) -> Value
+            where
+                Tz::Offset: Display,
+            {
+                match self {
+                    $( Field::$name => Value::from(field_value!($dt, $value $(, $reason)?)), )*
+                }
+            }
+
+            /// Why this field is null in `year`, when its format cannot
+            /// write that year
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use thedate::timestamp::Field;
+            ///
+            /// assert!(Field::yyyymmdd.null_reason(12345).is_some());
+            /// assert_eq!(Field::yyyymmdd.null_reason(2024), None);
+            /// assert_eq!(Field::full_iso.null_reason(12345), None);
+            /// ```
+            pub fn null_reason(self, year: i32) -> Option<&'static str> {
+                match self {
+                    $( Field::$name => {
+                        $( if !representable(year) {
+                            return Some($reason);
+                        } )?
+                        None
+                    } )*
+                }
+            }
+        }
+
+        fn build<Tz: TimeZone>($dt: &DateTime<Tz>) -> Timestamp
+        where
+            Tz::Offset: Display,
+        {
+            Timestamp {
+                $( $name: field_value!($dt, $value $(, $reason)?), )*
+            }
+        }
+    };
+}
+
+timestamp_fields!(dt => {
+    // Date formats (underscore-separated)
+    yyyy_mm_dd: String [FOUR_DIGIT_YEAR] = dt.format("%Y_%m_%d").to_string(),
+    mm_dd_yyyy: String [FOUR_DIGIT_YEAR] = dt.format("%m_%d_%Y").to_string(),
+    dd_mm_yyyy: String [FOUR_DIGIT_YEAR] = dt.format("%d_%m_%Y").to_string(),
+
+    // Date formats (no separator)
+    yyyymmdd: String [FOUR_DIGIT_YEAR] = dt.format("%Y%m%d").to_string(),
+    mmddyyyy: String [FOUR_DIGIT_YEAR] = dt.format("%m%d%Y").to_string(),
+    ddmmyyyy: String [FOUR_DIGIT_YEAR] = dt.format("%d%m%Y").to_string(),
+
+    // Date formats (hyphen-separated)
+    yyyymmdd_hyphenated: String [FOUR_DIGIT_YEAR] = dt.format("%Y-%m-%d").to_string(),
+    mmddyyyy_hyphenated: String [FOUR_DIGIT_YEAR] = dt.format("%m-%d-%Y").to_string(),
+    ddmmyyyy_hyphenated: String [FOUR_DIGIT_YEAR] = dt.format("%d-%m-%Y").to_string(),
+
+    // Numeric date components
+    week_number_of_the_year: u32 = dt.iso_week().week(),
+    day_of_the_year: u32 = dt.ordinal(),
+    unix_timestamp: i64 = dt.timestamp(),
+
+    // Time formats
+    military_time: String = dt.format("%H:%M").to_string(),
+    hh_mm_ss: String = dt.format("%H_%M_%S").to_string(),
+    am_pm_notation: String = dt.format("%p").to_string().to_lowercase(),
+
+    // Calculated fields
+    quarter_of_the_year: u32 = (dt.month() - 1) / 3 + 1,
+
+    // RFC formats
+    rfc2822_date_format: String [RFC_YEAR] = dt.to_rfc2822(),
+    rfc3339_date_format: String [RFC_YEAR] = dt.to_rfc3339(),
+    rfc3339_date_format_millis: String [RFC_YEAR] = dt.to_rfc3339_opts(SecondsFormat::Millis, false),
+    rfc3339_date_format_millis_z: String [RFC_YEAR] = dt.to_rfc3339_opts(SecondsFormat::Millis, true),
+    rfc3339_date_format_secs: String [RFC_YEAR] = dt.to_rfc3339_opts(SecondsFormat::Secs, false),
+    rfc3339_date_format_secs_z: String [RFC_YEAR] = dt.to_rfc3339_opts(SecondsFormat::Secs, true),
+    rfc3339_date_format_micros: String [RFC_YEAR] = dt.to_rfc3339_opts(SecondsFormat::Micros, false),
+    rfc3339_date_format_micros_z: String [RFC_YEAR] = dt.to_rfc3339_opts(SecondsFormat::Micros, true),
+    rfc3339_date_format_nanos: String [RFC_YEAR] = dt.to_rfc3339_opts(SecondsFormat::Nanos, false),
+    rfc3339_date_format_nanos_z: String [RFC_YEAR] = dt.to_rfc3339_opts(SecondsFormat::Nanos, true),
+    rfc3339_date_format_autosi: String [RFC_YEAR] = dt.to_rfc3339_opts(SecondsFormat::AutoSi, false),
+    rfc3339_date_format_autosi_z: String [RFC_YEAR] = dt.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+
+    // ISO week formats
+    iso_week_date_format: String = dt.format("%G-W%V-%u").to_string(),
+
+    // Numeric date/time components
+    month_of_the_year: u32 = dt.month(),
+    hour_of_the_day: u32 = dt.hour(),
+    minute_of_the_hour: u32 = dt.minute(),
+    second_of_the_minute: u32 = dt.second(),
+    iso_year_week_format: String = dt.format("%G-W%V").to_string(),
+    iso_year: i32 = dt.year(),
+    iso_week: u32 = dt.iso_week().week(),
+
+    // Weekday formats
+    weekday: String = dt.format("%A").to_string(),
+    weekday_short: String = dt.weekday().to_string(),
+    week: String = dt.iso_week().week().to_string(),
+
+    // Timezone
+    timezone_name: String = dt.format("%Z").to_string(),
+
+    // Year components
+    year_quad: String [FOUR_DIGIT_YEAR] = dt.format("%Y").to_string(),
+    century_duo: String [TWO_DIGIT_YEAR] = dt.format("%C").to_string(),
+    year_duo: String [TWO_DIGIT_YEAR] = dt.format("%y").to_string(),
+
+    // Month components
+    month_number: String = dt.format("%m").to_string(),
+    short_month: String = dt.format("%b").to_string(),
+    long_month: String = dt.format("%B").to_string(),
+
+    // Day components
+    day_duo: String = dt.format("%d").to_string(),
+    easy_day: String = dt.format("%e").to_string(),
+
+    // Weekday names and numbers
+    abbrev_weekday: String = dt.format("%a").to_string(),
+    weekday_index: String = dt.format("%w").to_string(),
+    iso_weekday: String = dt.format("%u").to_string(),
+
+    // Week numbers
+    us_week_num: String = dt.format("%U").to_string(),
+    work_week_num: String = dt.format("%W").to_string(),
+    iso_year_full: String = dt.format("%G").to_string(),
+    iso_year_duo: String [TWO_DIGIT_YEAR] = dt.format("%g").to_string(),
+    iso_week_num: String = dt.format("%V").to_string(),
+
+    // Miscellaneous formats
+    // Day of the year, whatever the name; see `julian_date`
+    julian_day: String = dt.format("%j").to_string(),
+    mdy_format: String [TWO_DIGIT_YEAR] = dt.format("%D").to_string(),
+    locale_date: String [TWO_DIGIT_YEAR] = dt.format("%x").to_string(),
+    full_iso: String = dt.format("%F").to_string(),
+    verbose_date: String [FOUR_DIGIT_YEAR] = dt.format("%v").to_string(),
+
+    // Era-based years, from the civil date, so null where the zone's offset
+    // takes it out of range
+    japanese_era: Option<String> =
+        local_date(dt).and_then(EraYear::of).map(|e| e.era.name.to_string()),
+    japanese_era_year: Option<u32> = local_date(dt).and_then(EraYear::of).map(|e| e.year),
+    japanese_era_kanji: Option<String> = local_date(dt).and_then(EraYear::of).map(|e| e.kanji()),
+    japanese_era_romaji: Option<String> =
+        local_date(dt).and_then(EraYear::of).map(|e| e.romaji()),
+    japanese_era_date: Option<String> =
+        local_date(dt).and_then(|date| Some(EraYear::of(date)?.abbreviated(date))),
+    roc_year: i32 = era::roc_year(dt.year()),
+    roc_year_chinese: String = era::roc_year_chinese(dt.year()),
+    era: String = if dt.year() > 0 { "CE" } else { "BCE" }.to_string(),
+    year_of_era: u32 = dt.year_ce().1,
+
+    // Astronomical day counts, from the instant except for the civil
+    // `rata_die`; sidereal times in hours
+    julian_date: f64 = astronomy::julian_date(dt.to_utc()),
+    modified_julian_date: f64 = astronomy::julian_date(dt.to_utc()) - astronomy::MJD_EPOCH,
+    truncated_julian_day: i64 =
+        (astronomy::julian_date(dt.to_utc()) - astronomy::TJD_EPOCH).floor() as i64,
+    rata_die: i64 = rata_die(dt),
+    j2000_days: f64 = astronomy::julian_date(dt.to_utc()) - astronomy::J2000_JULIAN_DATE,
+    greenwich_mean_sidereal_time: f64 = astronomy::greenwich_mean_sidereal_time(dt.to_utc()),
+    greenwich_apparent_sidereal_time: f64 =
+        astronomy::greenwich_apparent_sidereal_time(dt.to_utc()),
+
+    // Atomic time scales from the leap second table, null before 1972 and
+    // GPS time before 1980-01-06; TAI and TT readings have no zone
+    tai: Option<String> = leap_seconds::table().tai(dt.to_utc()).map(|tai| atomic_reading(&tai)),
+    terrestrial_time: Option<String> =
+        leap_seconds::table().tt(dt.to_utc()).map(|tt| atomic_reading(&tt)),
+    tai_utc_offset: Option<i64> = leap_seconds::table().offset(dt.to_utc()),
+    gps_week: Option<i64> = leap_seconds::table().gps(dt.to_utc()).map(|(week, _)| week),
+    gps_time_of_week: Option<f64> =
+        leap_seconds::table().gps(dt.to_utc()).map(|(_, seconds)| seconds),
+});
+
+impl Field {
+    /// Why this field is null at `dt`: [`Field::null_reason`] for its
+    /// year, [`LOCAL_DATE_RANGE`] for the era fields where the zone's offset
+    /// takes the civil date out of range or that it is before Meiji, or why
+    /// an atomic time scale has no reading
+    pub fn null_reason_at<Tz: TimeZone>(self, dt: &DateTime<Tz>) -> Option<&'static str> {
+        if let Some(reason) = self.null_reason(dt.year()) {
+            return Some(reason);
+        }
+        let table = leap_seconds::table();
+        let utc = dt.to_utc();
+        match self {
+            Field::japanese_era
+            | Field::japanese_era_year
+            | Field::japanese_era_kanji
+            | Field::japanese_era_romaji
+            | Field::japanese_era_date => match local_date(dt) {
+                None => Some(LOCAL_DATE_RANGE),
+                Some(date) => EraYear::of(date).is_none().then_some(BEFORE_MEIJI),
+            },
+            Field::tai | Field::terrestrial_time | Field::tai_utc_offset
+                if table.offset(utc).is_none() =>
+            {
+                Some(BEFORE_LEAP_SECONDS)
+            }
+            Field::tai => table.tai(utc).is_none().then_some(ATOMIC_RANGE),
+            Field::terrestrial_time => table.tt(utc).is_none().then_some(ATOMIC_RANGE),
+            Field::gps_week | Field::gps_time_of_week => {
+                table.gps(utc).is_none().then_some(BEFORE_GPS)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Field {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Field::ALL
+            .iter()
+            .copied()
+            .find(|field| field.name() == s)
+            .ok_or_else(|| ApiError::UnknownFields(vec![s.to_string()]))
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Parse a comma-separated `fields` list such as `unix_timestamp,full_iso`
+///
+/// Whitespace around names and empty entries are ignored, duplicates are
+/// dropped, and an empty list selects every field.
+///
+/// # Errors
 ///
-/// Contains the same moment in time expressed in many different
-/// date and time formats for maximum compatibility and utility.
-#[derive(Serialize)]
-pub struct Timestamp {
-    yyyy_mm_dd: String,
-    mm_dd_yyyy: String,
-    dd_mm_yyyy: String,
-    yyyymmdd: String,
-    mmddyyyy: String,
-    ddmmyyyy: String,
-    yyyymmdd_hyphenated: String,
-    mmddyyyy_hyphenated: String,
-    ddmmyyyy_hyphenated: String,
-    week_number_of_the_year: u32,
-    day_of_the_year: u32,
-    unix_timestamp: i64,
-    military_time: String,
-    hh_mm_ss: String,
-    am_pm_notation: String,
-    quarter_of_the_year: u32,
-    rfc2822_date_format: String,
-    rfc3339_date_format: String,
-    rfc3339_date_format_millis: String,
-    rfc3339_date_format_millis_z: String,
-    rfc3339_date_format_secs: String,
-    rfc3339_date_format_secs_z: String,
-    rfc3339_date_format_micros: String,
-    rfc3339_date_format_micros_z: String,
-    rfc3339_date_format_nanos: String,
-    rfc3339_date_format_nanos_z: String,
-    rfc3339_date_format_autosi: String,
-    rfc3339_date_format_autosi_z: String,
-    iso_week_date_format: String,
-    month_of_the_year: u32,
-    hour_of_the_day: u32,
-    minute_of_the_hour: u32,
-    second_of_the_minute: u32,
-    iso_year_week_format: String,
-    iso_year: i32,
-    iso_week: u32,
-    weekday: String,
-    weekday_short: String,
-    week: String,
-    timezone_name: String,
-    year_quad: String,
-    century_duo: String,
-    year_duo: String,
-    month_number: String,
-    short_month: String,
-    long_month: String,
-    day_duo: String,
-    easy_day: String,
-    abbrev_weekday: String,
-    weekday_index: String,
-    iso_weekday: String,
-    us_week_num: String,
-    work_week_num: String,
-    iso_year_full: String,
-    iso_year_duo: String,
-    iso_week_num: String,
-    julian_day: String,
-    mdy_format: String,
-    locale_date: String,
-    full_iso: String,
-    verbose_date: String,
+/// Returns [`ApiError::UnknownFields`] listing every name that is not a
+/// [`Timestamp`] field.
+pub fn parse_fields(list: &str) -> Result<Vec<Field>, ApiError> {
+    let mut fields = Vec::new();
+    let mut unknown = Vec::new();
+    for name in list.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        match name.parse::<Field>() {
+            Ok(field) if !fields.contains(&field) => fields.push(field),
+            Ok(_) => {}
+            Err(_) => unknown.push(name.to_string()),
+        }
+    }
+    if !unknown.is_empty() {
+        return Err(ApiError::UnknownFields(unknown));
+    }
+    if fields.is_empty() {
+        fields.extend_from_slice(Field::ALL);
+    }
+    Ok(fields)
 }
 
 /// Convert a chrono DateTime to a Timestamp with all format variants
 ///
 /// # Arguments
 ///
-/// * `dt` - A `DateTime<Utc>` to convert
+/// * `dt` - A `DateTime` in any time zone; every field is rendered in that
+///   zone, including `timezone_name`
 ///
 /// # Returns
 ///
@@ -94,109 +391,44 @@ pub struct Timestamp {
 /// let ts = from_chrono(dt);
 /// // ts now contains the timestamp in all 61+ formats
 /// ```
-pub fn from_chrono(dt: DateTime<Utc>) -> Timestamp {
-    Timestamp {
-        // Date formats (underscore-separated)
-        yyyy_mm_dd: dt.format("%Y_%m_%d").to_string(),
-        mm_dd_yyyy: dt.format("%m_%d_%Y").to_string(),
-        dd_mm_yyyy: dt.format("%d_%m_%Y").to_string(),
-
-        // Date formats (no separator)
-        yyyymmdd: dt.format("%Y%m%d").to_string(),
-        mmddyyyy: dt.format("%m%d%Y").to_string(),
-        ddmmyyyy: dt.format("%d%m%Y").to_string(),
-
-        // Date formats (hyphen-separated)
-        yyyymmdd_hyphenated: dt.format("%Y-%m-%d").to_string(),
-        mmddyyyy_hyphenated: dt.format("%m-%d-%Y").to_string(),
-        ddmmyyyy_hyphenated: dt.format("%d-%m-%Y").to_string(),
-
-        // Time formats
-        military_time: dt.format("%H:%M").to_string(),
-        hh_mm_ss: dt.format("%H_%M_%S").to_string(),
-        am_pm_notation: dt.format("%p").to_string().to_lowercase(),
-
-        // Calculated fields
-        quarter_of_the_year: (dt.month() - 1) / 3 + 1,
-
-        // RFC formats
-        rfc2822_date_format: dt.to_rfc2822(),
-        rfc3339_date_format: dt.to_rfc3339(),
-        rfc3339_date_format_millis: dt.to_rfc3339_opts(SecondsFormat::Millis, false),
-        rfc3339_date_format_millis_z: dt.to_rfc3339_opts(SecondsFormat::Millis, true),
-        rfc3339_date_format_secs: dt.to_rfc3339_opts(SecondsFormat::Secs, false),
-        rfc3339_date_format_secs_z: dt.to_rfc3339_opts(SecondsFormat::Secs, true),
-        rfc3339_date_format_micros: dt.to_rfc3339_opts(SecondsFormat::Micros, false),
-        rfc3339_date_format_micros_z: dt.to_rfc3339_opts(SecondsFormat::Micros, true),
-        rfc3339_date_format_nanos: dt.to_rfc3339_opts(SecondsFormat::Nanos, false),
-        rfc3339_date_format_nanos_z: dt.to_rfc3339_opts(SecondsFormat::Nanos, true),
-        rfc3339_date_format_autosi: dt.to_rfc3339_opts(SecondsFormat::AutoSi, false),
-        rfc3339_date_format_autosi_z: dt.to_rfc3339_opts(SecondsFormat::AutoSi, true),
-
-        // ISO week formats
-        iso_week_date_format: dt.format("%G-W%V-%u").to_string(),
-        iso_year_week_format: dt.format("%G-W%V").to_string(),
-
-        // Numeric date/time components
-        day_of_the_year: dt.ordinal(),
-        month_of_the_year: dt.month(),
-        week_number_of_the_year: dt.iso_week().week(),
-        unix_timestamp: dt.timestamp(),
-        hour_of_the_day: dt.hour(),
-        minute_of_the_hour: dt.minute(),
-        second_of_the_minute: dt.second(),
-        iso_year: dt.year(),
-        iso_week: dt.iso_week().week(),
-
-        // Weekday formats
-        weekday_short: dt.weekday().to_string(),
-        week: dt.iso_week().week().to_string(),
-
-        // Timezone
-        timezone_name: dt.format("%Z").to_string(),
-
-        // Year components
-        year_quad: dt.format("%Y").to_string(),
-        century_duo: dt.format("%C").to_string(),
-        year_duo: dt.format("%y").to_string(),
-
-        // Month components
-        month_number: dt.format("%m").to_string(),
-        short_month: dt.format("%b").to_string(),
-        long_month: dt.format("%B").to_string(),
-
-        // Day components
-        day_duo: dt.format("%d").to_string(),
-        easy_day: dt.format("%e").to_string(),
-
-        // Weekday names
-        abbrev_weekday: dt.format("%a").to_string(),
-        weekday: dt.format("%A").to_string(),
-
-        // Weekday numbers
-        weekday_index: dt.format("%w").to_string(),
-        iso_weekday: dt.format("%u").to_string(),
-
-        // Week numbers
-        us_week_num: dt.format("%U").to_string(),
-        work_week_num: dt.format("%W").to_string(),
-        iso_year_full: dt.format("%G").to_string(),
-        iso_year_duo: dt.format("%g").to_string(),
-        iso_week_num: dt.format("%V").to_string(),
-
-        // Miscellaneous formats
-        julian_day: dt.format("%j").to_string(),
-        mdy_format: dt.format("%D").to_string(),
-        locale_date: dt.format("%x").to_string(),
-        full_iso: dt.format("%F").to_string(),
-        verbose_date: dt.format("%v").to_string(),
-    }
+pub fn from_chrono<Tz: TimeZone>(dt: DateTime<Tz>) -> Timestamp
+where
+    Tz::Offset: Display,
+{
+    build(&dt)
+}
+
+/// Render only the selected fields of a DateTime, in the order given
+///
+/// Unselected fields are never computed, so a projection is cheaper than
+/// [`from_chrono`] followed by filtering.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use thedate::timestamp::{from_chrono_fields, Field};
+///
+/// let dt = Utc.with_ymd_and_hms(2024, 3, 15, 12, 30, 45).unwrap();
+/// let map = from_chrono_fields(&dt, &[Field::unix_timestamp, Field::full_iso]);
+/// assert_eq!(map["unix_timestamp"], 1710505845);
+/// assert_eq!(map["full_iso"], "2024-03-15");
+/// ```
+pub fn from_chrono_fields<Tz: TimeZone>(dt: &DateTime<Tz>, fields: &[Field]) -> Map<String, Value>
+where
+    Tz::Offset: Display,
+{
+    fields
+        .iter()
+        .map(|field| (field.name().to_string(), field.render(dt)))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::TimeZone;
+    use crate::zone::Zone;
+    use chrono::{NaiveDate, NaiveTime, Utc};
 
     // Helper to create a test datetime
     fn test_dt(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> DateTime<Utc> {
@@ -209,37 +441,37 @@ mod tests {
     fn test_yyyy_mm_dd_format() {
         let dt = test_dt(2024, 3, 15, 12, 30, 45);
         let ts = from_chrono(dt);
-        assert_eq!(ts.yyyy_mm_dd, "2024_03_15");
+        assert_eq!(ts.yyyy_mm_dd.as_deref(), Some("2024_03_15"));
     }
 
     #[test]
     fn test_mm_dd_yyyy_format() {
         let dt = test_dt(2024, 3, 15, 12, 30, 45);
         let ts = from_chrono(dt);
-        assert_eq!(ts.mm_dd_yyyy, "03_15_2024");
+        assert_eq!(ts.mm_dd_yyyy.as_deref(), Some("03_15_2024"));
     }
 
     #[test]
     fn test_dd_mm_yyyy_format() {
         let dt = test_dt(2024, 3, 15, 12, 30, 45);
         let ts = from_chrono(dt);
-        assert_eq!(ts.dd_mm_yyyy, "15_03_2024");
+        assert_eq!(ts.dd_mm_yyyy.as_deref(), Some("15_03_2024"));
     }
 
     #[test]
     fn test_yyyymmdd_format() {
         let dt = test_dt(2024, 3, 15, 12, 30, 45);
         let ts = from_chrono(dt);
-        assert_eq!(ts.yyyymmdd, "20240315");
+        assert_eq!(ts.yyyymmdd.as_deref(), Some("20240315"));
     }
 
     #[test]
     fn test_hyphenated_formats() {
         let dt = test_dt(2024, 3, 15, 12, 30, 45);
         let ts = from_chrono(dt);
-        assert_eq!(ts.yyyymmdd_hyphenated, "2024-03-15");
-        assert_eq!(ts.mmddyyyy_hyphenated, "03-15-2024");
-        assert_eq!(ts.ddmmyyyy_hyphenated, "15-03-2024");
+        assert_eq!(ts.yyyymmdd_hyphenated.as_deref(), Some("2024-03-15"));
+        assert_eq!(ts.mmddyyyy_hyphenated.as_deref(), Some("03-15-2024"));
+        assert_eq!(ts.ddmmyyyy_hyphenated.as_deref(), Some("15-03-2024"));
     }
 
     #[test]
@@ -286,14 +518,22 @@ mod tests {
     fn test_rfc2822_format() {
         let dt = test_dt(2024, 3, 15, 12, 30, 45);
         let ts = from_chrono(dt);
-        assert!(ts.rfc2822_date_format.contains("15 Mar 2024"));
+        assert!(ts
+            .rfc2822_date_format
+            .as_deref()
+            .unwrap()
+            .contains("15 Mar 2024"));
     }
 
     #[test]
     fn test_rfc3339_format() {
         let dt = test_dt(2024, 3, 15, 12, 30, 45);
         let ts = from_chrono(dt);
-        assert!(ts.rfc3339_date_format.starts_with("2024-03-15T12:30:45"));
+        assert!(ts
+            .rfc3339_date_format
+            .as_deref()
+            .unwrap()
+            .starts_with("2024-03-15T12:30:45"));
     }
 
     #[test]
@@ -338,6 +578,26 @@ mod tests {
         assert_eq!(ts.julian_day, "075"); // March 15 is day 75 in leap year
     }
 
+    #[test]
+    fn test_era_years() {
+        let ts = from_chrono(test_dt(2024, 3, 15, 12, 30, 45));
+        assert_eq!(ts.japanese_era.as_deref(), Some("Reiwa"));
+        assert_eq!(ts.japanese_era_year, Some(6));
+        assert_eq!(ts.japanese_era_kanji.as_deref(), Some("令和6年"));
+        assert_eq!(ts.japanese_era_romaji.as_deref(), Some("Reiwa 6"));
+        assert_eq!(ts.japanese_era_date.as_deref(), Some("R6.03.15"));
+        assert_eq!(ts.roc_year, 113);
+        assert_eq!(ts.roc_year_chinese, "民國113年");
+    }
+
+    #[test]
+    fn test_era_years_before_meiji() {
+        let ts = from_chrono(test_dt(1850, 1, 1, 0, 0, 0));
+        assert_eq!(ts.japanese_era, None);
+        assert_eq!(ts.japanese_era_date, None);
+        assert_eq!(ts.roc_year_chinese, "民前62年");
+    }
+
     #[test]
     fn test_full_iso() {
         let dt = test_dt(2024, 3, 15, 12, 30, 45);
@@ -349,16 +609,157 @@ mod tests {
     fn test_mdy_format() {
         let dt = test_dt(2024, 3, 15, 12, 30, 45);
         let ts = from_chrono(dt);
-        assert_eq!(ts.mdy_format, "03/15/24");
+        assert_eq!(ts.mdy_format.as_deref(), Some("03/15/24"));
     }
 
     #[test]
     fn test_year_formats() {
         let dt = test_dt(2024, 3, 15, 12, 30, 45);
         let ts = from_chrono(dt);
-        assert_eq!(ts.year_quad, "2024");
-        assert_eq!(ts.century_duo, "20");
-        assert_eq!(ts.year_duo, "24");
+        assert_eq!(ts.year_quad.as_deref(), Some("2024"));
+        assert_eq!(ts.century_duo.as_deref(), Some("20"));
+        assert_eq!(ts.year_duo.as_deref(), Some("24"));
+    }
+
+    #[test]
+    fn test_astronomical_day_counts() {
+        let ts = from_chrono(test_dt(2024, 3, 15, 12, 30, 45));
+        assert!((ts.julian_date - 2_460_385.021_354_167).abs() < 1e-8);
+        assert!((ts.modified_julian_date - 60_384.521_354_167).abs() < 1e-8);
+        assert_eq!(ts.truncated_julian_day, 20_384);
+        assert_eq!(ts.rata_die, 738_960);
+        assert!((ts.j2000_days - 8_840.021_354_167).abs() < 1e-8);
+        assert_eq!(ts.julian_day, "075");
+    }
+
+    #[test]
+    fn test_day_counts_follow_the_instant_and_rata_die_the_zone() {
+        let zone = Zone::parse("+05:30").unwrap();
+        let utc = from_chrono(test_dt(2024, 3, 15, 20, 0, 0));
+        let local = from_chrono(zone.at(test_dt(2024, 3, 15, 20, 0, 0)));
+        assert_eq!(utc.julian_date, local.julian_date);
+        assert_eq!(
+            utc.greenwich_mean_sidereal_time,
+            local.greenwich_mean_sidereal_time
+        );
+        assert_eq!(local.rata_die, utc.rata_die + 1);
+    }
+
+    #[test]
+    fn test_atomic_time_scales() {
+        let ts = from_chrono(test_dt(2024, 3, 15, 12, 0, 0));
+        assert_eq!(ts.tai.as_deref(), Some("2024-03-15T12:00:37"));
+        assert_eq!(
+            ts.terrestrial_time.as_deref(),
+            Some("2024-03-15T12:01:09.184")
+        );
+        assert_eq!(ts.tai_utc_offset, Some(37));
+        assert_eq!(ts.gps_week, Some(2305));
+        assert_eq!(ts.gps_time_of_week, Some(475_218.0));
+
+        let dt = test_dt(1975, 6, 1, 0, 0, 0);
+        let ts = from_chrono(dt);
+        assert_eq!(ts.tai_utc_offset, Some(14));
+        assert_eq!(ts.gps_week, None);
+        assert_eq!(Field::gps_week.null_reason_at(&dt), Some(BEFORE_GPS));
+        let dt = test_dt(1969, 7, 20, 20, 17, 0);
+        let ts = from_chrono(dt);
+        assert_eq!(ts.tai, None);
+        assert_eq!(ts.terrestrial_time, None);
+        assert_eq!(Field::tai.null_reason_at(&dt), Some(BEFORE_LEAP_SECONDS));
+
+        // TT runs 69.184 s ahead of UTC, TAI 37 s, past the last instant
+        let dt = NaiveDate::MAX.and_hms_opt(23, 59, 0).unwrap().and_utc();
+        let ts = from_chrono(dt);
+        assert_eq!(ts.tai.as_deref(), Some("+262142-12-31T23:59:37"));
+        assert_eq!(ts.terrestrial_time, None);
+        assert_eq!(Field::tai.null_reason_at(&dt), None);
+        assert_eq!(
+            Field::terrestrial_time.null_reason_at(&dt),
+            Some(ATOMIC_RANGE)
+        );
+        let dt = NaiveDate::MAX.and_hms_opt(23, 59, 30).unwrap().and_utc();
+        assert_eq!(from_chrono(dt).tai, None);
+        assert_eq!(Field::tai.null_reason_at(&dt), Some(ATOMIC_RANGE));
+    }
+
+    #[test]
+    fn test_era_fields_where_the_local_date_is_out_of_range() {
+        let last = NaiveDate::MAX.and_hms_opt(23, 0, 0).unwrap().and_utc();
+        let dt = Zone::parse("+14:00").unwrap().at(last);
+        let fields = [
+            Field::japanese_era,
+            Field::japanese_era_date,
+            Field::roc_year,
+        ];
+        let map = from_chrono_fields(&dt, &fields);
+        assert!(map["japanese_era"].is_null());
+        assert!(map["japanese_era_date"].is_null());
+        let reason = Field::japanese_era.null_reason_at(&dt);
+        assert_eq!(reason, Some(LOCAL_DATE_RANGE));
+        assert_eq!(Field::roc_year.null_reason_at(&dt), None);
+        assert_eq!(map["roc_year"], 260_232);
+    }
+
+    #[test]
+    fn test_rata_die_past_the_ends_of_the_range() {
+        let first = NaiveDate::MIN.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let last = NaiveDate::MAX.and_hms_opt(23, 0, 0).unwrap().and_utc();
+        let west = from_chrono(Zone::parse("-12:00").unwrap().at(first));
+        let east = from_chrono(Zone::parse("+14:00").unwrap().at(last));
+        let min = i64::from(NaiveDate::MIN.num_days_from_ce());
+        let max = i64::from(NaiveDate::MAX.num_days_from_ce());
+        assert_eq!(west.rata_die, min - 1);
+        assert_eq!(east.rata_die, max + 1);
+    }
+
+    #[test]
+    fn test_expanded_years() {
+        let ts = from_chrono(test_dt(12345, 3, 15, 12, 0, 0));
+        assert_eq!(ts.full_iso, "+12345-03-15");
+        assert_eq!(ts.iso_year, 12345);
+        assert_eq!(ts.yyyymmdd, None);
+        assert_eq!(ts.rfc2822_date_format, None);
+        assert_eq!(ts.rfc3339_date_format, None);
+        assert_eq!(ts.century_duo, None);
+        assert_eq!((ts.era.as_str(), ts.year_of_era), ("CE", 12345));
+
+        // The Ides of March, 44 BCE
+        let ts = from_chrono(test_dt(-43, 3, 15, 12, 0, 0));
+        assert_eq!(ts.full_iso, "-0043-03-15");
+        assert_eq!(ts.year_quad, None);
+        assert_eq!(ts.year_duo, None);
+        assert_eq!(ts.verbose_date, None);
+        assert_eq!((ts.era.as_str(), ts.year_of_era), ("BCE", 44));
+
+        let ts = from_chrono(test_dt(0, 1, 1, 0, 0, 0));
+        assert_eq!(ts.year_quad.as_deref(), Some("0000"));
+        assert_eq!((ts.era.as_str(), ts.year_of_era), ("BCE", 1));
+    }
+
+    #[test]
+    fn test_every_field_renders_across_the_range() {
+        let first = NaiveDate::MIN.and_time(NaiveTime::MIN).and_utc();
+        let last = NaiveDate::MAX.and_hms_opt(12, 0, 0).unwrap().and_utc();
+        let final_second = NaiveDate::MAX.and_hms_opt(23, 59, 59).unwrap().and_utc();
+        let utc = Zone::default();
+        // Offsets that take the civil date past either end
+        let west = Zone::parse("-12:00").unwrap();
+        let east = Zone::parse("+14:00").unwrap();
+        let ends = [
+            utc.at(first),
+            west.at(first),
+            utc.at(last),
+            east.at(last),
+            utc.at(final_second),
+        ];
+        for dt in ends {
+            for &field in Field::ALL {
+                let value = field.render(&dt);
+                let expected = field.null_reason_at(&dt).is_some();
+                assert_eq!(value.is_null(), expected, "{} at {}", field, dt);
+            }
+        }
     }
 
     #[test]
@@ -374,7 +775,7 @@ mod tests {
     fn test_leap_year_feb_29() {
         let dt = test_dt(2024, 2, 29, 12, 0, 0);
         let ts = from_chrono(dt);
-        assert_eq!(ts.yyyy_mm_dd, "2024_02_29");
+        assert_eq!(ts.yyyy_mm_dd.as_deref(), Some("2024_02_29"));
         assert_eq!(ts.julian_day, "060");
     }
 
@@ -382,7 +783,7 @@ mod tests {
     fn test_year_end() {
         let dt = test_dt(2023, 12, 31, 23, 59, 59);
         let ts = from_chrono(dt);
-        assert_eq!(ts.yyyy_mm_dd, "2023_12_31");
+        assert_eq!(ts.yyyy_mm_dd.as_deref(), Some("2023_12_31"));
         assert_eq!(ts.day_of_the_year, 365);
         assert_eq!(ts.month_of_the_year, 12);
         assert_eq!(ts.quarter_of_the_year, 4);
@@ -392,7 +793,7 @@ mod tests {
     fn test_year_start() {
         let dt = test_dt(2024, 1, 1, 0, 0, 0);
         let ts = from_chrono(dt);
-        assert_eq!(ts.yyyy_mm_dd, "2024_01_01");
+        assert_eq!(ts.yyyy_mm_dd.as_deref(), Some("2024_01_01"));
         assert_eq!(ts.day_of_the_year, 1);
         assert_eq!(ts.month_of_the_year, 1);
         assert_eq!(ts.quarter_of_the_year, 1);
@@ -406,15 +807,15 @@ mod tests {
         let dt = test_dt(1970, 1, 1, 0, 0, 0);
         let ts = from_chrono(dt);
         assert_eq!(ts.unix_timestamp, 0);
-        assert_eq!(ts.yyyy_mm_dd, "1970_01_01");
+        assert_eq!(ts.yyyy_mm_dd.as_deref(), Some("1970_01_01"));
     }
 
     #[test]
     fn test_far_future() {
         let dt = test_dt(2100, 12, 31, 23, 59, 59);
         let ts = from_chrono(dt);
-        assert_eq!(ts.yyyy_mm_dd, "2100_12_31");
-        assert_eq!(ts.year_quad, "2100");
+        assert_eq!(ts.yyyy_mm_dd.as_deref(), Some("2100_12_31"));
+        assert_eq!(ts.year_quad.as_deref(), Some("2100"));
     }
 
     #[test]
@@ -561,10 +962,12 @@ mod tests {
         let ts = from_chrono(dt);
 
         // RFC 2822 should be parseable
-        DateTime::parse_from_rfc2822(&ts.rfc2822_date_format).expect("RFC 2822 should parse");
+        DateTime::parse_from_rfc2822(ts.rfc2822_date_format.as_deref().unwrap())
+            .expect("RFC 2822 should parse");
 
         // RFC 3339 should be parseable
-        DateTime::parse_from_rfc3339(&ts.rfc3339_date_format).expect("RFC 3339 should parse");
+        DateTime::parse_from_rfc3339(ts.rfc3339_date_format.as_deref().unwrap())
+            .expect("RFC 3339 should parse");
     }
 
     #[test]
@@ -585,7 +988,10 @@ mod tests {
     fn test_hyphenated_and_underscore_formats_equivalent() {
         let dt = test_dt(2024, 3, 15, 12, 30, 45);
         let ts = from_chrono(dt);
-        assert_eq!(ts.yyyy_mm_dd.replace('_', "-"), ts.yyyymmdd_hyphenated);
+        assert_eq!(
+            ts.yyyy_mm_dd.map(|date| date.replace('_', "-")),
+            ts.yyyymmdd_hyphenated
+        );
     }
 
     #[test]
@@ -596,4 +1002,103 @@ mod tests {
         assert!(json.contains("yyyy_mm_dd"));
         assert!(json.contains("2024"));
     }
+
+    // Time Zone Tests
+    #[test]
+    fn test_utc_timezone_name() {
+        let ts = from_chrono(test_dt(2024, 3, 15, 12, 30, 45));
+        assert_eq!(ts.timezone_name, "UTC");
+    }
+
+    #[test]
+    fn test_named_zone_renders_local_fields() {
+        let zone = Zone::parse("America/New_York").unwrap();
+        let ts = from_chrono(zone.at(test_dt(2024, 1, 1, 3, 0, 0)));
+        assert_eq!(ts.yyyy_mm_dd.as_deref(), Some("2023_12_31"));
+        assert_eq!(ts.hour_of_the_day, 22);
+        assert_eq!(ts.timezone_name, "EST");
+        assert_eq!(
+            ts.rfc3339_date_format.as_deref(),
+            Some("2023-12-31T22:00:00-05:00")
+        );
+        assert_eq!(ts.unix_timestamp, 1704078000);
+    }
+
+    #[test]
+    fn test_named_zone_follows_dst() {
+        let zone = Zone::parse("Europe/London").unwrap();
+        let ts = from_chrono(zone.at(test_dt(2024, 7, 1, 12, 0, 0)));
+        assert_eq!(ts.military_time, "13:00");
+        assert_eq!(ts.timezone_name, "BST");
+        assert_eq!(
+            ts.rfc2822_date_format.as_deref(),
+            Some("Mon, 1 Jul 2024 13:00:00 +0100")
+        );
+    }
+
+    #[test]
+    fn test_fixed_offset_zone() {
+        let zone = Zone::parse("+05:30").unwrap();
+        let ts = from_chrono(zone.at(test_dt(2024, 3, 15, 20, 0, 0)));
+        assert_eq!(ts.yyyy_mm_dd.as_deref(), Some("2024_03_16"));
+        assert_eq!(ts.military_time, "01:30");
+        assert_eq!(ts.timezone_name, "+05:30");
+        assert_eq!(
+            ts.rfc3339_date_format_secs_z.as_deref(),
+            Some("2024-03-16T01:30:00+05:30")
+        );
+    }
+
+    // Projection Tests
+    #[test]
+    fn test_field_table_has_82_fields() {
+        assert_eq!(Field::ALL.len(), 82);
+    }
+
+    #[test]
+    fn test_projection_matches_full_struct() {
+        let dt = Zone::parse("Europe/Paris")
+            .unwrap()
+            .at(test_dt(2024, 3, 15, 12, 30, 45));
+        let full = serde_json::to_value(from_chrono(dt)).unwrap();
+        let projected = Value::Object(from_chrono_fields(&dt, Field::ALL));
+        assert_eq!(full, projected);
+    }
+
+    #[test]
+    fn test_projection_keeps_requested_order() {
+        let dt = test_dt(2024, 3, 15, 12, 30, 45);
+        let map = from_chrono_fields(&dt, &[Field::full_iso, Field::unix_timestamp]);
+        let keys: Vec<_> = map.keys().map(String::as_str).collect();
+        assert_eq!(keys, ["full_iso", "unix_timestamp"]);
+        assert_eq!(map["unix_timestamp"], 1710505845);
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        let fields = parse_fields("unix_timestamp, rfc3339_date_format_millis_z").unwrap();
+        assert_eq!(
+            fields,
+            [Field::unix_timestamp, Field::rfc3339_date_format_millis_z]
+        );
+    }
+
+    #[test]
+    fn test_parse_fields_drops_duplicates_and_blanks() {
+        let fields = parse_fields("full_iso,,full_iso,").unwrap();
+        assert_eq!(fields, [Field::full_iso]);
+    }
+
+    #[test]
+    fn test_parse_fields_empty_selects_all() {
+        assert_eq!(parse_fields("").unwrap().len(), 82);
+    }
+
+    #[test]
+    fn test_parse_fields_lists_every_unknown_name() {
+        match parse_fields("full_iso,nope,also_nope") {
+            Err(ApiError::UnknownFields(unknown)) => assert_eq!(unknown, ["nope", "also_nope"]),
+            _ => panic!("expected UnknownFields"),
+        }
+    }
 }
@@ -0,0 +1,294 @@
+//! Region-dependent week numbering.
+//!
+//! A week rule is a first day of the week plus the minimal number of days
+//! the first week of a year (or month) must contain, as in CLDR's
+//! `firstDay` and `minDays`. ISO 8601 is Monday with 4 minimal days; the
+//! United States is Sunday with 1; much of the Middle East starts on
+//! Saturday.
+//!
+//! Week of year may belong to the previous or next year near January 1,
+//! so the week-numbering year is reported alongside it. Week of month
+//! follows ICU and does not roll over: days before the month's first week
+//! are in week 0.
+
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use serde::Serialize;
+
+use crate::error::ApiError;
+
+/// Regions whose week starts on Sunday (CLDR `firstDay`)
+const SUNDAY_REGIONS: &[&str] = &[
+    "AG", "AS", "BD", "BR", "BS", "BT", "BW", "BZ", "CA", "CN", "CO", "DM", "DO", "ET", "GT", "GU",
+    "HK", "HN", "ID", "IL", "IN", "JM", "JP", "KE", "KH", "KR", "LA", "MH", "MM", "MO", "MT", "MX",
+    "MZ", "NI", "NP", "PA", "PE", "PH", "PK", "PR", "PT", "PY", "SA", "SG", "SV", "TH", "TT", "TW",
+    "UM", "US", "VE", "VI", "WS", "YE", "ZA", "ZW",
+];
+
+/// Regions whose week starts on Saturday (CLDR `firstDay`)
+const SATURDAY_REGIONS: &[&str] = &[
+    "AE", "AF", "BH", "DJ", "DZ", "EG", "IQ", "IR", "JO", "KW", "LY", "OM", "QA", "SD", "SY",
+];
+
+/// Regions whose week starts on Friday (CLDR `firstDay`)
+const FRIDAY_REGIONS: &[&str] = &["MV"];
+
+/// Regions whose first week needs 4 days (CLDR `minDays`); the rest need 1
+const FOUR_DAY_REGIONS: &[&str] = &[
+    "AD", "AN", "AT", "AX", "BE", "BG", "CH", "CZ", "DE", "DK", "EE", "ES", "FI", "FJ", "FO", "FR",
+    "GB", "GF", "GG", "GI", "GP", "GR", "HU", "IE", "IM", "IS", "IT", "JE", "LI", "LT", "LU", "MC",
+    "MQ", "NL", "NO", "PL", "RE", "RU", "SE", "SJ", "SK", "SM", "VA",
+];
+
+const DAY_NAMES: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+/// First day of the week plus minimal days in the first week
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WeekRule {
+    /// Normalized spec the rule was built from: `iso`, `SA` or `sat:1`
+    pub name: String,
+    pub first_day: Weekday,
+    pub minimal_days: u32,
+}
+
+/// Week numbers of one date under a [`WeekRule`]
+#[derive(Debug, Serialize)]
+pub struct WeekInfo {
+    pub rule: String,
+    pub first_day: String,
+    pub minimal_days: u32,
+    /// Year the week belongs to, which differs from the calendar year for
+    /// days at either end of it
+    pub week_year: i32,
+    pub week_of_year: u32,
+    pub week_of_month: u32,
+    /// Position in the week, 1 for `first_day` through 7
+    pub weekday_index: u32,
+}
+
+impl WeekRule {
+    /// Parse `iso`, a two-letter region code (`US`, `SA`, `DE`) or an
+    /// explicit `<day>:<minimal days>` such as `sat:1`
+    ///
+    /// Region codes not listed by CLDR fall back to its world default,
+    /// Monday with 1 minimal day.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::Weekday;
+    /// use thedate::week::WeekRule;
+    ///
+    /// let rule = WeekRule::parse("eg").unwrap();
+    /// assert_eq!((rule.name.as_str(), rule.first_day, rule.minimal_days), ("EG", Weekday::Sat, 1));
+    /// assert_eq!(WeekRule::parse("sun:4").unwrap().first_day, Weekday::Sun);
+    /// assert!(WeekRule::parse("sun:8").is_err());
+    /// ```
+    pub fn parse(spec: &str) -> Result<Self, ApiError> {
+        let spec = spec.trim();
+        let invalid = || ApiError::InvalidWeekRule(spec.to_string());
+        if spec.eq_ignore_ascii_case("iso") {
+            return Ok(WeekRule {
+                name: "iso".into(),
+                first_day: Weekday::Mon,
+                minimal_days: 4,
+            });
+        }
+        if let Some((day, minimal)) = spec.split_once(':') {
+            let day = day.trim().to_ascii_lowercase();
+            let first_day = DAY_NAMES
+                .iter()
+                .position(|name| day.starts_with(name))
+                .map(|i| Weekday::try_from(i as u8).expect("index below 7"))
+                .ok_or_else(invalid)?;
+            let minimal_days = minimal
+                .trim()
+                .parse()
+                .ok()
+                .filter(|n| (1..=7).contains(n))
+                .ok_or_else(invalid)?;
+            return Ok(WeekRule {
+                name: format!(
+                    "{}:{}",
+                    DAY_NAMES[first_day.num_days_from_monday() as usize],
+                    minimal_days
+                ),
+                first_day,
+                minimal_days,
+            });
+        }
+        if spec.len() == 2 && spec.bytes().all(|b| b.is_ascii_alphabetic()) {
+            let region = spec.to_ascii_uppercase();
+            let listed = |regions: &[&str]| regions.contains(&region.as_str());
+            let first_day = if listed(SUNDAY_REGIONS) {
+                Weekday::Sun
+            } else if listed(SATURDAY_REGIONS) {
+                Weekday::Sat
+            } else if listed(FRIDAY_REGIONS) {
+                Weekday::Fri
+            } else {
+                Weekday::Mon
+            };
+            let minimal_days = if listed(FOUR_DAY_REGIONS) { 4 } else { 1 };
+            return Ok(WeekRule {
+                name: region,
+                first_day,
+                minimal_days,
+            });
+        }
+        Err(invalid())
+    }
+
+    /// Position of `date` in its week, 1 through 7
+    fn weekday_index(&self, date: NaiveDate) -> u32 {
+        date.weekday().days_since(self.first_day) + 1
+    }
+
+    /// Start of week 1 of the period beginning on `first`
+    ///
+    /// Saturates at the ends of chrono's date range rather than panicking.
+    fn first_week_start(&self, first: NaiveDate) -> NaiveDate {
+        let into_week = u64::from(self.weekday_index(first) - 1);
+        let start = first.checked_sub_days(Days::new(into_week));
+        let start = if 7 - into_week >= u64::from(self.minimal_days) {
+            start
+        } else {
+            start.and_then(|start| start.checked_add_days(Days::new(7)))
+        };
+        start.unwrap_or(first)
+    }
+
+    /// Week numbers of `date`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use thedate::week::WeekRule;
+    ///
+    /// // Sunday 2023-01-01 is in ISO week 52 of 2022, but week 1 in the US
+    /// let date = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+    /// let iso = WeekRule::parse("iso").unwrap().info(date);
+    /// assert_eq!((iso.week_year, iso.week_of_year), (2022, 52));
+    /// let us = WeekRule::parse("US").unwrap().info(date);
+    /// assert_eq!((us.week_year, us.week_of_year, us.weekday_index), (2023, 1, 1));
+    /// ```
+    pub fn info(&self, date: NaiveDate) -> WeekInfo {
+        let year_start = |year: i32| {
+            NaiveDate::from_ymd_opt(year, 1, 1).map(|first| self.first_week_start(first))
+        };
+        let this_year = year_start(date.year()).unwrap_or(date);
+        let (week_year, start) = match (year_start(date.year() - 1), year_start(date.year() + 1)) {
+            (Some(previous), _) if date < this_year => (date.year() - 1, previous),
+            (_, Some(next)) if date >= next => (date.year() + 1, next),
+            _ => (date.year(), this_year),
+        };
+        let weeks_since = |start: NaiveDate| (date - start).num_days().div_euclid(7) + 1;
+        let month_start = self.first_week_start(date.with_day(1).expect("day 1 exists"));
+        WeekInfo {
+            rule: self.name.clone(),
+            first_day: DAY_NAMES[self.first_day.num_days_from_monday() as usize].to_string(),
+            minimal_days: self.minimal_days,
+            week_year,
+            week_of_year: weeks_since(start).max(1) as u32,
+            week_of_month: weeks_since(month_start).max(0) as u32,
+            weekday_index: self.weekday_index(date),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_iso_rule_matches_chrono_iso_week() {
+        let rule = WeekRule::parse("iso").unwrap();
+        let mut day = date(1999, 12, 1);
+        while day < date(2030, 2, 1) {
+            let info = rule.info(day);
+            let iso = day.iso_week();
+            assert_eq!(
+                (info.week_year, info.week_of_year),
+                (iso.year(), iso.week()),
+                "{}",
+                day
+            );
+            assert_eq!(info.weekday_index, day.weekday().number_from_monday());
+            day = day.succ_opt().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_us_rule_week_one_contains_january_first() {
+        let rule = WeekRule::parse("US").unwrap();
+        // Saturday 2022-01-01 opens week 1; Sunday 2022-01-02 starts week 2
+        assert_eq!(rule.info(date(2022, 1, 1)).week_of_year, 1);
+        assert_eq!(rule.info(date(2022, 1, 2)).week_of_year, 2);
+        // Sunday 2021-12-26 starts the week holding January 1
+        let info = rule.info(date(2021, 12, 26));
+        assert_eq!((info.week_year, info.week_of_year), (2022, 1));
+    }
+
+    #[test]
+    fn test_saturday_rule() {
+        let rule = WeekRule::parse("SA").unwrap();
+        assert_eq!(rule.first_day, Weekday::Sun);
+        let rule = WeekRule::parse("AE").unwrap();
+        assert_eq!((rule.first_day, rule.minimal_days), (Weekday::Sat, 1));
+        // Friday 2024-03-15 is the last day of a Saturday week
+        let info = rule.info(date(2024, 3, 15));
+        assert_eq!(info.weekday_index, 7);
+        assert_eq!(info.week_of_year, 11);
+        assert_eq!(rule.info(date(2024, 3, 16)).week_of_year, 12);
+    }
+
+    #[test]
+    fn test_week_of_month() {
+        let iso = WeekRule::parse("iso").unwrap();
+        // March 2024 starts on a Friday: only 3 days, so the 1st-3rd are week 0
+        assert_eq!(iso.info(date(2024, 3, 1)).week_of_month, 0);
+        assert_eq!(iso.info(date(2024, 3, 4)).week_of_month, 1);
+        assert_eq!(iso.info(date(2024, 3, 31)).week_of_month, 4);
+        let us = WeekRule::parse("US").unwrap();
+        assert_eq!(us.info(date(2024, 3, 1)).week_of_month, 1);
+        assert_eq!(us.info(date(2024, 3, 31)).week_of_month, 6);
+    }
+
+    #[test]
+    fn test_region_defaults() {
+        assert_eq!(WeekRule::parse("de").unwrap().minimal_days, 4);
+        let world = WeekRule::parse("ZZ").unwrap();
+        assert_eq!((world.first_day, world.minimal_days), (Weekday::Mon, 1));
+        assert_eq!(WeekRule::parse("mv").unwrap().first_day, Weekday::Fri);
+    }
+
+    #[test]
+    fn test_explicit_rule_is_normalized() {
+        let rule = WeekRule::parse("Saturday:1").unwrap();
+        assert_eq!(rule.name, "sat:1");
+    }
+
+    #[test]
+    fn test_range_edges_do_not_panic() {
+        for rule in ["iso", "US", "sat:7"] {
+            let rule = WeekRule::parse(rule).unwrap();
+            rule.info(NaiveDate::MIN);
+            rule.info(NaiveDate::MAX);
+        }
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        for spec in ["", "USA", "x:1", "sat:0", "sat:x", "12"] {
+            assert!(
+                matches!(WeekRule::parse(spec), Err(ApiError::InvalidWeekRule(_))),
+                "{:?} should be rejected",
+                spec
+            );
+        }
+    }
+}
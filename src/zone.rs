@@ -0,0 +1,275 @@
+//! Time zone selection for rendered timestamps.
+//!
+//! A [`Zone`] is either an IANA zone from the bundled tz database (with DST
+//! rules and real abbreviations such as `EDT`) or a fixed UTC offset such as
+//! `+05:30`. `Zone` implements chrono's [`TimeZone`], so a single
+//! `DateTime<Zone>` type flows through the rest of the service.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{
+    DateTime, FixedOffset, MappedLocalTime, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc,
+};
+use chrono_tz::{Tz, TzOffset, TZ_VARIANTS};
+
+use crate::error::ApiError;
+
+/// A time zone requested by a client
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Zone {
+    /// An IANA zone such as `America/New_York`
+    Named(Tz),
+    /// A fixed offset from UTC such as `+05:30`
+    Fixed(FixedOffset),
+}
+
+/// The offset in effect for a [`Zone`] at a given instant
+///
+/// Displays as the zone abbreviation (`EST`, `CEST`) for named zones and as
+/// `+HH:MM` for fixed offsets, which is what `%Z` renders.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZoneOffset {
+    Named(TzOffset),
+    Fixed(FixedOffset),
+}
+
+impl Zone {
+    /// Parse a `tz` query value
+    ///
+    /// Accepts IANA names (matched case-insensitively), `Z`, and fixed
+    /// offsets in the forms `+05`, `+0530` and `+05:30`. Because a literal
+    /// `+` in a query string decodes to a space, a leading space followed by
+    /// a digit is read as `+`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use thedate::zone::Zone;
+    ///
+    /// assert!(Zone::parse("America/New_York").is_ok());
+    /// assert!(Zone::parse("+05:30").is_ok());
+    /// assert!(Zone::parse("Mars/Olympus_Mons").is_err());
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, ApiError> {
+        if s == "Z" || s == "z" {
+            return Ok(Zone::Named(Tz::UTC));
+        }
+        if let Some(offset) = parse_fixed_offset(s) {
+            return Ok(Zone::Fixed(offset));
+        }
+        if let Ok(tz) = Tz::from_str(s) {
+            return Ok(Zone::Named(tz));
+        }
+        TZ_VARIANTS
+            .iter()
+            .find(|tz| tz.name().eq_ignore_ascii_case(s))
+            .map(|tz| Zone::Named(*tz))
+            .ok_or_else(|| ApiError::UnknownTimeZone(s.to_string()))
+    }
+
+    /// Express a UTC instant in this zone
+    pub fn at(&self, instant: DateTime<Utc>) -> DateTime<Zone> {
+        instant.with_timezone(self)
+    }
+}
+
+/// The wall-clock reading of `dt`, or `None` when its offset carries it
+/// past the first or last date-time chrono can represent
+///
+/// `DateTime::naive_local` and `date_naive` panic there instead, which
+/// instants within a day of either end of the supported range can reach.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use thedate::zone::{local_datetime, Zone};
+///
+/// let last = NaiveDate::MAX.and_hms_opt(23, 0, 0).unwrap().and_utc();
+/// assert_eq!(local_datetime(&last), Some(last.naive_utc()));
+/// assert_eq!(local_datetime(&Zone::parse("+14:00").unwrap().at(last)), None);
+/// ```
+pub fn local_datetime<Tz: TimeZone>(dt: &DateTime<Tz>) -> Option<NaiveDateTime> {
+    dt.naive_utc().checked_add_offset(dt.offset().fix())
+}
+
+/// The civil date of `dt`, or `None` where [`local_datetime`] is
+pub fn local_date<Tz: TimeZone>(dt: &DateTime<Tz>) -> Option<NaiveDate> {
+    local_datetime(dt).map(|local| local.date())
+}
+
+impl Default for Zone {
+    fn default() -> Self {
+        Zone::Named(Tz::UTC)
+    }
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Zone::Named(tz) => f.write_str(tz.name()),
+            Zone::Fixed(offset) => write!(f, "{}", offset),
+        }
+    }
+}
+
+/// Parse `±HH`, `±HHMM` or `±HH:MM` into a fixed offset
+fn parse_fixed_offset(s: &str) -> Option<FixedOffset> {
+    let (sign, rest) = match s.as_bytes().first()? {
+        b'+' | b' ' => (1, &s[1..]),
+        b'-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    let digits: String = match rest.len() {
+        2 | 4 => rest.to_string(),
+        5 if rest.as_bytes()[2] == b':' => rest.replacen(':', "", 1),
+        _ => return None,
+    };
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i32 = digits[..2].parse().ok()?;
+    let minutes: i32 = if digits.len() == 4 {
+        digits[2..].parse().ok()?
+    } else {
+        0
+    };
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+impl Offset for ZoneOffset {
+    fn fix(&self) -> FixedOffset {
+        match self {
+            ZoneOffset::Named(offset) => offset.fix(),
+            ZoneOffset::Fixed(offset) => *offset,
+        }
+    }
+}
+
+impl fmt::Display for ZoneOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZoneOffset::Named(offset) => fmt::Display::fmt(offset, f),
+            ZoneOffset::Fixed(offset) => fmt::Display::fmt(offset, f),
+        }
+    }
+}
+
+impl TimeZone for Zone {
+    type Offset = ZoneOffset;
+
+    fn from_offset(offset: &ZoneOffset) -> Self {
+        match offset {
+            ZoneOffset::Named(offset) => Zone::Named(Tz::from_offset(offset)),
+            ZoneOffset::Fixed(offset) => Zone::Fixed(*offset),
+        }
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<ZoneOffset> {
+        match self {
+            #[allow(deprecated)]
+            Zone::Named(tz) => tz.offset_from_local_date(local).map(ZoneOffset::Named),
+            Zone::Fixed(offset) => MappedLocalTime::Single(ZoneOffset::Fixed(*offset)),
+        }
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> MappedLocalTime<ZoneOffset> {
+        match self {
+            Zone::Named(tz) => tz.offset_from_local_datetime(local).map(ZoneOffset::Named),
+            Zone::Fixed(offset) => MappedLocalTime::Single(ZoneOffset::Fixed(*offset)),
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> ZoneOffset {
+        match self {
+            #[allow(deprecated)]
+            Zone::Named(tz) => ZoneOffset::Named(tz.offset_from_utc_date(utc)),
+            Zone::Fixed(offset) => ZoneOffset::Fixed(*offset),
+        }
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> ZoneOffset {
+        match self {
+            Zone::Named(tz) => ZoneOffset::Named(tz.offset_from_utc_datetime(utc)),
+            Zone::Fixed(offset) => ZoneOffset::Fixed(*offset),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_iana_name() {
+        assert_eq!(
+            Zone::parse("America/New_York").unwrap(),
+            Zone::Named(Tz::America__New_York)
+        );
+    }
+
+    #[test]
+    fn test_parse_is_case_insensitive() {
+        assert_eq!(
+            Zone::parse("europe/berlin").unwrap(),
+            Zone::Named(Tz::Europe__Berlin)
+        );
+    }
+
+    #[test]
+    fn test_parse_fixed_offsets() {
+        let ist = FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap();
+        assert_eq!(Zone::parse("+05:30").unwrap(), Zone::Fixed(ist));
+        assert_eq!(Zone::parse("+0530").unwrap(), Zone::Fixed(ist));
+        assert_eq!(
+            Zone::parse("-08").unwrap(),
+            Zone::Fixed(FixedOffset::west_opt(8 * 3600).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_decoded_plus_sign() {
+        // `?tz=+05:30` arrives as " 05:30" after query decoding
+        assert_eq!(
+            Zone::parse(" 05:30").unwrap(),
+            Zone::parse("+05:30").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_garbage() {
+        assert!(Zone::parse("Mars/Olympus_Mons").is_err());
+        assert!(Zone::parse("+25:00").is_err());
+        assert!(Zone::parse("+05:60").is_err());
+        assert!(Zone::parse("").is_err());
+    }
+
+    #[test]
+    fn test_dst_abbreviation() {
+        let zone = Zone::parse("America/New_York").unwrap();
+        let winter = zone.at(Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap());
+        let summer = zone.at(Utc.with_ymd_and_hms(2024, 7, 15, 12, 0, 0).unwrap());
+        assert_eq!(winter.format("%Z %:z").to_string(), "EST -05:00");
+        assert_eq!(summer.format("%Z %:z").to_string(), "EDT -04:00");
+    }
+
+    #[test]
+    fn test_local_date_at_the_range_edges() {
+        let first = NaiveDate::MIN.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let east = Zone::parse("+01:00").unwrap();
+        let west = Zone::parse("America/New_York").unwrap();
+        assert_eq!(local_date(&east.at(first)), Some(NaiveDate::MIN));
+        assert_eq!(local_date(&west.at(first)), None);
+    }
+
+    #[test]
+    fn test_fixed_offset_name() {
+        let zone = Zone::parse("+05:30").unwrap();
+        let dt = zone.at(Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap());
+        assert_eq!(dt.format("%H:%M %Z").to_string(), "17:30 +05:30");
+    }
+}
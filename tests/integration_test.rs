@@ -1,5 +1,6 @@
 use actix_web::{test, App};
 use serde_json::Value;
+use thedate::timestamp::Field;
 
 mod helpers {
     use super::*;
@@ -35,6 +36,138 @@ mod helpers {
         let body = test::read_body(resp).await;
         serde_json::from_slice(&body).expect("Failed to parse JSON response")
     }
+
+    pub async fn get_json(uri: &str) -> (u16, Value) {
+        let app = test::init_service(
+            App::new()
+                .app_data(thedate::query_config())
+                .route("/", actix_web::web::get().to(thedate::home))
+                .route(
+                    "/at/{instant}",
+                    actix_web::web::get().to(thedate::at_instant),
+                )
+                .route("/f/{field}", actix_web::web::get().to(thedate::field_value))
+                .route("/format", actix_web::web::get().to(thedate::format_pattern))
+                .route("/convert", actix_web::web::get().to(thedate::convert_time))
+                .route("/diff", actix_web::web::get().to(thedate::diff))
+                .route("/add", actix_web::web::get().to(thedate::add))
+                .route(
+                    "/resolve",
+                    actix_web::web::get().to(thedate::resolve_expression),
+                )
+                .route("/health", actix_web::web::get().to(thedate::health_check)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri(uri).to_request();
+        let resp = test::call_service(&app, req).await;
+        let status = resp.status().as_u16();
+
+        let body = test::read_body(resp).await;
+        let json = serde_json::from_slice(&body).expect("Failed to parse JSON response");
+        (status, json)
+    }
+
+    pub async fn post_body(uri: &str, content_type: &str, body: &str) -> (u16, Value) {
+        let app = test::init_service(App::new().app_data(thedate::query_config()).route(
+            "/parse",
+            actix_web::web::post().to(thedate::parse_timestamp),
+        ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(uri)
+            .insert_header(("Content-Type", content_type))
+            .set_payload(body.to_string())
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        let status = resp.status().as_u16();
+
+        let body = test::read_body(resp).await;
+        let json = serde_json::from_slice(&body).expect("Failed to parse JSON response");
+        (status, json)
+    }
+
+    pub async fn get_accept(uri: &str, accept: &str) -> (u16, String, Vec<u8>) {
+        let app = test::init_service(
+            App::new()
+                .route("/", actix_web::web::get().to(thedate::home))
+                .route(
+                    "/at/{instant}",
+                    actix_web::web::get().to(thedate::at_instant),
+                ),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri(uri)
+            .insert_header(("Accept", accept))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        let status = resp.status().as_u16();
+        let content_type = resp
+            .headers()
+            .get("content-type")
+            .map(|v| v.to_str().unwrap().to_string())
+            .unwrap_or_default();
+
+        let body = test::read_body(resp).await;
+        (status, content_type, body.to_vec())
+    }
+
+    pub async fn get_text(uri: &str) -> (u16, String, String) {
+        let app = test::init_service(
+            App::new().route("/f/{field}", actix_web::web::get().to(thedate::field_value)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri(uri).to_request();
+        let resp = test::call_service(&app, req).await;
+        let status = resp.status().as_u16();
+        let content_type = resp
+            .headers()
+            .get("content-type")
+            .map(|v| v.to_str().unwrap().to_string())
+            .unwrap_or_default();
+
+        let body = test::read_body(resp).await;
+        (
+            status,
+            String::from_utf8(body.to_vec()).unwrap(),
+            content_type,
+        )
+    }
+
+    pub async fn get_language(uri: &str, accept_language: &str) -> (u16, String, Value) {
+        let app = test::init_service(
+            App::new()
+                .route("/", actix_web::web::get().to(thedate::home))
+                .route(
+                    "/at/{instant}",
+                    actix_web::web::get().to(thedate::at_instant),
+                ),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri(uri)
+            .insert_header(("Accept-Language", accept_language))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        let status = resp.status().as_u16();
+        let content_language = resp
+            .headers()
+            .get("content-language")
+            .map(|v| v.to_str().unwrap().to_string())
+            .unwrap_or_default();
+
+        let body = test::read_body(resp).await;
+        (
+            status,
+            content_language,
+            serde_json::from_slice(&body).unwrap(),
+        )
+    }
 }
 
 // HTTP Endpoint Tests
@@ -59,7 +192,8 @@ async fn test_all_61_fields_present() {
     let json = helpers::get_root().await;
     let obj = json.as_object().expect("Response should be an object");
 
-    // List all 61 expected fields from v0.3.3 for backward compatibility
+    // All 61 fields from v0.3.3 must stay for backward compatibility; the
+    // era fields were added since
     let expected_fields = vec![
         "yyyy_mm_dd",
         "mm_dd_yyyy",
@@ -124,7 +258,7 @@ async fn test_all_61_fields_present() {
         "verbose_date",
     ];
 
-    assert_eq!(obj.len(), 61, "Expected exactly 61 fields");
+    assert_eq!(obj.len(), Field::ALL.len(), "Expected every field");
 
     for field in expected_fields {
         assert!(obj.contains_key(field), "Missing field: {}", field);
@@ -289,3 +423,1347 @@ async fn test_health_check_json_format() {
     assert!(obj.contains_key("status"));
     assert_eq!(obj["status"], "healthy");
 }
+
+#[actix_web::test]
+async fn test_health_check_reports_leap_second_table() {
+    let json = helpers::get_health().await;
+    let table = &json["leap_seconds"];
+    assert_eq!(table["source"], "embedded");
+    assert_eq!(table["updated"], "2026-01-06T11:14:18Z");
+    assert_eq!(table["expires"], "2026-12-28T00:00:00Z");
+    // Whether it has expired depends on today's date
+    let stale = table["stale"].as_bool().unwrap();
+    assert_eq!(json.get("warnings").is_some(), stale);
+}
+
+// Time Zone Tests
+#[actix_web::test]
+async fn test_tz_named_zone() {
+    let (status, json) = helpers::get_json("/?tz=Asia/Tokyo").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["timezone_name"], "JST");
+    assert!(json["rfc3339_date_format"]
+        .as_str()
+        .unwrap()
+        .ends_with("+09:00"));
+}
+
+#[actix_web::test]
+async fn test_tz_fixed_offset() {
+    let (status, json) = helpers::get_json("/?tz=%2B05:30").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["timezone_name"], "+05:30");
+    assert_eq!(json.as_object().unwrap().len(), Field::ALL.len());
+}
+
+#[actix_web::test]
+async fn test_tz_unencoded_plus_offset() {
+    let (status, json) = helpers::get_json("/?tz=+05:30").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["timezone_name"], "+05:30");
+}
+
+#[actix_web::test]
+async fn test_tz_unknown_zone_is_400() {
+    let (status, json) = helpers::get_json("/?tz=Mars/Olympus_Mons").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "unknown_time_zone");
+    assert!(json["message"]
+        .as_str()
+        .unwrap()
+        .contains("Mars/Olympus_Mons"));
+}
+
+// Arbitrary Instant Tests
+#[actix_web::test]
+async fn test_at_unix_seconds() {
+    let (status, json) = helpers::get_json("/at/1710504045").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["unix_timestamp"], 1710504045);
+    assert_eq!(json["rfc3339_date_format"], "2024-03-15T12:00:45+00:00");
+    assert_eq!(json.as_object().unwrap().len(), Field::ALL.len());
+}
+
+#[actix_web::test]
+async fn test_at_explicit_millis() {
+    let (status, json) = helpers::get_json("/at/@1710504045123ms").await;
+    assert_eq!(status, 200);
+    assert_eq!(
+        json["rfc3339_date_format_millis_z"],
+        "2024-03-15T12:00:45.123Z"
+    );
+}
+
+#[actix_web::test]
+async fn test_at_rfc3339_with_tz() {
+    let (status, json) = helpers::get_json("/at/2024-03-15T12:30:45Z?tz=America/New_York").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["military_time"], "08:30");
+    assert_eq!(json["timezone_name"], "EDT");
+}
+
+#[actix_web::test]
+async fn test_at_invalid_instant_is_400() {
+    let (status, json) = helpers::get_json("/at/@1710504045fortnights").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_instant");
+}
+
+#[actix_web::test]
+async fn test_at_relative_to_reference() {
+    let (status, json) = helpers::get_json(
+        "/at/2024-03-15T09:00:00Z?fields=unix_timestamp&relative_to=2024-03-15T12:00:00Z",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(
+        json["relative"],
+        serde_json::json!({
+            "relative_to": "2024-03-15T12:00:00Z",
+            "phrase": "3 hours ago",
+            "short": "3h ago",
+            "value": -3,
+            "unit": "hour"
+        })
+    );
+}
+
+#[actix_web::test]
+async fn test_relative_thresholds_override() {
+    let uri = "/at/2024-03-16T10:00:00Z?fields=unix_timestamp&relative_to=2024-03-15T12:00:00Z";
+    let (_, json) = helpers::get_json(uri).await;
+    assert_eq!(json["relative"]["phrase"], "in 1 day");
+    let (_, json) = helpers::get_json(&format!("{}&thresholds=hours:24", uri)).await;
+    assert_eq!(json["relative"]["phrase"], "in 22 hours");
+}
+
+#[actix_web::test]
+async fn test_relative_invalid_thresholds_is_400() {
+    let (status, json) =
+        helpers::get_json("/?relative_to=1710504045&thresholds=fortnights:2").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_thresholds");
+}
+
+// Localization Tests
+#[actix_web::test]
+async fn test_locale_parameter() {
+    let (status, json) = helpers::get_json(
+        "/at/2024-03-15T12:00:00Z?locale=de-DE&fields=long_month,weekday,locale_date,verbose_date",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "long_month": "März",
+            "weekday": "Freitag",
+            "locale_date": "15.03.24",
+            "verbose_date": "15. März 2024"
+        })
+    );
+}
+
+#[actix_web::test]
+async fn test_accept_language_negotiation() {
+    let (status, content_language, json) = helpers::get_language(
+        "/at/2024-03-15T12:00:00Z?fields=weekday,verbose_date",
+        "tlh, ja-JP;q=0.9, fr;q=0.5",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(content_language, "ja");
+    assert_eq!(json["weekday"], "金曜日");
+    assert_eq!(json["verbose_date"], "2024年3月15日");
+}
+
+#[actix_web::test]
+async fn test_locale_parameter_overrides_header() {
+    let (_, content_language, json) =
+        helpers::get_language("/at/1710504045?fields=long_month&locale=es", "de").await;
+    assert_eq!(content_language, "es");
+    assert_eq!(json["long_month"], "marzo");
+}
+
+#[actix_web::test]
+async fn test_unsupported_locale_is_400() {
+    let (status, json) = helpers::get_json("/?locale=tlh").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "unsupported_locale");
+    assert!(json["supported"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!("de")));
+}
+
+// Week Rule Tests
+#[actix_web::test]
+async fn test_week_rule_region() {
+    let (status, json) =
+        helpers::get_json("/at/2024-03-15T12:00:00Z?fields=iso_weekday&week_rule=ae").await;
+    assert_eq!(status, 200);
+    assert_eq!(
+        json["week_rule"],
+        serde_json::json!({
+            "rule": "AE",
+            "first_day": "sat",
+            "minimal_days": 1,
+            "week_year": 2024,
+            "week_of_year": 11,
+            "week_of_month": 3,
+            "weekday_index": 7
+        })
+    );
+}
+
+#[actix_web::test]
+async fn test_week_rule_year_boundary() {
+    let uri = "/at/2023-01-01T12:00:00Z?fields=iso_week&week_rule=";
+    let (_, json) = helpers::get_json(&format!("{}iso", uri)).await;
+    assert_eq!(json["week_rule"]["week_year"], 2022);
+    assert_eq!(json["week_rule"]["week_of_year"], 52);
+    let (_, json) = helpers::get_json(&format!("{}sun:1", uri)).await;
+    assert_eq!(json["week_rule"]["rule"], "sun:1");
+    assert_eq!(json["week_rule"]["week_of_year"], 1);
+}
+
+#[actix_web::test]
+async fn test_invalid_week_rule_is_400() {
+    let (status, json) = helpers::get_json("/?week_rule=fortnight").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_week_rule");
+}
+
+// Numbering System Tests
+#[actix_web::test]
+async fn test_numerals_apply_to_strings_only() {
+    let (status, json) = helpers::get_json(
+        "/at/1710504045?numerals=arabext&fields=full_iso,unix_timestamp,military_time",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(json["full_iso"], "۲۰۲۴-۰۳-۱۵");
+    assert_eq!(json["military_time"], "۱۲:۰۰");
+    assert_eq!(json["unix_timestamp"], 1710504045);
+}
+
+#[actix_web::test]
+async fn test_native_digits_parse_back() {
+    let (status, json) =
+        helpers::get_json("/at/%E0%A5%A7%E0%A5%AD%E0%A5%A7%E0%A5%A6%E0%A5%AB%E0%A5%A6%E0%A5%AA%E0%A5%A6%E0%A5%AA%E0%A5%AB?fields=unix_timestamp")
+            .await;
+    assert_eq!(status, 200);
+    assert_eq!(json["unix_timestamp"], 1710504045);
+}
+
+#[actix_web::test]
+async fn test_numerals_on_single_field() {
+    let (status, body, _) = helpers::get_text("/f/yyyymmdd?at=1710504045&numerals=thai").await;
+    assert_eq!(status, 200);
+    assert_eq!(body, "๒๐๒๔๐๓๑๕");
+}
+
+#[actix_web::test]
+async fn test_unknown_numerals_is_400() {
+    let (status, json) = helpers::get_json("/?numerals=roman").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_parameter");
+}
+
+// Spoken Output Tests
+#[actix_web::test]
+async fn test_spoken_block() {
+    let (status, json) =
+        helpers::get_json("/at/2024-03-15T12:32:00Z?fields=military_time&spoken=true").await;
+    assert_eq!(status, 200);
+    assert_eq!(
+        json["spoken"],
+        serde_json::json!({
+            "language": "en",
+            "exact": "twelve thirty-two p.m.",
+            "fuzzy": "just after half past twelve in the afternoon",
+            "date": "Friday, the fifteenth of March, twenty twenty-four"
+        })
+    );
+}
+
+#[actix_web::test]
+async fn test_spoken_follows_zone_and_falls_back_to_english() {
+    let (_, json) = helpers::get_json(
+        "/at/2024-03-15T14:15:00Z?fields=military_time&spoken=1&tz=Europe/Berlin&locale=de",
+    )
+    .await;
+    assert_eq!(json["spoken"]["language"], "en");
+    assert_eq!(
+        json["spoken"]["fuzzy"],
+        "quarter past three in the afternoon"
+    );
+}
+
+#[actix_web::test]
+async fn test_spoken_invalid_flag_is_400() {
+    let (status, json) = helpers::get_json("/?spoken=maybe").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_parameter");
+}
+
+// Calendar Tests
+#[actix_web::test]
+async fn test_calendars_block() {
+    let (status, json) = helpers::get_json(
+        "/at/2024-03-15T12:00:00Z?fields=unix_timestamp&calendars=hebrew,islamic",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(
+        json["calendars"],
+        serde_json::json!({
+            "hebrew": {
+                "year": 5784, "month": 13, "month_name": "Adar II", "day": 5,
+                "leap_year": true, "leap_month": false
+            },
+            "islamic": {
+                "variant": "tabular-civil",
+                "year": 1445, "month": 9, "month_name": "Ramadan", "day": 5,
+                "leap_year": true, "leap_month": false
+            }
+        })
+    );
+}
+
+#[actix_web::test]
+async fn test_calendars_follow_zone() {
+    // Already 16 March in Tokyo
+    let (_, json) = helpers::get_json(
+        "/at/2024-03-15T20:00:00Z?fields=unix_timestamp&calendars=islamic-tbla&tz=Asia/Tokyo",
+    )
+    .await;
+    assert_eq!(
+        json["calendars"]["islamic-tbla"]["variant"],
+        "tabular-astronomical"
+    );
+    assert_eq!(json["calendars"]["islamic-tbla"]["day"], 7);
+}
+
+#[actix_web::test]
+async fn test_solar_calendars_round_trip() {
+    let (status, json) = helpers::get_json(
+        "/at/persian:1403-01-01T00:00:00+03:30?fields=rfc3339_date_format&tz=Asia/Tehran\
+         &calendars=persian,indian,ethiopic,coptic,buddhist",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(json["rfc3339_date_format"], "2024-03-20T00:00:00+03:30");
+    let calendars = &json["calendars"];
+    assert_eq!(calendars["persian"]["year"], 1403);
+    assert_eq!(calendars["persian"]["month_name"], "Farvardin");
+    assert_eq!(calendars["persian"]["day"], 1);
+    assert_eq!(calendars["indian"]["month_name"], "Phalguna");
+    assert_eq!(calendars["indian"]["day"], 30);
+    assert_eq!(calendars["ethiopic"]["month_name"], "Megabit");
+    assert_eq!(calendars["coptic"]["month_name"], "Baramhat");
+    assert_eq!(calendars["buddhist"]["year"], 2567);
+}
+
+#[actix_web::test]
+async fn test_calendar_month_names_follow_locale() {
+    let (_, language, json) = helpers::get_language(
+        "/at/2024-03-15T12:00:00Z?fields=unix_timestamp&calendars=persian,buddhist",
+        "fa-IR, en;q=0.5",
+    )
+    .await;
+    assert_eq!(language, "fa");
+    assert_eq!(json["calendars"]["persian"]["month_name"], "اسفند");
+    assert_eq!(json["calendars"]["buddhist"]["month_name"], "مارس");
+}
+
+#[actix_web::test]
+async fn test_chinese_calendar_block() {
+    let (status, json) = helpers::get_json(
+        "/at/2024-03-15T12:00:00Z?fields=unix_timestamp&calendars=chinese&tz=Asia/Shanghai",
+    )
+    .await;
+    assert_eq!(status, 200);
+    let chinese = &json["calendars"]["chinese"];
+    assert_eq!(chinese["year"], 2024);
+    assert_eq!(chinese["month"], 2);
+    assert_eq!(chinese["day"], 6);
+    assert_eq!(chinese["leap_month"], false);
+    assert_eq!(chinese["sexagenary"]["year"], "甲辰");
+    assert_eq!(chinese["zodiac"], "Dragon");
+    assert_eq!(chinese["solar_term"]["name"], "Awakening of Insects");
+    let next = chinese["next_solar_term"]["instant"].as_str().unwrap();
+    assert!(next.starts_with("2024-03-20T11:06"), "{}", next);
+    assert!(next.ends_with("+08:00"), "{}", next);
+}
+
+#[actix_web::test]
+async fn test_chinese_leap_month_round_trip() {
+    let (status, json) = helpers::get_json(
+        "/at/chinese:2023-02L-15?fields=rfc3339_date_format&calendars=chinese&locale=zh",
+    )
+    .await;
+    assert_eq!(status, 200);
+    let chinese = &json["calendars"]["chinese"];
+    assert_eq!(chinese["month"], 2);
+    assert_eq!(chinese["leap_month"], true);
+    assert_eq!(chinese["month_name"], "闰二月");
+    assert_eq!(chinese["zodiac"], "兔");
+}
+
+#[actix_web::test]
+async fn test_nonexistent_calendar_date_is_400() {
+    let (status, json) = helpers::get_json("/at/ethiopic:2016-13-07").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_instant");
+    let (status, json) = helpers::get_json("/at/hebrew:9223372036854775807-01-01").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_instant");
+}
+
+#[actix_web::test]
+async fn test_julian_and_historical_blocks() {
+    let (status, json) = helpers::get_json(
+        "/at/julian:1917-10-25?fields=rfc3339_date_format&calendars=julian,historical-ru",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(json["rfc3339_date_format"], "1917-11-07T00:00:00+00:00");
+    assert_eq!(json["calendars"]["julian"]["day"], 25);
+    let russia = &json["calendars"]["historical-ru"];
+    assert_eq!(russia["cutover"], "1918-02-14");
+    assert_eq!(russia["reckoning"], "julian");
+    assert_eq!(russia["month_name"], "October");
+}
+
+#[actix_web::test]
+async fn test_date_in_cutover_gap_is_400() {
+    let (status, json) = helpers::get_json("/at/historical-gb:1752-09-05").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_instant");
+    assert!(json["message"]
+        .as_str()
+        .unwrap()
+        .contains("the Julian calendar ended on 1752-09-02"));
+}
+
+#[actix_web::test]
+async fn test_umm_al_qura_calendar() {
+    let (status, json) = helpers::get_json(
+        "/at/2024-07-07T12:00:00Z?fields=unix_timestamp&calendars=islamic,islamic-umalqura",
+    )
+    .await;
+    assert_eq!(status, 200);
+    let umalqura = &json["calendars"]["islamic-umalqura"];
+    assert_eq!(umalqura["variant"], "umalqura");
+    assert_eq!(
+        (&umalqura["year"], &umalqura["month"], &umalqura["day"]),
+        (&Value::from(1446), &Value::from(1), &Value::from(1))
+    );
+    assert_eq!(json["calendars"]["islamic"]["day"], 30);
+
+    let (status, json) =
+        helpers::get_json("/at/islamic-umalqura:1446-10-01?fields=rfc3339_date_format").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["rfc3339_date_format"], "2025-03-30T00:00:00+00:00");
+    // Ramadan 1446 had 29 days
+    let (status, _) = helpers::get_json("/at/islamic-umalqura:1446-09-30").await;
+    assert_eq!(status, 400);
+}
+
+#[actix_web::test]
+async fn test_unknown_calendar_is_400() {
+    let (status, json) = helpers::get_json("/?calendars=islamic-rgsa").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_parameter");
+}
+
+// Era Tests
+#[actix_web::test]
+async fn test_era_fields() {
+    let (status, json) = helpers::get_json(
+        "/at/R6.03.15T09:00:00%2B09:00?tz=Asia/Tokyo&fields=japanese_era,japanese_era_year,\
+         japanese_era_kanji,japanese_era_romaji,japanese_era_date,roc_year,roc_year_chinese",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "japanese_era": "Reiwa",
+            "japanese_era_year": 6,
+            "japanese_era_kanji": "令和6年",
+            "japanese_era_romaji": "Reiwa 6",
+            "japanese_era_date": "R6.03.15",
+            "roc_year": 113,
+            "roc_year_chinese": "民國113年"
+        })
+    );
+}
+
+#[actix_web::test]
+async fn test_roc_date_is_accepted() {
+    let (status, json) = helpers::get_json(
+        "/at/%E6%B0%91%E5%9C%8B113%E5%B9%B43%E6%9C%8815%E6%97%A5?fields=full_iso",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(json["full_iso"], "2024-03-15");
+}
+
+#[actix_web::test]
+async fn test_era_fields_are_null_before_meiji() {
+    let (_, json) =
+        helpers::get_json("/at/1850-01-01T00:00:00Z?fields=japanese_era,roc_year").await;
+    assert_eq!(json["japanese_era"], Value::Null);
+    assert_eq!(json["roc_year"], -61);
+}
+
+#[actix_web::test]
+async fn test_date_outside_its_era_is_400() {
+    let (status, json) = helpers::get_json("/at/H31.05.01").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_instant");
+    assert!(json["message"]
+        .as_str()
+        .unwrap()
+        .contains("Heisei ended on 2019-04-30"));
+}
+
+#[actix_web::test]
+async fn test_roc_years_out_of_range_are_400() {
+    for (uri, reason) in [
+        (
+            "/at/%E6%B0%91%E5%9C%8B0%E5%B9%B41%E6%9C%881%E6%97%A5",
+            "start at 1",
+        ),
+        (
+            "/at/%E6%B0%91%E5%89%8D0%E5%B9%B41%E6%9C%881%E6%97%A5",
+            "start at 1",
+        ),
+        (
+            "/at/%E6%B0%91%E5%9C%8B-5%E5%B9%B41%E6%9C%881%E6%97%A5",
+            "start at 1",
+        ),
+        (
+            "/at/%E6%B0%91%E5%9C%8B2147483647%E5%B9%B41%E6%9C%881%E6%97%A5",
+            "out of range",
+        ),
+    ] {
+        let (status, json) = helpers::get_json(uri).await;
+        assert_eq!(status, 400, "{}", uri);
+        assert_eq!(json["error"], "invalid_instant", "{}", uri);
+        let message = json["message"].as_str().unwrap();
+        assert!(message.contains(reason), "{}: {}", uri, message);
+    }
+}
+
+// Expanded Year Tests
+#[actix_web::test]
+async fn test_bce_date_nulls_four_digit_formats() {
+    let (status, json) = helpers::get_json(
+        "/at/-0043-03-15?fields=full_iso,era,year_of_era,yyyymmdd,rfc2822_date_format",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(json["full_iso"], "-0043-03-15");
+    assert_eq!(json["era"], "BCE");
+    assert_eq!(json["year_of_era"], 44);
+    assert!(json["yyyymmdd"].is_null());
+    assert!(json["rfc2822_date_format"].is_null());
+    let reasons = json["null_reasons"].as_object().unwrap();
+    assert_eq!(
+        reasons.keys().collect::<Vec<_>>(),
+        ["yyyymmdd", "rfc2822_date_format"]
+    );
+}
+
+#[actix_web::test]
+async fn test_year_beyond_9999() {
+    let (status, json) = helpers::get_json("/at/+12345-01-01T00:00:00Z").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["full_iso"], "+12345-01-01");
+    assert_eq!(json["iso_year"], 12345);
+    assert_eq!(json["era"], "CE");
+    assert!(json["year_quad"].is_null());
+    assert!(json["null_reasons"]["year_quad"].is_string());
+}
+
+#[actix_web::test]
+async fn test_four_digit_years_have_no_null_reasons() {
+    let (_, json) = helpers::get_json("/at/2024-03-15T12:00:00Z").await;
+    assert!(json.get("null_reasons").is_none());
+}
+
+#[actix_web::test]
+async fn test_local_dates_past_the_ends_of_the_range() {
+    for uri in [
+        "/at/%2B262142-12-31T23:59:59Z?tz=%2B14:00",
+        "/at/-262143-01-01T00:00:00Z?tz=America/New_York",
+    ] {
+        let (status, json) = helpers::get_json(uri).await;
+        assert_eq!(status, 200, "{}", uri);
+        assert!(json["full_iso"].is_string(), "{}", uri);
+        assert!(json["japanese_era"].is_null(), "{}", uri);
+        let reason = &json["null_reasons"]["japanese_era"];
+        assert_eq!(
+            reason,
+            "the date in this zone is outside the supported range"
+        );
+    }
+}
+
+#[actix_web::test]
+async fn test_blocks_past_the_ends_of_the_range_are_null() {
+    let (status, json) = helpers::get_json(
+        "/at/-262143-01-01T00:00:00Z?tz=-12:00&fields=japanese_era\
+         &calendars=hebrew,chinese&spoken=true&week_rule=us",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(json["japanese_era"], Value::Null);
+    for block in ["japanese_era", "calendars", "spoken", "week_rule"] {
+        assert!(json["null_reasons"][block].is_string(), "{}", block);
+    }
+    for block in ["calendars", "spoken", "week_rule"] {
+        assert_eq!(json[block], Value::Null, "{}", block);
+    }
+}
+
+#[actix_web::test]
+async fn test_arithmetic_refuses_local_times_out_of_range() {
+    for uri in [
+        "/diff?start=-262143-01-01T00:00:00Z&end=2024-01-01T00:00:00Z&tz=-12:00",
+        "/add?instant=%2B262142-12-31T23:59:59Z&tz=%2B14:00&days=-1",
+        "/resolve?q=yesterday&now=%2B262142-12-31T23:59:59Z&tz=%2B14:00",
+    ] {
+        let (status, json) = helpers::get_json(uri).await;
+        assert_eq!(status, 400, "{}", uri);
+        assert_eq!(json["error"], "invalid_instant", "{}", uri);
+    }
+    let (status, json) = helpers::get_json(
+        "/add?instant=%2B262142-12-31T09:00:00Z&tz=%2B14:00&hours=1&clock=absolute",
+    )
+    .await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "out_of_range");
+    // Wall-clock results the zone's offset takes before the first instant
+    for uri in [
+        "/add?instant=-262143-01-01T20:00:00Z&tz=%2B05:00&days=-1",
+        "/resolve?q=yesterday%20at%201am&tz=%2B14:00&now=-262143-01-01T12:00:00Z",
+        "/resolve?q=next%20monday&now=%2B262142-12-31T12:00:00Z",
+        "/resolve?q=end%20of%20week&now=%2B262142-12-31T12:00:00Z",
+        "/resolve?q=in%20999999999999%20business%20days&now=2024-03-15T12:00:00Z",
+    ] {
+        let (status, json) = helpers::get_json(uri).await;
+        assert_eq!(status, 400, "{}", uri);
+        assert_eq!(json["error"], "out_of_range", "{}", uri);
+    }
+}
+
+// Astronomy Tests
+#[actix_web::test]
+async fn test_julian_dates_and_sidereal_time() {
+    let (status, json) = helpers::get_json(
+        "/at/1987-04-10T19:21:00Z?fields=julian_day,julian_date,modified_julian_date,\
+         greenwich_mean_sidereal_time&longitude=-77.0656",
+    )
+    .await;
+    assert_eq!(status, 200);
+    // Still the day of the year, for compatibility
+    assert_eq!(json["julian_day"], "100");
+    let julian_date = json["julian_date"].as_f64().unwrap();
+    assert!((julian_date - 2_446_896.306_25).abs() < 1e-8);
+    let mjd = json["modified_julian_date"].as_f64().unwrap();
+    assert!((mjd - 46_895.806_25).abs() < 1e-8);
+    // Meeus, Astronomical Algorithms, example 12.b: 8h34m57.0896s
+    let gmst = json["greenwich_mean_sidereal_time"].as_f64().unwrap();
+    let expected = 8.0 + 34.0 / 60.0 + 57.0896 / 3600.0;
+    assert!((gmst - expected).abs() * 3600.0 < 0.001);
+    let local = &json["local_sidereal_time"];
+    assert_eq!(local["longitude"], -77.0656);
+    let mean = local["mean"].as_f64().unwrap();
+    assert!((mean - (expected - 77.0656 / 15.0)).abs() * 3600.0 < 0.001);
+    assert!(local["apparent"].is_f64());
+}
+
+#[actix_web::test]
+async fn test_invalid_longitude_is_400() {
+    for longitude in ["200", "east", "NaN"] {
+        let (status, json) = helpers::get_json(&format!("/?longitude={}", longitude)).await;
+        assert_eq!(status, 400, "{}", longitude);
+        assert_eq!(json["error"], "invalid_longitude");
+    }
+}
+
+// Atomic Time Tests
+#[actix_web::test]
+async fn test_atomic_time_scales() {
+    let (status, json) = helpers::get_json(
+        "/at/2024-03-15T12:00:00Z?fields=tai,terrestrial_time,tai_utc_offset,gps_week,\
+         gps_time_of_week",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(json["tai"], "2024-03-15T12:00:37");
+    assert_eq!(json["terrestrial_time"], "2024-03-15T12:01:09.184");
+    assert_eq!(json["tai_utc_offset"], 37);
+    assert_eq!(json["gps_week"], 2305);
+    assert_eq!(json["gps_time_of_week"], 475_218.0);
+}
+
+#[actix_web::test]
+async fn test_atomic_time_is_null_before_1972() {
+    let (status, json) =
+        helpers::get_json("/at/1969-07-20T20:17:00Z?fields=tai,tai_utc_offset,gps_week").await;
+    assert_eq!(status, 200);
+    assert!(json["tai"].is_null());
+    assert!(json["tai_utc_offset"].is_null());
+    assert!(json["gps_week"].is_null());
+    let reasons = &json["null_reasons"];
+    assert!(reasons["tai"].as_str().unwrap().contains("since 1972"));
+    assert_eq!(reasons["tai_utc_offset"], reasons["tai"]);
+    assert!(reasons["gps_week"].as_str().unwrap().contains("1980-01-06"));
+}
+
+#[actix_web::test]
+async fn test_atomic_time_at_the_end_of_the_range() {
+    let (status, json) = helpers::get_json("/at/%2B262142-12-31T23:59:00Z").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["tai"], "+262142-12-31T23:59:37");
+    assert!(json["terrestrial_time"].is_null());
+    let reason = json["null_reasons"]["terrestrial_time"].as_str().unwrap();
+    assert!(reason.contains("past the end"), "{}", reason);
+    assert!(json["null_reasons"].get("tai").is_none());
+
+    let (status, json) =
+        helpers::get_json("/at/%2B262142-12-31T23:59:30Z?fields=tai,terrestrial_time").await;
+    assert_eq!(status, 200);
+    assert!(json["tai"].is_null());
+    assert_eq!(
+        json["null_reasons"]["tai"],
+        json["null_reasons"]["terrestrial_time"]
+    );
+}
+
+#[actix_web::test]
+async fn test_day_counts_and_sidereal_time_at_the_range_edges() {
+    for uri in [
+        "/at/-262143-01-01T00:00:00Z?tz=-12:00&longitude=10",
+        "/at/%2B262142-12-31T23:59:59Z?tz=%2B14:00&longitude=10",
+    ] {
+        let (status, json) = helpers::get_json(
+            &(uri.to_string() + "&fields=julian_date,rata_die,greenwich_mean_sidereal_time"),
+        )
+        .await;
+        assert_eq!(status, 200, "{}", uri);
+        assert!(json["rata_die"].is_i64(), "{}", uri);
+        assert!(json["local_sidereal_time"]["mean"].is_f64(), "{}", uri);
+    }
+}
+
+// Field Projection Tests
+#[actix_web::test]
+async fn test_fields_projection() {
+    let (status, json) =
+        helpers::get_json("/at/1710504045?fields=unix_timestamp,rfc3339_date_format_millis_z")
+            .await;
+    assert_eq!(status, 200);
+    let obj = json.as_object().unwrap();
+    assert_eq!(obj.len(), 2);
+    assert_eq!(obj["unix_timestamp"], 1710504045);
+    assert_eq!(
+        obj["rfc3339_date_format_millis_z"],
+        "2024-03-15T12:00:45.000Z"
+    );
+}
+
+#[actix_web::test]
+async fn test_fields_projection_honours_tz() {
+    let (status, json) = helpers::get_json("/?fields=timezone_name&tz=UTC").await;
+    assert_eq!(status, 200);
+    assert_eq!(json, serde_json::json!({"timezone_name": "UTC"}));
+}
+
+#[actix_web::test]
+async fn test_full_response_keeps_field_order() {
+    let json = helpers::get_root().await;
+    let keys: Vec<_> = json.as_object().unwrap().keys().cloned().collect();
+    assert_eq!(keys.first().map(String::as_str), Some("yyyy_mm_dd"));
+    // Fields added since are appended, so the original ones keep their places
+    assert_eq!(keys.last().map(String::as_str), Some("gps_time_of_week"));
+    let position = |name: &str| keys.iter().position(|key| key == name).unwrap();
+    let original_end = position("verbose_date");
+    assert_eq!(original_end, 60);
+    let appended = [
+        "japanese_era",
+        "roc_year_chinese",
+        "era",
+        "year_of_era",
+        "julian_date",
+        "greenwich_apparent_sidereal_time",
+        "tai",
+        "gps_time_of_week",
+    ];
+    for appended in appended {
+        assert!(position(appended) > original_end, "{}", appended);
+    }
+}
+
+#[actix_web::test]
+async fn test_unknown_fields_are_listed() {
+    let (status, json) = helpers::get_json("/?fields=unix_timestamp,bogus,nope").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "unknown_fields");
+    assert_eq!(json["unknown"], serde_json::json!(["bogus", "nope"]));
+    assert_eq!(json["valid"].as_array().unwrap().len(), Field::ALL.len());
+}
+
+// Single Field Tests
+#[actix_web::test]
+async fn test_field_is_plain_text() {
+    let (status, body, content_type) = helpers::get_text("/f/yyyymmdd?at=1710504045").await;
+    assert_eq!(status, 200);
+    assert!(content_type.starts_with("text/plain"));
+    assert_eq!(body, "20240315");
+}
+
+#[actix_web::test]
+async fn test_field_numeric_value_is_unquoted() {
+    let (status, body, _) = helpers::get_text("/f/unix_timestamp?at=@1710504045000ms").await;
+    assert_eq!(status, 200);
+    assert_eq!(body, "1710504045");
+}
+
+#[actix_web::test]
+async fn test_field_honours_tz() {
+    let (status, body, _) =
+        helpers::get_text("/f/military_time?at=2024-03-15T12:30:45Z&tz=Asia/Kolkata").await;
+    assert_eq!(status, 200);
+    assert_eq!(body, "18:00");
+}
+
+#[actix_web::test]
+async fn test_field_unknown_is_404_with_valid_names() {
+    let (status, json) = helpers::get_json("/f/not_a_field").await;
+    assert_eq!(status, 404);
+    assert_eq!(json["error"], "unknown_field");
+    assert_eq!(json["valid"].as_array().unwrap().len(), Field::ALL.len());
+}
+
+#[actix_web::test]
+async fn test_field_without_a_value_is_422_with_the_reason() {
+    for (uri, field, reason) in [
+        ("/f/yyyymmdd?at=%2B12345-01-01", "yyyymmdd", "0000 to 9999"),
+        ("/f/tai?at=1969-07-20T20:17:00Z", "tai", "since 1972"),
+        (
+            "/f/gps_week?at=1975-01-01T00:00:00Z",
+            "gps_week",
+            "1980-01-06",
+        ),
+        (
+            "/f/japanese_era?at=1850-01-01T00:00:00Z",
+            "japanese_era",
+            "Meiji",
+        ),
+    ] {
+        let (status, json) = helpers::get_json(uri).await;
+        assert_eq!(status, 422, "{}", uri);
+        assert_eq!(json["error"], "null_field", "{}", uri);
+        assert_eq!(json["field"], field, "{}", uri);
+        assert!(json["reason"].as_str().unwrap().contains(reason), "{}", uri);
+        assert!(json["message"].as_str().unwrap().contains(field), "{}", uri);
+    }
+}
+
+#[actix_web::test]
+async fn test_root_honours_at() {
+    let (status, json) = helpers::get_json("/?at=0&fields=full_iso").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["full_iso"], "1970-01-01");
+}
+
+// Custom Format Tests
+#[actix_web::test]
+async fn test_format_pattern() {
+    let (status, json) =
+        helpers::get_json("/format?pattern=%25Y-%25m-%25dT%25H&at=1710504045").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["pattern"], "%Y-%m-%dT%H");
+    assert_eq!(json["formatted"], "2024-03-15T12");
+}
+
+#[actix_web::test]
+async fn test_format_pattern_honours_tz() {
+    let (status, json) =
+        helpers::get_json("/format?pattern=%25H:%25M%20%25Z&at=1710504045&tz=Europe/Berlin").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["formatted"], "13:00 CET");
+}
+
+#[actix_web::test]
+async fn test_format_invalid_specifier_is_named() {
+    let (status, json) = helpers::get_json("/format?pattern=%25Y-%25Q").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_pattern");
+    assert_eq!(json["token"], "%Q");
+    assert_eq!(json["position"], 3);
+}
+
+#[actix_web::test]
+async fn test_format_missing_pattern() {
+    let (status, json) = helpers::get_json("/format").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "missing_parameter");
+}
+
+#[actix_web::test]
+async fn test_format_pattern_too_long() {
+    let uri = format!("/format?pattern={}", "x".repeat(300));
+    let (status, json) = helpers::get_json(&uri).await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "pattern_too_long");
+}
+
+#[actix_web::test]
+async fn test_format_two_digit_year_outside_0000_to_9999() {
+    let (status, json) =
+        helpers::get_json("/format?pattern=%25Y%20%25C%25y%20%25x&at=%2B12345-01-01").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "unrepresentable_year");
+    assert_eq!(json["token"], "%C");
+    assert_eq!(json["position"], 3);
+    let message = json["message"].as_str().unwrap();
+    assert!(message.contains("year 12345"), "{}", message);
+    assert!(message.contains("0000 to 9999"), "{}", message);
+
+    let (status, json) =
+        helpers::get_json("/format?pattern=%25x%20%25D%20%25C&at=-0044-03-15").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["token"], "%x");
+
+    let (status, json) = helpers::get_json("/format?pattern=%25Y-%25m-%25d&at=-0044-03-15").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["formatted"], "-0044-03-15");
+}
+
+// Content Negotiation Tests
+#[actix_web::test]
+async fn test_accept_any_keeps_json() {
+    let (status, content_type, body) = helpers::get_accept("/", "*/*").await;
+    assert_eq!(status, 200);
+    assert!(content_type.starts_with("application/json"));
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json.as_object().unwrap().len(), Field::ALL.len());
+}
+
+#[actix_web::test]
+async fn test_accept_csv() {
+    let (status, content_type, body) =
+        helpers::get_accept("/at/1710504045?fields=yyyymmdd,unix_timestamp", "text/csv").await;
+    assert_eq!(status, 200);
+    assert!(content_type.starts_with("text/csv"));
+    assert_eq!(
+        String::from_utf8(body).unwrap(),
+        "yyyymmdd,unix_timestamp\r\n20240315,1710504045\r\n"
+    );
+}
+
+#[actix_web::test]
+async fn test_accept_csv_full_response_has_every_column() {
+    let (_, _, body) = helpers::get_accept("/", "text/csv").await;
+    let csv = String::from_utf8(body).unwrap();
+    let header = csv.lines().next().unwrap();
+    assert_eq!(header.split(',').count(), Field::ALL.len());
+}
+
+#[actix_web::test]
+async fn test_accept_xml() {
+    let (status, content_type, body) =
+        helpers::get_accept("/at/1710504045?fields=full_iso", "application/xml").await;
+    assert_eq!(status, 200);
+    assert!(content_type.starts_with("application/xml"));
+    let xml = String::from_utf8(body).unwrap();
+    assert!(xml.contains("<timestamp>"));
+    assert!(xml.contains("<full_iso>2024-03-15</full_iso>"));
+}
+
+#[actix_web::test]
+async fn test_accept_yaml() {
+    let (status, content_type, body) = helpers::get_accept(
+        "/at/1710504045?fields=yyyymmdd,iso_week",
+        "application/yaml",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert!(content_type.starts_with("application/yaml"));
+    assert_eq!(
+        String::from_utf8(body).unwrap(),
+        "---\nyyyymmdd: \"20240315\"\niso_week: 11\n"
+    );
+}
+
+#[actix_web::test]
+async fn test_format_param_overrides_accept() {
+    let (status, content_type, body) =
+        helpers::get_accept("/at/0?fields=full_iso&format=text", "application/json").await;
+    assert_eq!(status, 200);
+    assert!(content_type.starts_with("text/plain"));
+    assert_eq!(String::from_utf8(body).unwrap(), "full_iso=1970-01-01\n");
+}
+
+#[actix_web::test]
+async fn test_unsupported_accept_is_406() {
+    let (status, _, body) = helpers::get_accept("/", "image/png").await;
+    assert_eq!(status, 406);
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"], "not_acceptable");
+}
+
+#[actix_web::test]
+async fn test_unknown_format_param_is_400() {
+    let (status, json) = helpers::get_json("/?format=pdf").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "unknown_format");
+    assert!(json["supported"]
+        .as_array()
+        .unwrap()
+        .contains(&"csv".into()));
+}
+
+#[actix_web::test]
+async fn test_malformed_query_is_json() {
+    let (status, json) = helpers::get_json("/add?instant=2024-01-31&days=abc").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_query");
+    assert_eq!(
+        json["message"],
+        "invalid query string 'instant=2024-01-31&days=abc': invalid digit found in string"
+    );
+
+    let (status, json) = helpers::get_json("/at/0?tz=UTC&tz=Asia/Tokyo").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_query");
+    assert!(json["message"]
+        .as_str()
+        .unwrap()
+        .contains("duplicate field"));
+}
+
+// Binary Encoding Tests
+#[actix_web::test]
+async fn test_msgpack_round_trips_to_json() {
+    let (_, json) = helpers::get_json("/at/@1710504045123ms").await;
+    let (status, content_type, body) =
+        helpers::get_accept("/at/@1710504045123ms", "application/msgpack").await;
+    assert_eq!(status, 200);
+    assert_eq!(content_type, "application/msgpack");
+
+    let decoded: Value = rmp_serde::from_slice(&body).expect("valid MessagePack");
+    assert_eq!(decoded, json);
+    assert!(decoded["unix_timestamp"].is_i64());
+    assert!(decoded["quarter_of_the_year"].is_u64());
+}
+
+#[actix_web::test]
+async fn test_cbor_round_trips_to_json() {
+    let (_, json) = helpers::get_json("/at/@1710504045123ms").await;
+    let (status, content_type, body) =
+        helpers::get_accept("/at/@1710504045123ms", "application/cbor").await;
+    assert_eq!(status, 200);
+    assert_eq!(content_type, "application/cbor");
+
+    let decoded: Value = ciborium::from_reader(body.as_slice()).expect("valid CBOR");
+    assert_eq!(decoded, json);
+    assert!(decoded["unix_timestamp"].is_i64());
+}
+
+#[actix_web::test]
+async fn test_binary_format_param() {
+    let (status, content_type, body) =
+        helpers::get_accept("/at/0?fields=unix_timestamp&format=msgpack", "*/*").await;
+    assert_eq!(status, 200);
+    assert_eq!(content_type, "application/msgpack");
+    // fixmap(1), fixstr(14) "unix_timestamp", positive fixint 0
+    assert_eq!(body[0], 0x81);
+    assert_eq!(body[1], 0xae);
+    assert_eq!(&body[2..16], b"unix_timestamp");
+    assert_eq!(body[16], 0x00);
+}
+
+// Zone Conversion Tests
+#[actix_web::test]
+async fn test_convert_unique() {
+    let (status, json) =
+        helpers::get_json("/convert?from=Europe/Berlin&to=Asia/Tokyo&time=2024-03-15T12:00").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["status"], "unique");
+    assert_eq!(json["candidates"][0]["to"], "2024-03-15T20:00:00+09:00");
+    assert!(json["candidates"][0].get("fold").is_none());
+}
+
+#[actix_web::test]
+async fn test_convert_ambiguous() {
+    let (status, json) =
+        helpers::get_json("/convert?from=America/New_York&to=UTC&time=2024-11-03T01:30").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["status"], "ambiguous");
+    let candidates = json["candidates"].as_array().unwrap();
+    assert_eq!(candidates.len(), 2);
+    assert_eq!(candidates[0]["fold"], "earlier");
+    assert_eq!(candidates[0]["from_abbreviation"], "EDT");
+    assert_eq!(candidates[1]["fold"], "later");
+    assert_eq!(candidates[1]["from_abbreviation"], "EST");
+}
+
+#[actix_web::test]
+async fn test_convert_nonexistent() {
+    let (status, json) =
+        helpers::get_json("/convert?from=Europe/Berlin&to=Asia/Tokyo&time=2024-03-31T02:30").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "nonexistent_local_time");
+    assert_eq!(json["nearest"][0]["from"], "2024-03-31T01:59:59+01:00");
+    assert_eq!(json["nearest"][1]["from"], "2024-03-31T03:00:00+02:00");
+}
+
+#[actix_web::test]
+async fn test_convert_missing_parameter() {
+    let (status, json) = helpers::get_json("/convert?from=UTC&time=2024-03-15T12:00").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "missing_parameter");
+    assert!(json["message"].as_str().unwrap().contains("'to'"));
+}
+
+#[actix_web::test]
+async fn test_convert_at_the_range_edges() {
+    for uri in [
+        "/convert?from=UTC&to=%2B14:00&time=%2B262142-12-31T23:00",
+        "/convert?from=%2B14:00&to=UTC&time=-262143-01-01T01:00",
+        "/convert?from=UTC&to=Pacific/Kiritimati&time=%2B262142-12-31T23:00",
+    ] {
+        let (status, json) = helpers::get_json(uri).await;
+        assert_eq!(status, 400, "{}", uri);
+        assert_eq!(json["error"], "out_of_range", "{}", uri);
+    }
+    let (status, json) =
+        helpers::get_json("/convert?from=UTC&to=-12:00&time=-262143-01-02T00:00").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["candidates"][0]["to"], "-262143-01-01T12:00:00-12:00");
+}
+
+// Difference Tests
+#[actix_web::test]
+async fn test_diff_breakdown() {
+    let (status, json) =
+        helpers::get_json("/diff?start=2023-01-01T00:00:00Z&end=2024-03-04T04:00:00Z").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["total_seconds"], 36_993_600);
+    assert_eq!(json["total_milliseconds"], 36_993_600_000i64);
+    assert_eq!(json["calendar"]["years"], 1);
+    assert_eq!(json["calendar"]["months"], 2);
+    assert_eq!(json["iso8601"], "P1Y2M3DT4H");
+    assert_eq!(json["human"], "1 year, 2 months, 3 days and 4 hours");
+}
+
+#[actix_web::test]
+async fn test_diff_negative_unix_inputs() {
+    let (status, json) = helpers::get_json("/diff?start=86400&end=@43200000ms").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["total_seconds"], -43_200);
+    assert_eq!(json["total_days"], -0.5);
+    assert_eq!(json["calendar"]["negative"], true);
+    assert_eq!(json["iso8601"], "-PT12H");
+}
+
+#[actix_web::test]
+async fn test_diff_across_dst_in_zone() {
+    let (status, json) = helpers::get_json(
+        "/diff?start=2024-03-30T11:00:00Z&end=2024-03-31T10:00:00Z&tz=Europe/Berlin",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(json["total_seconds"], 82_800);
+    assert_eq!(json["iso8601"], "P1D");
+    assert_eq!(json["start"], "2024-03-30T12:00:00+01:00");
+}
+
+#[actix_web::test]
+async fn test_diff_errors() {
+    let (status, json) = helpers::get_json("/diff?start=2024-03-15T12:00:00Z").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "missing_parameter");
+    let (status, json) = helpers::get_json("/diff?start=nope&end=0").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_instant");
+}
+
+// Date Arithmetic Tests
+#[actix_web::test]
+async fn test_add_months_and_days() {
+    let (status, json) = helpers::get_json("/add?instant=2024-01-31&months=1&days=-3").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["month_end"], "clamp");
+    assert_eq!(json["month_end_adjusted"], true);
+    assert_eq!(json["clock"], "wall");
+    assert_eq!(json["result"]["yyyy_mm_dd"], "2024_02_26");
+    assert_eq!(json["result"]["weekday"], "Monday");
+}
+
+#[actix_web::test]
+async fn test_add_iso_duration_with_fields() {
+    let (status, json) = helpers::get_json(
+        "/add?instant=2024-01-31T00:00:00Z&duration=P1M3D&month_end=overflow&fields=yyyy_mm_dd",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(json["offset"]["months"], 1);
+    assert_eq!(json["offset"]["days"], 3);
+    assert_eq!(json["result"]["yyyy_mm_dd"], "2024_03_05");
+    assert_eq!(json["result"].as_object().unwrap().len(), 1);
+}
+
+#[actix_web::test]
+async fn test_add_clock_across_dst() {
+    let base = "/add?instant=2024-03-30T12:00&tz=Europe/Berlin&days=1&fields=rfc3339_date_format";
+    let (_, wall) = helpers::get_json(base).await;
+    assert_eq!(
+        wall["result"]["rfc3339_date_format"],
+        "2024-03-31T12:00:00+02:00"
+    );
+    assert_eq!(wall["resolution"], "unique");
+    let (_, absolute) = helpers::get_json(&format!("{}&clock=absolute", base)).await;
+    assert_eq!(absolute["clock"], "absolute");
+    assert_eq!(
+        absolute["result"]["rfc3339_date_format"],
+        "2024-03-31T13:00:00+02:00"
+    );
+    assert!(absolute.get("resolution").is_none());
+}
+
+#[actix_web::test]
+async fn test_add_errors() {
+    let (status, json) =
+        helpers::get_json("/add?instant=2024-01-31&months=1&month_end=error").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "day_out_of_range");
+
+    let (status, json) = helpers::get_json("/add?duration=P1H").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_duration");
+
+    let (status, json) = helpers::get_json("/add?days=1&month_end=round").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_parameter");
+    assert_eq!(
+        json["valid"],
+        serde_json::json!(["clamp", "overflow", "error"])
+    );
+}
+
+// Timestamp Parsing Tests
+#[actix_web::test]
+async fn test_parse_ambiguous_day_month() {
+    let (status, json) = helpers::post_body(
+        "/parse?fields=rfc3339_date_format",
+        "text/plain",
+        "03/04/24 5pm",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(json["status"], "ambiguous");
+    let candidates = json["candidates"].as_array().unwrap();
+    assert_eq!(candidates.len(), 2);
+    assert_eq!(candidates[0]["formats"][0], "%m/%d/%y %-I%P");
+    assert_eq!(
+        candidates[0]["timestamp"]["rfc3339_date_format"],
+        "2024-03-04T17:00:00+00:00"
+    );
+    assert_eq!(
+        candidates[1]["timestamp"]["rfc3339_date_format"],
+        "2024-04-03T17:00:00+00:00"
+    );
+}
+
+#[actix_web::test]
+async fn test_parse_rfc2822_is_unique() {
+    let (status, json) =
+        helpers::post_body("/parse", "text/plain", "Fri, 15 Mar 2024 12:30:45 +0000").await;
+    assert_eq!(status, 200);
+    assert_eq!(json["status"], "unique");
+    assert_eq!(json["candidates"][0]["formats"][0], "RFC 2822");
+    assert_eq!(
+        json["candidates"][0]["timestamp"]["unix_timestamp"],
+        1710505845
+    );
+}
+
+#[actix_web::test]
+async fn test_parse_json_body_in_zone() {
+    let (status, json) = helpers::post_body(
+        "/parse?tz=Asia/Tokyo&fields=rfc3339_date_format",
+        "application/json",
+        r#"{"input": "2024-W11-5"}"#,
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(json["zone"], "Asia/Tokyo");
+    assert_eq!(
+        json["candidates"][0]["timestamp"]["rfc3339_date_format"],
+        "2024-03-15T00:00:00+09:00"
+    );
+    assert_eq!(json["candidates"][0]["fields"][0], "iso_week_date_format");
+}
+
+#[actix_web::test]
+async fn test_parse_unrecognized() {
+    let (status, json) = helpers::post_body("/parse", "text/plain", "half past never").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "unrecognized_timestamp");
+}
+
+// Relative Expression Tests
+#[actix_web::test]
+async fn test_resolve_next_friday() {
+    let (status, json) = helpers::get_json(
+        "/resolve?q=next%20friday%20at%209am&tz=Europe/London&now=2024-03-29T15:00:00Z&fields=rfc3339_date_format",
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(json["query"], "next friday at 9am");
+    assert_eq!(json["now"], "2024-03-29T15:00:00+00:00");
+    // Clocks go forward in London on 2024-03-31
+    assert_eq!(
+        json["result"]["rfc3339_date_format"],
+        "2024-04-05T09:00:00+01:00"
+    );
+}
+
+#[actix_web::test]
+async fn test_resolve_business_days_and_quarter() {
+    let (_, json) = helpers::get_json(
+        "/resolve?q=in+3+business+days&now=2024-03-15T10:00:00Z&fields=weekday,full_iso",
+    )
+    .await;
+    assert_eq!(json["result"]["full_iso"], "2024-03-20");
+    assert_eq!(json["result"]["weekday"], "Wednesday");
+
+    let (_, json) =
+        helpers::get_json("/resolve?q=end+of+quarter&now=2024-05-02&fields=full_iso").await;
+    assert_eq!(json["result"]["full_iso"], "2024-06-30");
+}
+
+#[actix_web::test]
+async fn test_resolve_points_at_offending_word() {
+    let (status, json) = helpers::get_json("/resolve?q=next+fryday+at+9am").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "invalid_expression");
+    assert_eq!(json["word"], "fryday");
+    assert_eq!(json["position"], 5);
+}
+
+#[actix_web::test]
+async fn test_resolve_missing_query() {
+    let (status, json) = helpers::get_json("/resolve?tz=UTC").await;
+    assert_eq!(status, 400);
+    assert_eq!(json["error"], "missing_parameter");
+}